@@ -0,0 +1,477 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use bevy_ecs::prelude::Resource;
+use clap::builder::PossibleValue;
+use clap::ValueEnum;
+use rand::Rng;
+use rand_chacha::ChaCha20Rng;
+
+use crate::game::{
+    GameAction, GameLogEntry, GameState, MinAttackDice, PlayerId, RegionId, Rules, TieRule,
+};
+use crate::tiered_prng::get_randomness;
+
+/// Maximum number of attacks simulated in a single playout
+const MAX_PLAYOUT_DEPTH: usize = 8;
+/// Attacks searched at each node of a minimax search, the likeliest to be won first
+const MAX_SEARCH_BRANCHING: usize = 6;
+/// Playouts per decision of [`AiStrategy::MonteCarlo`] unless told otherwise
+pub const DEFAULT_ROLLOUTS: usize = 200;
+/// Opponent replies [`AiStrategy::Minimax`] looks ahead unless told otherwise
+pub const DEFAULT_SEARCH_DEPTH: usize = 2;
+
+/// How an AI player picks its moves
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AiStrategy {
+    /// Run random playouts of the rest of the turn for each possible attack and pick the one
+    /// with the best average outcome. `rollouts` is the total number of playouts per decision.
+    MonteCarlo { rollouts: usize },
+    /// Pick the attack with the biggest dice advantage. Attacks that can't be won are
+    /// never made, so the player may pass with moves left.
+    Greedy,
+    /// Look `depth` opponent replies ahead with [`best_move_minimax`]
+    Minimax { depth: usize },
+}
+
+/// Strategies picked by name on the command line, with the default rollouts and depth
+impl ValueEnum for AiStrategy {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            AiStrategy::MonteCarlo {
+                rollouts: DEFAULT_ROLLOUTS,
+            },
+            AiStrategy::Greedy,
+            AiStrategy::Minimax {
+                depth: DEFAULT_SEARCH_DEPTH,
+            },
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(match self {
+            AiStrategy::MonteCarlo { .. } => PossibleValue::new("monte-carlo")
+                .help("Random playouts of the rest of the turn, see `--ai-rollouts`"),
+            AiStrategy::Greedy => PossibleValue::new("greedy").help("Biggest dice advantage first"),
+            AiStrategy::Minimax { .. } => PossibleValue::new("minimax")
+                .help("Best expected outcome against the best replies, see `--ai-depth`"),
+        })
+    }
+}
+
+/// What a [`DiceBot`] gets to know besides the game: the rules of the match and the AI
+/// randomness, so games with bots replay exactly from their seeds
+pub struct BotContext<'a> {
+    pub rules: &'a Rules,
+    pub rng: &'a mut ChaCha20Rng,
+}
+
+/// Strategy of an AI player, built in or supplied from outside the game. A bot is asked
+/// for an attack `(region_1, region_2)` of the current player of `state`, or `None` to
+/// end the turn.
+pub trait DiceBot: Send {
+    fn choose(
+        &mut self,
+        state: &GameState,
+        context: &mut BotContext,
+    ) -> Option<(RegionId, RegionId)>;
+}
+
+impl DiceBot for AiStrategy {
+    fn choose(
+        &mut self,
+        state: &GameState,
+        context: &mut BotContext,
+    ) -> Option<(RegionId, RegionId)> {
+        choose_move_with_rules(state, self, context.rules, context.rng)
+    }
+}
+
+/// Bot attacking from any region that can still attack, at random
+pub struct RandomBot;
+
+impl DiceBot for RandomBot {
+    fn choose(
+        &mut self,
+        state: &GameState,
+        context: &mut BotContext,
+    ) -> Option<(RegionId, RegionId)> {
+        let board = &state.board;
+        let acted: HashSet<RegionId> = board
+            .regions
+            .iter()
+            .map(|region| region.id)
+            .filter(|id| !state.can_attack(*id, context.rules.capture_bonus))
+            .collect();
+
+        let attacks = available_attacks(state, &acted, context.rules.min_attack_dice);
+        match attacks.is_empty() {
+            true => None,
+            false => Some(attacks[context.rng.gen_range(0..attacks.len())]),
+        }
+    }
+}
+
+/// Resource that maps player indices to the bots of AI controlled players
+#[derive(Resource, Default, Clone)]
+pub struct AiPlayers {
+    pub bots: HashMap<PlayerId, Arc<Mutex<Box<dyn DiceBot>>>>,
+}
+
+impl AiPlayers {
+    /// Seat a bot for a player
+    pub fn add_bot(&mut self, player: PlayerId, bot: Box<dyn DiceBot>) {
+        self.bots.insert(player, Arc::new(Mutex::new(bot)));
+    }
+
+    /// Whether a player is controlled by a bot
+    pub fn is_ai(&self, player: PlayerId) -> bool {
+        self.bots.contains_key(&player)
+    }
+
+    /// Attack of the current player picked by its bot, `None` for human players.
+    /// See [`choose_legal_move`].
+    pub fn choose_move(
+        &self,
+        game_state: &GameState,
+        rules: &Rules,
+        rng: &mut ChaCha20Rng,
+    ) -> Option<(RegionId, RegionId)> {
+        let bot = self.bots.get(&game_state.turn_of_player)?;
+        choose_legal_move(bot.lock().unwrap().as_mut(), game_state, rules, rng)
+    }
+}
+
+/// Attack picked by `bot` for the current player. Moves that aren't legal under the rules
+/// are dropped, so the player passes.
+pub fn choose_legal_move(
+    bot: &mut dyn DiceBot,
+    game_state: &GameState,
+    rules: &Rules,
+    rng: &mut ChaCha20Rng,
+) -> Option<(RegionId, RegionId)> {
+    let regions = &game_state.board.regions;
+    bot.choose(game_state, &mut BotContext { rules, rng })
+        .filter(
+            |(region_1, region_2)| match (regions.get(region_1.0), regions.get(region_2.0)) {
+                (Some(region_1), Some(region_2)) => {
+                    game_state.is_legal_move_with_rules(region_1, region_2, rules)
+                }
+                _ => false,
+            },
+        )
+}
+
+/// Pick an attack `(region_1, region_2)` for the current player, or `None` if there is no move
+pub fn choose_move(
+    game_state: &GameState,
+    strategy: &AiStrategy,
+    rng: &mut impl Rng,
+) -> Option<(RegionId, RegionId)> {
+    choose_move_with_rules(game_state, strategy, &Rules::default(), rng)
+}
+
+/// Same as [`choose_move`], but moves follow `rules`: regions allowed another attack by
+/// the capture bonus are considered too and attackers need the least number of dice
+pub fn choose_move_with_rules(
+    game_state: &GameState,
+    strategy: &AiStrategy,
+    rules: &Rules,
+    rng: &mut impl Rng,
+) -> Option<(RegionId, RegionId)> {
+    match strategy {
+        AiStrategy::MonteCarlo { rollouts } => monte_carlo(game_state, *rollouts, rules, rng),
+        AiStrategy::Greedy => greedy(game_state, rules, rng),
+        AiStrategy::Minimax { depth } => minimax(game_state, *depth, rules),
+    }
+}
+
+fn greedy(
+    game_state: &GameState,
+    rules: &Rules,
+    rng: &mut impl Rng,
+) -> Option<(RegionId, RegionId)> {
+    let regions = &game_state.board.regions;
+    let acted: HashSet<RegionId> = regions
+        .iter()
+        .map(|region| region.id)
+        .filter(|id| !game_state.can_attack(*id, rules.capture_bonus))
+        .collect();
+
+    let advantage = |(region_1, region_2): (RegionId, RegionId)| -> isize {
+        regions[region_1].num_dice as isize - regions[region_2].num_dice as isize
+    };
+
+    let candidates: Vec<(RegionId, RegionId)> =
+        available_attacks(game_state, &acted, rules.min_attack_dice)
+            .into_iter()
+            .filter(|(region_1, region_2)| {
+                regions[*region_1]
+                    .attack_win_probability_with(&regions[*region_2], game_state.dice_kind)
+                    > 0.0
+            })
+            .collect();
+
+    let best_advantage = candidates.iter().map(|c| advantage(*c)).max()?;
+    let best: Vec<(RegionId, RegionId)> = candidates
+        .into_iter()
+        .filter(|c| advantage(*c) == best_advantage)
+        .collect();
+
+    Some(best[rng.gen_range(0..best.len())])
+}
+
+fn monte_carlo(
+    game_state: &GameState,
+    rollouts: usize,
+    rules: &Rules,
+    rng: &mut impl Rng,
+) -> Option<(RegionId, RegionId)> {
+    let board = &game_state.board;
+    let acted: HashSet<RegionId> = board
+        .regions
+        .iter()
+        .map(|region| region.id)
+        .filter(|id| !game_state.can_attack(*id, rules.capture_bonus))
+        .collect();
+
+    let candidates = available_attacks(game_state, &acted, rules.min_attack_dice);
+    if candidates.is_empty() {
+        return None;
+    }
+    let search = search_state(game_state);
+
+    // Spread the budget evenly over candidates
+    let mut totals = vec![0.0; candidates.len()];
+    let mut counts = vec![0usize; candidates.len()];
+    for i in 0..rollouts {
+        let candidate = i % candidates.len();
+        totals[candidate] += playout(&search, candidates[candidate], &acted, rules, rng);
+        counts[candidate] += 1;
+    }
+
+    let average = |i: usize| match counts[i] {
+        0 => f64::MIN,
+        count => totals[i] / count as f64,
+    };
+
+    // Break ties with the AI RNG rather than candidate order, so replays stay exact
+    let best_average = (0..candidates.len()).map(average).fold(f64::MIN, f64::max);
+    let best: Vec<usize> = (0..candidates.len())
+        .filter(|i| average(*i) == best_average)
+        .collect();
+
+    Some(candidates[best[rng.gen_range(0..best.len())]])
+}
+
+/// Score of a board for `player`: number of regions they own plus the size of their
+/// largest connected group, which is what they get reinforced with
+pub fn board_value(state: &GameState, player: PlayerId) -> f64 {
+    (state.player_regions.regions_of(player).len() + state.largest_connected_group(player)) as f64
+}
+
+/// Attack of the current player with the best expected [`board_value`] after opponents
+/// answer with their best attacks `depth` times. Each clash is weighed by its odds instead
+/// of rolling dice. `None` if no attack has a chance to win.
+pub fn best_move_minimax(state: &GameState, depth: usize) -> Option<(RegionId, RegionId)> {
+    minimax(state, depth, &Rules::default())
+}
+
+fn minimax(state: &GameState, depth: usize, rules: &Rules) -> Option<(RegionId, RegionId)> {
+    let player = state.turn_of_player;
+
+    // The first of equally valued attacks wins, so the search stays deterministic
+    let search = search_state(state);
+    let mut best: Option<((RegionId, RegionId), f64)> = None;
+    for attack in search_candidates(state, rules) {
+        let value = expected_value(&search, attack, player, depth, rules);
+        if best.is_none_or(|(_, best_value)| value > best_value) {
+            best = Some((attack, value));
+        }
+    }
+
+    best.map(|(attack, _)| attack)
+}
+
+/// Copy of a game to be searched, cloned again at every node. Searches tell which regions
+/// acted this turn from the game itself, so the copy leaves out the game log along with the
+/// hex lookup of the board.
+fn search_state(state: &GameState) -> GameState {
+    let mut board = state.board.clone();
+    board.hexes = HashMap::new();
+
+    GameState {
+        board,
+        game_log: Vec::new(),
+        ..state.clone()
+    }
+}
+
+/// Attacks of the current player with a chance to win, likeliest first
+fn search_candidates(state: &GameState, rules: &Rules) -> Vec<(RegionId, RegionId)> {
+    let regions = &state.board.regions;
+    let acted: HashSet<RegionId> = regions
+        .iter()
+        .map(|region| region.id)
+        .filter(|id| !state.can_attack(*id, rules.capture_bonus))
+        .collect();
+
+    let odds = |(region_1, region_2): (RegionId, RegionId)| {
+        regions[region_1].attack_win_probability_with(&regions[region_2], state.dice_kind)
+    };
+
+    let mut attacks: Vec<(RegionId, RegionId)> =
+        available_attacks(state, &acted, rules.min_attack_dice)
+            .into_iter()
+            .filter(|attack| odds(*attack) > 0.0)
+            .collect();
+    attacks.sort_by(|a, b| odds(*b).total_cmp(&odds(*a)));
+    attacks.truncate(MAX_SEARCH_BRANCHING);
+    attacks
+}
+
+/// [`board_value`] for `player` after an attack, averaged over winning and losing the clash
+fn expected_value(
+    state: &GameState,
+    (region_1, region_2): (RegionId, RegionId),
+    player: PlayerId,
+    depth: usize,
+    rules: &Rules,
+) -> f64 {
+    let regions = &state.board.regions;
+    let p = regions[region_1].attack_win_probability_with(&regions[region_2], state.dice_kind);
+
+    let mut value = 0.0;
+    for (attacker_won, probability) in [(true, p), (false, 1.0 - p)] {
+        if probability == 0.0 {
+            continue;
+        }
+
+        // Dice moved by a clash don't change the value of a board, a fixed seed
+        // keeps the later odds of the search the same between calls
+        let mut next = state.clone();
+        next.apply_clash(region_1, region_2, attacker_won, &mut get_randomness(1))
+            .expect("searched attacks are made on bordering opponents");
+        value += probability * reply_value(&next, player, depth, rules);
+    }
+
+    value
+}
+
+/// [`board_value`] for `player` once the next player makes their best attack, or passes
+fn reply_value(state: &GameState, player: PlayerId, depth: usize, rules: &Rules) -> f64 {
+    if depth == 0 || state.is_game_over().is_some() {
+        return board_value(state, player);
+    }
+
+    let mut next = state.clone();
+    next.turn_of_player = next.next_player();
+    next.turn_counter += 1;
+
+    let pass = board_value(&next, player);
+    let values = search_candidates(&next, rules)
+        .into_iter()
+        .map(|attack| expected_value(&next, attack, player, depth - 1, rules));
+
+    match next.turn_of_player == player {
+        true => values.fold(pass, f64::max),
+        false => values.fold(pass, f64::min),
+    }
+}
+
+/// Attacks of the current player from regions which haven't acted this turn
+/// and have enough dice
+fn available_attacks(
+    game_state: &GameState,
+    acted: &HashSet<RegionId>,
+    min_attack_dice: MinAttackDice,
+) -> Vec<(RegionId, RegionId)> {
+    let regions = &game_state.board.regions;
+
+    let mut attacks = Vec::new();
+    for id in game_state
+        .player_regions
+        .regions_of(game_state.turn_of_player)
+    {
+        let region = &regions[*id];
+        if acted.contains(&region.id) || region.num_dice < min_attack_dice.0 {
+            continue;
+        }
+
+        for &neighbour in game_state.board.neighbours(region.id) {
+            if regions[neighbour].owner != region.owner {
+                attacks.push((region.id, neighbour));
+            }
+        }
+    }
+    attacks
+}
+
+/// Play a given attack followed by random attacks and score the resulting board
+/// by the value of regions owned by the current player
+fn playout(
+    game_state: &GameState,
+    attack: (RegionId, RegionId),
+    acted: &HashSet<RegionId>,
+    rules: &Rules,
+    rng: &mut impl Rng,
+) -> f64 {
+    let mut state = game_state.clone();
+    let mut acted = acted.clone();
+    let player = state.turn_of_player;
+
+    let mut attack = Some(attack);
+    for _ in 0..MAX_PLAYOUT_DEPTH {
+        let (region_1, region_2) = match attack {
+            Some(attack) => attack,
+            None => break,
+        };
+
+        simulate_clash(&mut state, region_1, region_2, rules, rng);
+        acted.insert(region_1);
+
+        let attacks = available_attacks(&state, &acted, rules.min_attack_dice);
+        attack = match attacks.is_empty() {
+            true => None,
+            false => Some(attacks[rng.gen_range(0..attacks.len())]),
+        };
+    }
+
+    state
+        .board
+        .regions
+        .iter()
+        .filter(|region| region.owner == player)
+        .map(|region| state.board.region_value(region.id) as f64)
+        .sum()
+}
+
+/// Roll dice for both sides of a clash and apply the result to the board, with ties and
+/// retreats following `rules` like the clashes of the match
+fn simulate_clash(
+    state: &mut GameState,
+    region_1: RegionId,
+    region_2: RegionId,
+    rules: &Rules,
+    rng: &mut impl Rng,
+) {
+    let mut log_entry = GameLogEntry::new(
+        GameAction::Clash,
+        state.turn_counter,
+        state.turn_of_player,
+        state.board.regions[region_1].clone(),
+        state.board.regions[region_2].clone(),
+    );
+    loop {
+        log_entry.region_1_dice_result = state.dice_kind.roll(log_entry.region_1.num_dice, rng);
+        log_entry.region_2_dice_result = state.dice_kind.roll(log_entry.region_2.num_dice, rng);
+        log_entry.resolve_with_tie_rule(rules.tie_rule);
+        if !(log_entry.is_tie && rules.tie_rule == TieRule::Reroll) {
+            break;
+        }
+    }
+
+    state
+        .resolve_clash(region_1, region_2, log_entry.attacker_won, rules, rng)
+        .expect("played out attacks are made on bordering opponents");
+}
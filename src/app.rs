@@ -1,77 +1,308 @@
+use bevy::diagnostic::{EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin};
 use bevy::prelude::*;
-use bevy_dice::{DicePlugin, DicePluginSettings};
+use bevy_dice::{DicePlugin, DicePluginSettings, DiceRollResult, DiceRollStartEvent};
 use bevy_mod_outline::*;
 use bevy_rapier3d::prelude::{NoUserData, RapierPhysicsPlugin};
+use rand::Rng;
 
-use crate::board::draw_board;
-use crate::game::{generate_board, GameState, SelectedRegion};
-use crate::tiered_prng::get_randomness;
-use crate::ui::{dice_roll_result_text_update, player_turn_text_update, setup_ui};
+use crate::ai::{AiPlayers, DiceBot};
+use crate::assets::check_dice_assets;
+use crate::audio::AudioSettings;
+use crate::board::{
+    animate_attack_arrows, animate_captured_dice, draw_board, event_attack_arrow, redraw_board,
+    CaptureAnimation, DiceLayout, FogOfWar, Palette, RedrawBoard, RegionMeshCache,
+};
+use crate::camera::{camera_controller, CameraControls};
+use crate::game::{
+    generate_playable_board, generation_threads, BoardConfig, DiceKind, FirstPlayer, GameState,
+    PlayerId, ReinforcementReserve, Rules, SelectedRegion,
+};
+use crate::minimap::{minimap_update, setup_minimap, Minimap};
+use crate::recap::{
+    export_replay_on_game_over, recap_step, replay_input, RecapAutoplay, ReplayExport,
+};
+use crate::rng_dice::{rng_dice_roll_start, rng_dice_roll_tick, DiceMode, ShuffleBags};
+use crate::snapshots::{record_snapshots, SnapshotHistory};
+use crate::stats::{MatchClock, SessionStats};
+use crate::trace::{record_trace, TraceLog};
+use crate::ui::{
+    armed_attack_text_update, clash_odds_text_update, copy_seed_input, debug_overlay_input,
+    debug_overlay_update, dice_count_label_update, dice_counts_input, dice_roll_result_text_update,
+    elimination_banner_update, hot_seat_overlay_update, hot_seat_ready_input,
+    hover_info_text_update, hover_info_update, match_clock_text_update, match_clock_update,
+    odds_bar_chart_update, player_turn_text_update, region_tooltip_input, region_tooltip_update,
+    scoreboard_text_update, setup_seed_text, setup_ui, DebugOverlay, HotSeat, HoverInfo,
+    MatchClockDisplay, PlayerNames, RevealStyle, ShowDiceCounts, ShowRegionTooltip,
+};
 use crate::{events::*, highlights, tiered_prng};
 
-pub fn build_app(
-    app: &mut App,
-    world_seed: u64,
-    env_seed: u64,
-    number_of_players: usize,
-    testing: bool,
-) {
-    // Generate game map
-    let map = generate_board(number_of_players, get_randomness(world_seed));
-
-    // Source of randomness for the game
-    let prng_resource = tiered_prng::PrngResource {
-        world_seed,
-        env_seed,
-    };
+/// Everything a game needs to be set up
+#[derive(Clone)]
+pub struct GameSettings {
+    pub world_seed: u64,
+    pub env_seed: u64,
+    pub number_of_players: usize,
+    pub ai_players: AiPlayers,
+    pub board_config: BoardConfig,
+    pub dice_mode: DiceMode,
+    pub first_player: FirstPlayer,
+    pub dice_kind: DiceKind,
+    /// Headless mode without windowing, rendering, audio and animations
+    pub testing: bool,
+}
 
-    if !testing {
-        app.add_plugins(DefaultPlugins);
-        app.add_plugin(bevy_kira_audio::prelude::AudioPlugin);
-        app.add_plugin(OutlinePlugin);
-        app.add_plugins(highlights::StackRankDicePickingPlugins);
+impl GameSettings {
+    pub fn new(world_seed: u64, env_seed: u64, number_of_players: usize) -> Self {
+        GameSettings {
+            world_seed,
+            env_seed,
+            number_of_players,
+            ai_players: AiPlayers::default(),
+            board_config: BoardConfig::default(),
+            dice_mode: DiceMode::default(),
+            first_player: FirstPlayer::default(),
+            dice_kind: DiceKind::default(),
+            testing: false,
+        }
     }
+}
+
+/// The whole game as a plugin, so it can be embedded into other Bevy apps.
+/// `DefaultPlugins` are only added when the host app doesn't have them yet.
+pub struct StackRankDicePlugin {
+    pub settings: GameSettings,
+}
+
+impl Plugin for StackRankDicePlugin {
+    fn build(&self, app: &mut App) {
+        let GameSettings {
+            world_seed,
+            env_seed,
+            number_of_players,
+            testing,
+            dice_mode,
+            first_player,
+            dice_kind,
+            ..
+        } = self.settings;
+        let board_config = self.settings.board_config.clone();
 
-    app
-        // PRNG setup
-        .insert_resource(prng_resource)
-        // Plugins
-        .add_plugin(tiered_prng::PrngPlugin) // Adds Prng based resources for subcomponents
-        .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
-        .add_plugin(DicePlugin)
-        // Resources
-        .insert_resource(DicePluginSettings {
-            render_size: (640 * 2, 720 * 2),
-            number_of_fields: 2,
-            ..default()
-        })
-        .insert_resource(GameState {
-            board: map,
+        // Generate game map
+        // Unlucky seeds are retried on other threads from derived seeds
+        let map = generate_playable_board(
             number_of_players,
-            turn_of_player: 0,
-            turn_counter: 0,
-            game_log: Vec::new(),
-        })
-        .insert_resource(ClearColor(Color::BLACK))
-        .init_resource::<SelectedRegion>()
-        // Startup Systems
-        .add_startup_system(setup_ui.after("dice_plugin_init").label("setup"))
-        .add_startup_system(draw_board.after("setup"))
-        // UI Systems
-        .add_system(player_turn_text_update)
-        .add_system(dice_roll_result_text_update)
-        // Control Handling
-        .add_system_to_stage(CoreStage::PostUpdate, event_region_selected)
-        // Event Handlers
-        .add_system(event_player_move_start)
-        .add_system(event_dice_roll_result)
-        .add_system(event_dice_rolls_complete)
-        .add_system(event_player_move_end)
-        .add_system(event_game_over)
-        // Events
-        .add_event::<EventPlayerMoveStart>()
-        .add_event::<EventPlayerMoveEnd>()
-        .add_event::<EventGameOver>()
-        .add_event::<EventTurnStart>()
-        .add_event::<EventTurnEnd>();
+            &board_config,
+            world_seed,
+            generation_threads(),
+        )
+        .unwrap_or_else(|err| panic!("{}", err));
+        let mut game_state = GameState::from_board(map, number_of_players);
+        game_state.dice_kind = dice_kind;
+        info!(
+            "Generated {} regions covering {:.0}% of the board",
+            game_state.board.total_regions(),
+            game_state.board.coverage(&board_config) * 100.0
+        );
+
+        // Source of randomness for the game
+        let prng_resource = tiered_prng::PrngResource {
+            world_seed,
+            env_seed,
+        };
+
+        // Highlight materials pick their colors up from the palette as soon as they're added
+        app.init_resource::<Palette>();
+
+        if !testing {
+            if !app.is_plugin_added::<WindowPlugin>() {
+                app.add_plugins(DefaultPlugins);
+            }
+            app.add_plugin(bevy_kira_audio::prelude::AudioPlugin);
+            app.add_plugin(OutlinePlugin);
+            app.add_plugins(highlights::StackRankDicePickingPlugins);
+            app.add_plugin(FrameTimeDiagnosticsPlugin);
+            app.add_plugin(EntityCountDiagnosticsPlugin);
+            // Seeds are only worth sharing from a game someone can see
+            app.add_startup_system(setup_seed_text.after("setup"))
+                .add_system(copy_seed_input);
+            // Render targets need the render plugins
+            app.init_resource::<Minimap>()
+                .add_startup_system(setup_minimap.after("setup"))
+                .add_system(minimap_update);
+        } else {
+            // Headless runs don't wait for dramatic pauses
+            app.insert_resource(RevealStyle::Simultaneous);
+            app.insert_resource(CaptureAnimation {
+                enabled: false,
+                ..default()
+            });
+        }
+
+        app
+            // PRNG setup
+            .insert_resource(prng_resource)
+            // Plugins
+            .add_plugin(tiered_prng::PrngPlugin) // Adds Prng based resources for subcomponents
+            // Resources
+            .insert_resource(DicePluginSettings {
+                render_size: (640 * 2, 720 * 2),
+                number_of_fields: 2,
+                ..default()
+            })
+            .insert_resource(game_state)
+            .insert_resource(board_config)
+            .insert_resource(dice_mode)
+            .insert_resource(self.settings.ai_players.clone())
+            .insert_resource(ClearColor(Color::BLACK))
+            .init_resource::<SelectedRegion>()
+            .init_resource::<RegionMeshCache>()
+            .init_resource::<FogOfWar>()
+            .init_resource::<highlights::ClashSpotlight>()
+            .init_resource::<HotSeat>()
+            .init_resource::<SessionStats>()
+            .init_resource::<DebugOverlay>()
+            .init_resource::<ShowDiceCounts>()
+            .init_resource::<PlayerNames>()
+            .init_resource::<CameraControls>()
+            .init_resource::<HoverInfo>()
+            .init_resource::<ShowRegionTooltip>()
+            .init_resource::<RecapAutoplay>()
+            .init_resource::<ReplayExport>()
+            .init_resource::<Autosave>()
+            .init_resource::<AttackConfirmation>()
+            .init_resource::<RegionCursor>()
+            .init_resource::<DiceRollDuration>()
+            .init_resource::<Rules>()
+            .init_resource::<ReinforcementReserve>()
+            .init_resource::<DiceLayout>()
+            .init_resource::<RevealStyle>()
+            .init_resource::<AudioSettings>()
+            .init_resource::<MatchClock>()
+            .init_resource::<MatchClockDisplay>()
+            .init_resource::<CaptureAnimation>()
+            // Startup Systems
+            .add_startup_system(setup_ui.after("dice_plugin_init").label("setup"))
+            .add_startup_system(draw_board.after("setup"))
+            .add_startup_system(check_dice_assets)
+            // UI Systems
+            .add_system(player_turn_text_update)
+            .add_system(scoreboard_text_update)
+            .add_system(dice_roll_result_text_update)
+            .add_system(clash_odds_text_update)
+            .add_system(odds_bar_chart_update)
+            .add_system(dice_counts_input)
+            .add_system(dice_count_label_update.after(dice_counts_input))
+            .add_system(hot_seat_overlay_update)
+            .add_system(hot_seat_ready_input)
+            .add_system(debug_overlay_input)
+            .add_system(debug_overlay_update)
+            .add_system(armed_attack_text_update)
+            .add_system(hover_info_update)
+            .add_system(hover_info_text_update)
+            .add_system(region_tooltip_input)
+            .add_system(region_tooltip_update.after(hover_info_update))
+            .add_system(match_clock_update)
+            .add_system(match_clock_text_update.after(match_clock_update))
+            .add_system(elimination_banner_update)
+            // Camera
+            .add_system(camera_controller)
+            // Animations
+            .add_system(animate_captured_dice)
+            .add_system(event_attack_arrow)
+            .add_system(animate_attack_arrows)
+            // Control Handling
+            .add_system_to_stage(CoreStage::PostUpdate, event_region_selected)
+            // Event Handlers
+            .add_system(event_player_move_start)
+            .add_system(event_clash_spotlight)
+            .add_system(event_dice_roll_result)
+            .add_system(skip_dice_roll_input.before(event_dice_rolls_complete))
+            .add_system(event_dice_rolls_complete)
+            .add_system(event_player_move_end)
+            .add_system(event_retreat)
+            .add_system(attack_confirmation_input)
+            .add_system(
+                region_cursor_input
+                    .after(attack_confirmation_input)
+                    .before(hot_seat_ready_input),
+            )
+            .add_system(region_cursor_outline_update.after(region_cursor_input))
+            .add_system(fortify_input)
+            .add_system(event_fortify)
+            .add_system(undo_input)
+            .add_system(event_undo.after(undo_input))
+            .add_system(ai_turn.after(event_player_move_end))
+            .add_system(event_player_pass.after(ai_turn))
+            .add_system(event_turn_start)
+            .add_system(autosave_on_turn_end)
+            .add_system(event_player_eliminated)
+            .add_system(event_game_over)
+            .add_system(recap_step)
+            .add_system(replay_input)
+            .add_system(export_replay_on_game_over)
+            .add_system_to_stage(CoreStage::PostUpdate, redraw_board)
+            // Events
+            .add_event::<EventPlayerMoveStart>()
+            .add_event::<EventPlayerMoveEnd>()
+            .add_event::<EventRetreat>()
+            .add_event::<EventFortify>()
+            .add_event::<EventGameOver>()
+            .add_event::<EventTurnStart>()
+            .add_event::<EventTurnEnd>()
+            .add_event::<EventPlayerEliminated>()
+            .add_event::<EventPlayerPass>()
+            .add_event::<EventUndo>()
+            .add_event::<GameEvent>()
+            .add_event::<RedrawBoard>();
+
+        // Drawn only now, the env PRNG is set up by `PrngPlugin`
+        let first_player = match first_player {
+            FirstPlayer::Player(player) => player,
+            FirstPlayer::Random => PlayerId(
+                app.world
+                    .resource_mut::<tiered_prng::PrngEnvResource>()
+                    .rng
+                    .gen_range(0..number_of_players),
+            ),
+        };
+        let mut game_state = app.world.resource_mut::<GameState>();
+        game_state
+            .set_turn(first_player)
+            .unwrap_or_else(|err| panic!("{}", err));
+        game_state.first_player = first_player;
+
+        match dice_mode {
+            DiceMode::Physics => {
+                app.add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
+                    .add_plugin(DicePlugin);
+            }
+            DiceMode::RngOnly | DiceMode::ShuffleBag => {
+                app.init_resource::<ShuffleBags>()
+                    .add_event::<DiceRollStartEvent>()
+                    .add_event::<DiceRollResult>()
+                    .add_system(rng_dice_roll_start)
+                    .add_system(rng_dice_roll_tick);
+            }
+        }
+
+        // Keep recent game states around for debugging
+        if cfg!(debug_assertions) {
+            app.init_resource::<SnapshotHistory>()
+                .add_system_to_stage(CoreStage::Last, record_snapshots);
+        }
+        // A trace file asked for is written in release builds too
+        if cfg!(debug_assertions) || testing || app.world.contains_resource::<TraceLog>() {
+            app.init_resource::<TraceLog>()
+                .add_system_to_stage(CoreStage::Last, record_trace);
+        }
+    }
+}
+
+/// Set up the game on `app` from `settings`. `bots` take the first seats in order.
+pub fn build_app(app: &mut App, mut settings: GameSettings, bots: Vec<Box<dyn DiceBot>>) {
+    for (player, bot) in bots.into_iter().enumerate() {
+        settings.ai_players.add_bot(PlayerId(player), bot);
+    }
+
+    app.add_plugin(StackRankDicePlugin { settings });
 }
@@ -0,0 +1,40 @@
+use std::path::Path;
+
+use bevy::asset::AssetIo;
+use bevy::prelude::*;
+
+pub(crate) const DICE_MESH: &str = "models/dice/scene.gltf#Mesh0/Primitive0";
+pub(crate) const DICE_BASE_COLOR_TEXTURE: &str = "models/dice/textures/Dice_baseColor.png";
+pub(crate) const DICE_NORMAL_TEXTURE: &str = "models/dice/textures/Dice_normal.png";
+pub(crate) const DICE_METALLIC_ROUGHNESS_TEXTURE: &str =
+    "models/dice/textures/Dice_metallicRoughness.png";
+
+/// Assets required to render dice on the board
+pub const DICE_ASSETS: [&str; 4] = [
+    DICE_MESH,
+    DICE_BASE_COLOR_TEXTURE,
+    DICE_NORMAL_TEXTURE,
+    DICE_METALLIC_ROUGHNESS_TEXTURE,
+];
+
+/// Return asset paths which can't be found. Labels (`#Mesh0/...`) are stripped before lookup.
+pub fn missing_assets(asset_io: &dyn AssetIo, paths: &[&str]) -> Vec<String> {
+    paths
+        .iter()
+        .filter(|path| {
+            let file_path = path.split('#').next().unwrap_or(path);
+            !asset_io.is_file(Path::new(file_path))
+        })
+        .map(|path| path.to_string())
+        .collect()
+}
+
+/// Report missing dice assets at startup. Otherwise dice silently don't render.
+pub(crate) fn check_dice_assets(asset_server: Res<AssetServer>) {
+    for path in missing_assets(asset_server.asset_io(), &DICE_ASSETS) {
+        error!(
+            "Dice asset `{}` is missing, dice won't be rendered. Make sure `assets` folder is complete.",
+            path
+        );
+    }
+}
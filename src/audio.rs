@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::game::MAX_DICE;
+
+/// Playback parameters of a sound effect
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SoundParams {
+    /// Playback rate, below 1.0 sounds deeper
+    pub playback_rate: f64,
+    pub volume: f64,
+}
+
+/// Resource with settings of sound effects
+#[derive(Resource, Clone, Debug)]
+pub struct AudioSettings {
+    /// Vary the throw sound with the number of dice thrown
+    pub scale_throw_sound: bool,
+    /// Playback rate of the throw sound with the fewest dice
+    pub max_playback_rate: f64,
+    /// Playback rate of the throw sound with the most dice
+    pub min_playback_rate: f64,
+    /// Volume of the throw sound with the fewest dice
+    pub min_volume: f64,
+    /// Volume of the throw sound with the most dice
+    pub max_volume: f64,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        AudioSettings {
+            scale_throw_sound: true,
+            max_playback_rate: 1.2,
+            min_playback_rate: 0.7,
+            min_volume: 0.6,
+            max_volume: 1.0,
+        }
+    }
+}
+
+impl AudioSettings {
+    /// Throw sound for a clash with `num_dice` dice on both sides in total.
+    /// More dice sound deeper and louder.
+    pub fn throw_sound(&self, num_dice: usize) -> SoundParams {
+        if !self.scale_throw_sound {
+            return SoundParams {
+                playback_rate: 1.0,
+                volume: 1.0,
+            };
+        }
+
+        // A clash has at least one dice on each side and at most MAX_DICE on each
+        let t = (num_dice.clamp(2, 2 * MAX_DICE) - 2) as f64 / (2 * MAX_DICE - 2) as f64;
+
+        SoundParams {
+            playback_rate: self.max_playback_rate
+                + (self.min_playback_rate - self.max_playback_rate) * t,
+            volume: self.min_volume + (self.max_volume - self.min_volume) * t,
+        }
+    }
+}
@@ -1,22 +1,33 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use rand::Rng;
 
 use bevy::{
+    ecs::system::SystemParam,
     prelude::*,
     render::{mesh::Indices, render_resource::PrimitiveTopology},
 };
 
 use bevy_mod_outline::*;
 use bevy_mod_picking::PickableBundle;
+use clap::ValueEnum;
 
+use crate::ai::AiPlayers;
+use crate::assets::{
+    DICE_BASE_COLOR_TEXTURE, DICE_MESH, DICE_METALLIC_ROUGHNESS_TEXTURE, DICE_NORMAL_TEXTURE,
+};
+use crate::events::{DiceRollDuration, EventPlayerMoveStart};
 use crate::geometry::{center, flat_hexagon_points};
 use crate::hex::HexCoord;
 use crate::tiered_prng::PrngMapResource;
+use crate::ui::DiceCountLabel;
 use crate::{
-    game::{GameState, Region},
+    game::{DiceTransfer, GameState, PlayerId, Region, RegionId, Rules},
     geometry,
 };
 
-pub(crate) const PLAYER_COLORS: [Color; 8] = [
+const PLAYER_COLORS: [Color; 8] = [
     Color::PURPLE,
     Color::CYAN,
     Color::GREEN,
@@ -27,6 +38,81 @@ pub(crate) const PLAYER_COLORS: [Color; 8] = [
     Color::OLIVE,
 ];
 
+/// Most players a game can have, one for each distinct color
+pub const MAX_PLAYERS: usize = PLAYER_COLORS.len();
+
+/// Resource with the set of colors players and highlights are drawn with
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default, ValueEnum)]
+pub enum Palette {
+    #[default]
+    Default,
+    /// Avoids red and green side by side, based on the Okabe-Ito palette
+    Deuteranopia,
+    /// Saturated colors that stand apart on a dark background
+    HighContrast,
+}
+
+/// Colors of a hovered, a selected and an attackable opponent region
+pub struct HighlightColors {
+    pub hovered: Color,
+    pub selected: Color,
+    pub opponent: Color,
+}
+
+/// Colors of players under a palette, indexed by player id
+pub fn player_colors(palette: Palette) -> [Color; 8] {
+    match palette {
+        Palette::Default => PLAYER_COLORS,
+        Palette::Deuteranopia => [
+            Color::rgb(0.0, 0.45, 0.7),
+            Color::rgb(0.9, 0.62, 0.0),
+            Color::rgb(0.34, 0.71, 0.91),
+            Color::rgb(0.94, 0.89, 0.26),
+            Color::rgb(0.0, 0.62, 0.45),
+            Color::rgb(0.84, 0.37, 0.0),
+            Color::rgb(0.8, 0.47, 0.65),
+            Color::rgb(0.6, 0.6, 0.6),
+        ],
+        Palette::HighContrast => [
+            Color::rgb(1.0, 1.0, 0.0),
+            Color::rgb(0.0, 0.6, 1.0),
+            Color::rgb(1.0, 0.3, 0.0),
+            Color::rgb(0.0, 1.0, 0.5),
+            Color::rgb(1.0, 1.0, 1.0),
+            Color::rgb(0.6, 0.2, 1.0),
+            Color::rgb(1.0, 0.6, 0.8),
+            Color::rgb(0.45, 0.45, 0.45),
+        ],
+    }
+}
+
+/// Colors of highlighted regions under a palette
+pub fn highlight_colors(palette: Palette) -> HighlightColors {
+    match palette {
+        Palette::Default => HighlightColors {
+            hovered: Color::rgb(0.85, 0.0, 0.85),
+            selected: Color::rgb(0.95, 0.0, 0.85),
+            opponent: Color::rgba(0.95, 0.0, 0.0, 0.7),
+        },
+        Palette::Deuteranopia => HighlightColors {
+            hovered: Color::rgb(0.85, 0.85, 0.85),
+            selected: Color::rgb(1.0, 1.0, 1.0),
+            opponent: Color::rgba(0.0, 0.45, 0.7, 0.7),
+        },
+        Palette::HighContrast => HighlightColors {
+            hovered: Color::rgb(1.0, 0.0, 1.0),
+            selected: Color::rgb(1.0, 0.5, 1.0),
+            opponent: Color::rgba(1.0, 0.0, 0.0, 0.8),
+        },
+    }
+}
+
+/// Color of a player, tied to its stable id rather than to its current owner index
+pub(crate) fn player_color(player_id: PlayerId, palette: Palette) -> Color {
+    let colors = player_colors(palette);
+    colors[player_id.0 % colors.len()]
+}
+
 /// Generate a single hex mesh
 fn generate_hex_region_mesh(region: &Region) -> Mesh {
     let hexes = region.hexes.clone();
@@ -46,7 +132,7 @@ fn generate_hex_region_mesh(region: &Region) -> Mesh {
         for _ in 0..9 {
             normals.push([0., 1., 0.]);
         }
-        for i in 0..=6 {
+        for i in 0..6 {
             indices.push(18 * hex_num); // Center
             indices.push(18 * hex_num + i + 1); // Point       East           North-east
             indices.push(18 * hex_num + i + 2); // Next point  North-east     North-west
@@ -63,15 +149,16 @@ fn generate_hex_region_mesh(region: &Region) -> Mesh {
             normals.push([0., -1., 0.]);
         }
 
-        // Populate indices for bottom
-        for i in 0..=6 {
+        // Populate indices for bottom, wound the other way so it faces down
+        for i in 0..6 {
             indices.push(18 * hex_num + 9); // Center
-            indices.push(18 * hex_num + i + 1 + 9); // Point       East           North-east
             indices.push(18 * hex_num + i + 2 + 9); // Next point  North-east     North-west
+            indices.push(18 * hex_num + i + 1 + 9); // Point       East           North-east
         }
 
-        // Populate indices sides
-        for i in 0..=6 {
+        // Populate indices sides. Only the six edges of the ring, the last point of the ring
+        // is the center, so going further makes degenerate faces with NaN outline normals.
+        for i in 0..6 {
             indices.push(18 * hex_num + i + 2);
             indices.push(18 * hex_num + i + 1 + 9);
             indices.push(18 * hex_num + i + 2 + 9);
@@ -98,32 +185,228 @@ fn generate_hex_region_mesh(region: &Region) -> Mesh {
 #[derive(Component)]
 pub(crate) struct StackRankDiceGameBoardElement;
 
+/// Resource that configures how dice of a region are rendered
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DiceLayout {
+    /// Every dice is rendered, stacked in up to two columns
+    #[default]
+    Stacked,
+    /// A single dice with a label showing the number of dice
+    Single,
+}
+
+/// Resource hiding regions the current player can't see, i.e. ones not bordering its territory
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct FogOfWar(pub bool);
+
+impl FogOfWar {
+    /// Ids of regions shown on the board in ascending order, `None` when nothing is hidden.
+    /// Human players share the screen, so during turns of AI players the board shows what the
+    /// first human player still in the game sees. Games between AI players aren't hidden.
+    pub fn visible_regions(
+        &self,
+        game_state: &GameState,
+        ai_players: &AiPlayers,
+    ) -> Option<Vec<RegionId>> {
+        if !self.0 {
+            return None;
+        }
+
+        let viewer = match ai_players.is_ai(game_state.turn_of_player) {
+            false => game_state.turn_of_player,
+            true => (0..game_state.number_of_players)
+                .map(PlayerId)
+                .find(|player| {
+                    !ai_players.is_ai(*player)
+                        && !game_state.player_regions.regions_of(*player).is_empty()
+                })?,
+        };
+
+        Some(game_state.visible_regions(viewer))
+    }
+}
+
+/// Whether a region is hidden, given regions from [`FogOfWar::visible_regions`]
+pub(crate) fn is_fogged(visible_regions: Option<&[RegionId]>, region_id: RegionId) -> bool {
+    match visible_regions {
+        Some(visible) => visible.binary_search(&region_id).is_err(),
+        None => false,
+    }
+}
+
+/// Color of regions hidden by [`FogOfWar`]
+const FOG_COLOR: Color = Color::rgb(0.15, 0.15, 0.15);
+/// Height above the board attack arrows are drawn at
+const ARROW_HEIGHT: f32 = 2.0;
+const ARROW_WIDTH: f32 = 0.15;
+
+/// Resource that configures animation of dice moving onto a captured region
+#[derive(Resource)]
+pub struct CaptureAnimation {
+    pub enabled: bool,
+    pub duration: Duration,
+    /// Transfer of the last clash which is yet to be animated
+    pub transfer: Option<DiceTransfer>,
+}
+
+impl Default for CaptureAnimation {
+    fn default() -> Self {
+        CaptureAnimation {
+            enabled: true,
+            duration: Duration::from_millis(600),
+            transfer: None,
+        }
+    }
+}
+
+/// Dice moving along a line between two regions
+#[derive(Component)]
+pub(crate) struct DiceTween {
+    from: Vec3,
+    to: Vec3,
+    timer: Timer,
+}
+
+/// Resource with assets reused across redraws of the board. Meshes are keyed by the hexes
+/// they cover, which don't change during a game. Materials of regions get dimmed during
+/// clashes, so they're created on every draw and the ones of the previous draw are removed.
+#[derive(Resource, Default)]
+pub struct RegionMeshCache {
+    meshes: HashMap<Vec<(isize, isize)>, Handle<Mesh>>,
+    fortify_mesh: Option<Handle<Mesh>>,
+    dice_material: Option<Handle<StandardMaterial>>,
+    fortify_material: Option<Handle<StandardMaterial>>,
+    water_material: Option<Handle<StandardMaterial>>,
+    region_materials: Vec<Handle<StandardMaterial>>,
+}
+
+impl RegionMeshCache {
+    /// Mesh of a region covering `hexes`, generated the first time it's asked for
+    pub(crate) fn region_mesh(
+        &mut self,
+        region: &Region,
+        meshes: &mut Assets<Mesh>,
+    ) -> Handle<Mesh> {
+        let mut hexes = region.hexes.clone();
+        hexes.sort_unstable();
+
+        self.meshes
+            .entry(hexes)
+            .or_insert_with(|| {
+                let mut mesh = generate_hex_region_mesh(region);
+                mesh.generate_outline_normals().unwrap();
+                meshes.add(mesh)
+            })
+            .clone()
+    }
+}
+
+/// Color of a region's owner, or the fog color for regions outside of `visible_regions`
+pub(crate) fn region_color(
+    game_state: &GameState,
+    region: &Region,
+    visible_regions: Option<&[RegionId]>,
+    palette: Palette,
+) -> Color {
+    match is_fogged(visible_regions, region.id) {
+        true => FOG_COLOR,
+        false => player_color(game_state.players.id_of(region.owner), palette),
+    }
+}
+
+/// Dice mesh placed on the board
+#[derive(Component)]
+pub(crate) struct DiceMesh;
+
+/// Dice of a captured region, hidden until transferred dice land on it
+#[derive(Component)]
+pub(crate) struct CapturedDice;
+
+/// Event asking for the board to be drawn again from the game state, e.g. once a move is
+/// applied. However many are sent during a frame, the board is redrawn once.
+pub(crate) struct RedrawBoard;
+
+/// Assets and settings the board is drawn with
+#[derive(SystemParam)]
+pub(crate) struct BoardPainter<'w, 's> {
+    commands: Commands<'w, 's>,
+    asset_server: Res<'w, AssetServer>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+    mesh_cache: ResMut<'w, RegionMeshCache>,
+    map_prng: ResMut<'w, PrngMapResource>,
+    dice_layout: Res<'w, DiceLayout>,
+    palette: Res<'w, Palette>,
+    fog_of_war: Res<'w, FogOfWar>,
+    ai_players: Res<'w, AiPlayers>,
+    rules: Res<'w, Rules>,
+}
+
 pub(crate) fn draw_board(
-    asset_server: Res<AssetServer>,
-    mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut map_prng: ResMut<PrngMapResource>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
-    game_state: ResMut<GameState>,
+    painter: BoardPainter,
+    game_state: Res<GameState>,
+    capture_animation: Res<CaptureAnimation>,
+) {
+    paint_board(painter, &game_state, &capture_animation);
+}
+
+/// Replace elements of the board with freshly drawn ones on [`RedrawBoard`]
+pub(crate) fn redraw_board(
+    mut redraw_board_reader: EventReader<RedrawBoard>,
+    board_elements: Query<Entity, With<StackRankDiceGameBoardElement>>,
+    mut painter: BoardPainter,
+    game_state: Res<GameState>,
+    capture_animation: Res<CaptureAnimation>,
 ) {
+    if redraw_board_reader.iter().count() == 0 {
+        return;
+    }
+
+    for entity in board_elements.iter() {
+        painter.commands.entity(entity).despawn_recursive();
+    }
+
+    paint_board(painter, &game_state, &capture_animation);
+}
+
+fn paint_board(
+    painter: BoardPainter,
+    game_state: &GameState,
+    capture_animation: &CaptureAnimation,
+) {
+    let BoardPainter {
+        mut commands,
+        asset_server,
+        mut meshes,
+        mut materials,
+        mut mesh_cache,
+        mut map_prng,
+        dice_layout,
+        palette,
+        fog_of_war,
+        ai_players,
+        rules,
+    } = painter;
+
     let board = game_state.board.clone();
+    debug_assert!(
+        board.ids_match_indices(),
+        "region ids don't match their indices"
+    );
+    let visible_regions = fog_of_war.visible_regions(game_state, &ai_players);
+
+    for material in std::mem::take(&mut mesh_cache.region_materials) {
+        materials.remove(material);
+    }
 
     // Draw board
     for region in board.regions.iter() {
-        let color = PLAYER_COLORS[region.owner as usize];
+        let color = region_color(game_state, region, visible_regions.as_deref(), *palette);
 
         let center_coord = center(1.0, &region.center_hex(), &[0.0, 0.0, 0.0]);
 
-        #[allow(clippy::search_is_some)]
-        let is_region_playable = game_state
-            .game_log
-            .iter()
-            .find(|gl| {
-                gl.turn_counter == game_state.turn_counter
-                    && gl.region_1.id == region.id
-                    && region.owner == game_state.turn_of_player
-            })
-            .is_none();
+        let is_region_playable = region.owner != game_state.turn_of_player
+            || game_state.can_attack(region.id, rules.capture_bonus);
 
         let material = match is_region_playable {
             true => materials.add(StandardMaterial {
@@ -139,10 +422,9 @@ pub(crate) fn draw_board(
                 ..default()
             }),
         };
+        mesh_cache.region_materials.push(material.clone());
 
-        let mut mesh = generate_hex_region_mesh(region);
-        mesh.generate_outline_normals().unwrap();
-        let mesh = meshes.add(mesh);
+        let mesh = mesh_cache.region_mesh(region, &mut meshes);
         // Theese micro-height differences are to make otline rendering visible.
         // Otherwise tiles with the same height will be rendered as one.
         let height: f32 = 1.0 + map_prng.rng.gen_range(0.0..=0.0001);
@@ -175,56 +457,550 @@ pub(crate) fn draw_board(
         }
     }
 
+    // Draw lakes slightly below the regions
+    let water_material = mesh_cache
+        .water_material
+        .get_or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color: Color::rgb(0.1, 0.3, 0.7),
+                metallic: 0.0,
+                reflectance: 0.5,
+                ..default()
+            })
+        })
+        .clone();
+    for hole in board.holes.iter() {
+        let lake = Region {
+            hexes: vec![*hole],
+            ..default()
+        };
+        let center_coord = center(1.0, &lake.center_hex(), &[0.0, 0.0, 0.0]);
+
+        commands
+            .spawn(PbrBundle {
+                mesh: mesh_cache.region_mesh(&lake, &mut meshes),
+                material: water_material.clone(),
+                transform: Transform::from_translation(Vec3::new(
+                    center_coord[0],
+                    center_coord[1] + 0.9,
+                    center_coord[2],
+                )),
+                ..Default::default()
+            })
+            .insert(Name::new("Water"))
+            .insert(StackRankDiceGameBoardElement);
+    }
+
     // Place dice on areas
-    let dice_mesh_handle = asset_server.load("models/dice/scene.gltf#Mesh0/Primitive0");
-    let material_handle = materials.add(StandardMaterial {
-        base_color_texture: Some(asset_server.load("models/dice/textures/Dice_baseColor.png")),
-        normal_map_texture: Some(asset_server.load("models/dice/textures/Dice_normal.png")),
-        metallic_roughness_texture: Some(
-            asset_server.load("models/dice/textures/Dice_metallicRoughness.png"),
-        ),
-        ..default()
+    let dice_mesh_handle = asset_server.load(DICE_MESH);
+    let material_handle = mesh_cache
+        .dice_material
+        .get_or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color_texture: Some(asset_server.load(DICE_BASE_COLOR_TEXTURE)),
+                normal_map_texture: Some(asset_server.load(DICE_NORMAL_TEXTURE)),
+                metallic_roughness_texture: Some(
+                    asset_server.load(DICE_METALLIC_ROUGHNESS_TEXTURE),
+                ),
+                ..default()
+            })
+        })
+        .clone();
+
+    let fortify_mesh_handle = mesh_cache
+        .fortify_mesh
+        .get_or_insert_with(|| {
+            meshes.add(Mesh::from(shape::Torus {
+                radius: 0.7,
+                ring_radius: 0.05,
+                ..default()
+            }))
+        })
+        .clone();
+    let fortify_material_handle = mesh_cache
+        .fortify_material
+        .get_or_insert_with(|| {
+            materials.add(StandardMaterial {
+                base_color: Color::WHITE,
+                metallic: 0.8,
+                ..default()
+            })
+        })
+        .clone();
+
+    // Dice moving into or out of fog would give away the regions hidden there
+    let pending_transfer = capture_animation.transfer.filter(|t| {
+        capture_animation.enabled
+            && !is_fogged(visible_regions.as_deref(), t.from_region)
+            && !is_fogged(visible_regions.as_deref(), t.to_region)
     });
 
     for region in board.regions.iter() {
         let center_hex = region.center_hex();
         let pos = geometry::center(1.0, &center_hex, &[0., 0.0, 0.]);
+        commands
+            .spawn(PointLightBundle {
+                point_light: PointLight {
+                    intensity: 100.0,
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(pos[0] + 2.0, 2.0, pos[2]),
+                ..Default::default()
+            })
+            .insert(Name::new("RegionLight"))
+            .insert(StackRankDiceGameBoardElement);
 
-        for i in 0..region.num_dice {
-            let mut y_pos = 1.0 + pos[1] + 0.383 + (i as f32) * (2.0 * 0.383);
-            let mut z_pos = pos[2];
-            if i > 3 {
-                y_pos = pos[1] + 0.383 + ((i - 4) as f32) * (2.0 * 0.383);
-                z_pos += 0.383 * 2.0 + 0.01;
-            }
+        // Dice, their count and fortifications of hidden regions aren't drawn
+        if is_fogged(visible_regions.as_deref(), region.id) {
+            continue;
+        }
+
+        // Dice of a captured region are shown once the transferred dice land on it
+        let is_captured = pending_transfer.is_some_and(|t| t.to_region == region.id);
+
+        for dice_translation in dice_positions(*dice_layout, &pos, region.num_dice) {
+            let mut dice_command = commands.spawn(PbrBundle {
+                mesh: dice_mesh_handle.clone(),
+                material: material_handle.clone(),
+                transform: Transform::from_translation(dice_translation)
+                    .with_scale(Vec3::splat(0.4)),
+                visibility: Visibility {
+                    is_visible: !is_captured,
+                },
+                ..default()
+            });
 
-            if region.num_dice > 3 {
-                z_pos -= 0.383;
+            dice_command
+                .insert(OutlineStencil { offset: 1.0 })
+                .insert(Name::new("Dice"))
+                .insert(DiceMesh)
+                .insert(StackRankDiceGameBoardElement);
+
+            if is_captured {
+                dice_command.insert(CapturedDice);
             }
+        }
 
+        // Number of dice above the stack, in the owner's color on a dark plate for contrast
+        let top = dice_positions(*dice_layout, &pos, region.num_dice)
+            .iter()
+            .map(|p| p.y)
+            .fold(1.0 + pos[1], f32::max);
+        let mut label_command = commands.spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                padding: UiRect::horizontal(Val::Px(4.0)),
+                ..default()
+            },
+            background_color: Color::rgba(0.0, 0.0, 0.0, 0.7).into(),
+            visibility: Visibility {
+                is_visible: !is_captured,
+            },
+            ..default()
+        });
+
+        label_command
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    format!("{}", region.num_dice),
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 30.0,
+                        color: player_color(game_state.players.id_of(region.owner), *palette),
+                    },
+                ));
+            })
+            .insert(DiceCountLabel {
+                world_position: Vec3::new(pos[0], top + 0.8, pos[2]),
+                // A single dice doesn't tell how many there are
+                always_shown: *dice_layout == DiceLayout::Single,
+            })
+            .insert(Name::new("DiceCountLabel"))
+            .insert(StackRankDiceGameBoardElement);
+
+        if is_captured {
+            label_command.insert(CapturedDice);
+        }
+
+        // Fortify level as rings around the base of dice stack
+        for level in 0..region.fortify_bonus() {
+            commands
+                .spawn(PbrBundle {
+                    mesh: fortify_mesh_handle.clone(),
+                    material: fortify_material_handle.clone(),
+                    transform: Transform::from_xyz(
+                        pos[0],
+                        1.0 + pos[1] + 0.1 + (level as f32) * 0.15,
+                        pos[2],
+                    ),
+                    ..default()
+                })
+                .insert(Name::new("FortifyRing"))
+                .insert(StackRankDiceGameBoardElement);
+        }
+    }
+
+    // Dice moving from a winning region onto the region it has captured
+    if let Some(transfer) = pending_transfer {
+        let from_region = &board.regions[transfer.from_region];
+        let to_region = &board.regions[transfer.to_region];
+        let from_pos = geometry::center(1.0, &from_region.center_hex(), &[0., 0.0, 0.]);
+        let to_pos = geometry::center(1.0, &to_region.center_hex(), &[0., 0.0, 0.]);
+
+        let sources = dice_positions(*dice_layout, &from_pos, transfer.num_dice);
+        let targets = dice_positions(*dice_layout, &to_pos, to_region.num_dice);
+
+        for (from, to) in sources.into_iter().zip(targets) {
             commands
                 .spawn(PbrBundle {
                     mesh: dice_mesh_handle.clone(),
                     material: material_handle.clone(),
-                    transform: Transform::from_xyz(pos[0], y_pos, z_pos)
-                        .with_scale(Vec3::splat(0.4)),
+                    transform: Transform::from_translation(from).with_scale(Vec3::splat(0.4)),
                     ..default()
                 })
-                .insert(OutlineStencil { offset: 1.0 })
+                .insert(DiceTween {
+                    from,
+                    to,
+                    timer: Timer::new(capture_animation.duration, TimerMode::Once),
+                })
                 .insert(Name::new("Dice"))
+                .insert(DiceMesh)
                 .insert(StackRankDiceGameBoardElement);
         }
+    }
+}
+
+/// Positions of dice rendered on a region centered at `pos`
+pub fn dice_positions(layout: DiceLayout, pos: &[f32; 3], num_dice: usize) -> Vec<Vec3> {
+    match layout {
+        DiceLayout::Stacked => (0..num_dice)
+            .map(|i| dice_position(pos, i, num_dice))
+            .collect(),
+        DiceLayout::Single => vec![dice_position(pos, 0, 1)],
+    }
+}
+
+/// Position of `i`-th dice in a stack of `num_dice` dice placed on a region centered at `pos`
+fn dice_position(pos: &[f32; 3], i: usize, num_dice: usize) -> Vec3 {
+    let mut y_pos = 1.0 + pos[1] + 0.383 + (i as f32) * (2.0 * 0.383);
+    let mut z_pos = pos[2];
+    if i > 3 {
+        y_pos = pos[1] + 0.383 + ((i - 4) as f32) * (2.0 * 0.383);
+        z_pos += 0.383 * 2.0 + 0.01;
+    }
+
+    if num_dice > 3 {
+        z_pos -= 0.383;
+    }
+
+    Vec3::new(pos[0], y_pos, z_pos)
+}
+
+/// Move transferred dice towards a captured region and reveal its dice once they land
+pub(crate) fn animate_captured_dice(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut capture_animation: ResMut<CaptureAnimation>,
+    mut tweens: Query<(Entity, &mut Transform, &mut DiceTween)>,
+    mut captured_dice: Query<&mut Visibility, With<CapturedDice>>,
+) {
+    let mut landed = false;
+
+    for (entity, mut transform, mut tween) in tweens.iter_mut() {
+        tween.timer.tick(time.delta());
+        transform.translation = tween.from.lerp(tween.to, tween.timer.percent());
+
+        if tween.timer.finished() {
+            commands.entity(entity).despawn();
+            landed = true;
+        }
+    }
+
+    if landed {
+        for mut visibility in captured_dice.iter_mut() {
+            visibility.is_visible = true;
+        }
+        capture_animation.transfer = None;
+    }
+}
+
+/// Line from an attacking region of an AI player to its target, fading out while dice roll
+#[derive(Component)]
+pub(crate) struct AttackArrow {
+    timer: Timer,
+}
+
+/// Point an arrow at the target of each AI attack, so games between AI players can be followed
+pub(crate) fn event_attack_arrow(
+    mut commands: Commands,
+    mut event_reader: EventReader<EventPlayerMoveStart>,
+    (ai_players, game_state, fog_of_war): (Res<AiPlayers>, Res<GameState>, Res<FogOfWar>),
+    capture_animation: Res<CaptureAnimation>,
+    dice_roll_duration: Res<DiceRollDuration>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for e in event_reader.iter() {
+        if !capture_animation.enabled || !ai_players.is_ai(e.player_1) {
+            continue;
+        }
+
+        // Attacks from or into fog stay hidden
+        let visible_regions = fog_of_war.visible_regions(&game_state, &ai_players);
+        if is_fogged(visible_regions.as_deref(), e.region_1.id)
+            || is_fogged(visible_regions.as_deref(), e.region_2.id)
+        {
+            continue;
+        }
+
+        let [x, _, z] = center(1.0, &e.region_1.center_hex(), &[0.0, 0.0, 0.0]);
+        let from = Vec3::new(x, ARROW_HEIGHT, z);
+        let [x, _, z] = center(1.0, &e.region_2.center_hex(), &[0.0, 0.0, 0.0]);
+        let to = Vec3::new(x, ARROW_HEIGHT, z);
 
         commands
-            .spawn(PointLightBundle {
-                point_light: PointLight {
-                    intensity: 100.0,
-                    ..Default::default()
-                },
-                transform: Transform::from_xyz(pos[0] + 2.0, 2.0, pos[2]),
-                ..Default::default()
+            .spawn(PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(
+                    ARROW_WIDTH,
+                    ARROW_WIDTH,
+                    from.distance(to),
+                ))),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::WHITE,
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                }),
+                transform: Transform::from_translation(from.lerp(to, 0.5)).looking_at(to, Vec3::Y),
+                ..default()
             })
-            .insert(Name::new("RegionLight"))
+            .insert(AttackArrow {
+                timer: Timer::new(dice_roll_duration.0, TimerMode::Once),
+            })
+            .insert(Name::new("Attack Arrow"))
             .insert(StackRankDiceGameBoardElement);
     }
 }
+
+/// Fade attack arrows out and remove them once dice are done rolling
+pub(crate) fn animate_attack_arrows(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut arrows: Query<(Entity, &mut AttackArrow, &Handle<StandardMaterial>)>,
+) {
+    for (entity, mut arrow, material) in arrows.iter_mut() {
+        arrow.timer.tick(time.delta());
+
+        if arrow.timer.finished() {
+            commands.entity(entity).despawn();
+        } else if let Some(material) = materials.get_mut(material) {
+            material.base_color.set_a(arrow.timer.percent_left());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::AssetPlugin;
+    use bevy::core::CorePlugin;
+
+    use bevy::render::mesh::VertexAttributeValues;
+
+    use super::*;
+    use crate::ai::AiStrategy;
+    use crate::game::Board;
+    use crate::tiered_prng::{PrngPlugin, PrngResource};
+
+    fn asset_counts(app: &App) -> (usize, usize) {
+        (
+            app.world.resource::<Assets<Mesh>>().len(),
+            app.world.resource::<Assets<StandardMaterial>>().len(),
+        )
+    }
+
+    #[test]
+    fn outline_normals_of_multi_hex_region() {
+        let region = Region {
+            hexes: vec![(0, 0), (1, 0), (0, 1)],
+            ..default()
+        };
+
+        let mut mesh = generate_hex_region_mesh(&region);
+        assert!(mesh.generate_outline_normals().is_ok());
+
+        let position_count = mesh.count_vertices();
+        match mesh.attribute(ATTRIBUTE_OUTLINE_NORMAL) {
+            Some(VertexAttributeValues::Float32x3(normals)) => {
+                assert_eq!(normals.len(), position_count);
+                assert!(normals.iter().flatten().all(|n| n.is_finite()));
+            }
+            _ => panic!("Outline normals are missing"),
+        }
+    }
+
+    #[test]
+    fn redraws_reuse_assets() {
+        // Two fortified regions of different players next to a lake
+        let mut board = Board::from_regions(
+            [(0, 0), (1, 0)]
+                .into_iter()
+                .enumerate()
+                .map(|(id, coord)| Region {
+                    fortify: 1,
+                    ..Region::new(RegionId(id), PlayerId(id), 3, vec![coord])
+                })
+                .collect(),
+        );
+        board.holes.insert((0, 1));
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin::default())
+            .add_plugin(AssetPlugin::default())
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .insert_resource(PrngResource {
+                world_seed: 4242,
+                env_seed: 17,
+            })
+            .add_plugin(PrngPlugin)
+            .init_resource::<CaptureAnimation>()
+            .init_resource::<DiceLayout>()
+            .init_resource::<Rules>()
+            .init_resource::<Palette>()
+            .init_resource::<RegionMeshCache>()
+            .init_resource::<FogOfWar>()
+            .init_resource::<AiPlayers>()
+            .insert_resource(GameState::from_board(board, 2))
+            .add_system(draw_board);
+
+        app.update();
+        let first_draw = asset_counts(&app);
+
+        for _ in 0..50 {
+            app.update();
+        }
+
+        assert_eq!(asset_counts(&app), first_draw);
+    }
+
+    #[test]
+    fn fog_hides_dice_of_regions_the_human_player_cannot_see() {
+        // A human player next to the first of two regions of an AI player, whose turn it is
+        let board = Board::from_regions(
+            [(0, 0), (1, 0), (2, 0)]
+                .into_iter()
+                .enumerate()
+                .map(|(id, coord)| Region::new(RegionId(id), PlayerId(id.min(1)), 3, vec![coord]))
+                .collect(),
+        );
+        let mut game_state = GameState::from_board(board, 2);
+        game_state.turn_of_player = PlayerId(1);
+        let mut ai_players = AiPlayers::default();
+        ai_players.add_bot(PlayerId(1), Box::new(AiStrategy::Greedy));
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin::default())
+            .add_plugin(AssetPlugin::default())
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .insert_resource(PrngResource {
+                world_seed: 4242,
+                env_seed: 17,
+            })
+            .add_plugin(PrngPlugin)
+            .init_resource::<CaptureAnimation>()
+            .init_resource::<DiceLayout>()
+            .init_resource::<Rules>()
+            .init_resource::<Palette>()
+            .init_resource::<RegionMeshCache>()
+            .insert_resource(FogOfWar(true))
+            .insert_resource(ai_players)
+            .insert_resource(game_state)
+            .add_system(draw_board);
+        app.update();
+
+        let mut dice = app.world.query_filtered::<(), With<DiceMesh>>();
+        assert_eq!(dice.iter(&app.world).count(), 6);
+        let mut labels = app.world.query::<&DiceCountLabel>();
+        assert_eq!(labels.iter(&app.world).count(), 2);
+    }
+
+    #[test]
+    fn attack_arrows_follow_ai_attacks_only() {
+        let region = |id: usize, owner: usize| Region {
+            hexes: vec![(id as isize, 0)],
+            owner: PlayerId(owner),
+            num_dice: 3,
+            id: RegionId(id),
+            ..default()
+        };
+        let mut ai_players = AiPlayers::default();
+        ai_players.add_bot(PlayerId(1), Box::new(AiStrategy::Greedy));
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin::default())
+            .add_plugin(AssetPlugin::default())
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .init_resource::<CaptureAnimation>()
+            .init_resource::<DiceRollDuration>()
+            .init_resource::<FogOfWar>()
+            .insert_resource(GameState::from_board(
+                Board::from_regions(vec![region(0, 0), region(1, 1)]),
+                2,
+            ))
+            .insert_resource(ai_players)
+            .add_event::<EventPlayerMoveStart>()
+            .add_system(event_attack_arrow);
+
+        for (player_1, player_2) in [(0, 1), (1, 0)] {
+            app.world.send_event(EventPlayerMoveStart {
+                region_1: region(player_1, player_1),
+                region_2: region(player_2, player_2),
+                player_1: PlayerId(player_1),
+            });
+        }
+        app.update();
+
+        let mut arrows = app.world.query::<&AttackArrow>();
+        assert_eq!(arrows.iter(&app.world).count(), 1);
+    }
+
+    #[test]
+    fn no_attack_arrows_in_fog() {
+        // A human player next to the first of two AI players, who is next to the second
+        let region = |id: usize| Region::new(RegionId(id), PlayerId(id), 3, vec![(id as isize, 0)]);
+        let mut game_state =
+            GameState::from_board(Board::from_regions((0..3).map(region).collect()), 3);
+        game_state.turn_of_player = PlayerId(1);
+        let mut ai_players = AiPlayers::default();
+        ai_players.add_bot(PlayerId(1), Box::new(AiStrategy::Greedy));
+        ai_players.add_bot(PlayerId(2), Box::new(AiStrategy::Greedy));
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin::default())
+            .add_plugin(AssetPlugin::default())
+            .add_asset::<Mesh>()
+            .add_asset::<StandardMaterial>()
+            .init_resource::<CaptureAnimation>()
+            .init_resource::<DiceRollDuration>()
+            .insert_resource(FogOfWar(true))
+            .insert_resource(game_state)
+            .insert_resource(ai_players)
+            .add_event::<EventPlayerMoveStart>()
+            .add_system(event_attack_arrow);
+
+        for (player_1, player_2) in [(1, 0), (1, 2), (2, 1)] {
+            app.world.send_event(EventPlayerMoveStart {
+                region_1: region(player_1),
+                region_2: region(player_2),
+                player_1: PlayerId(player_1),
+            });
+        }
+        app.update();
+
+        let mut arrows = app.world.query::<&AttackArrow>();
+        assert_eq!(arrows.iter(&app.world).count(), 1);
+    }
+}
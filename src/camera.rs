@@ -0,0 +1,145 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+
+/// Radians the camera turns per pixel of mouse movement
+const ORBIT_SPEED: f32 = 0.005;
+/// Lowest and highest angle of the camera above the board
+const MIN_PITCH: f32 = 0.2;
+const MAX_PITCH: f32 = FRAC_PI_2 - 0.05;
+/// Change of the projection scale per line scrolled, relative to the current scale
+const ZOOM_SPEED: f32 = 0.1;
+const MIN_SCALE: f32 = 3.0;
+const MAX_SCALE: f32 = 20.0;
+/// Pixels of a touchpad scroll counting as one line of a mouse wheel
+const PIXELS_PER_LINE: f32 = 20.0;
+
+/// Resource that enables orbiting the board camera with the right mouse button and zooming
+/// with the scroll wheel. Turned off for deterministic screenshots.
+#[derive(Resource)]
+pub struct CameraControls {
+    pub enabled: bool,
+}
+
+impl Default for CameraControls {
+    fn default() -> Self {
+        CameraControls { enabled: true }
+    }
+}
+
+/// Position of a camera orbiting the center of the board
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+pub struct CameraController {
+    /// Angle around the vertical axis, 0 looks from the positive X axis
+    pub yaw: f32,
+    /// Angle above the board
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        // Looking at the board from (50, 32, 0)
+        CameraController {
+            yaw: 0.0,
+            pitch: 32.0_f32.atan2(50.0),
+            distance: Vec2::new(50.0, 32.0).length(),
+        }
+    }
+}
+
+impl CameraController {
+    pub fn transform(&self) -> Transform {
+        let horizontal = self.distance * self.pitch.cos();
+        let translation = Vec3::new(
+            horizontal * self.yaw.cos(),
+            self.distance * self.pitch.sin(),
+            -horizontal * self.yaw.sin(),
+        );
+
+        Transform::from_translation(translation).looking_at(Vec3::ZERO, Vec3::Y)
+    }
+
+    /// Turn the camera by a mouse movement of `delta` pixels
+    pub fn orbit(&mut self, delta: Vec2) {
+        self.yaw -= delta.x * ORBIT_SPEED;
+        self.pitch = (self.pitch + delta.y * ORBIT_SPEED).clamp(MIN_PITCH, MAX_PITCH);
+    }
+}
+
+/// Projection scale after scrolling by `lines`, positive lines zoom in
+pub fn zoomed_scale(scale: f32, lines: f32) -> f32 {
+    (scale * (1.0 - lines * ZOOM_SPEED)).clamp(MIN_SCALE, MAX_SCALE)
+}
+
+/// Orbit with the right mouse button held, which leaves left clicks to picking
+pub(crate) fn camera_controller(
+    camera_controls: Res<CameraControls>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    mut mouse_motion_reader: EventReader<MouseMotion>,
+    mut mouse_wheel_reader: EventReader<MouseWheel>,
+    mut query: Query<(&mut CameraController, &mut Transform, &mut Projection)>,
+) {
+    let delta: Vec2 = mouse_motion_reader.iter().map(|e| e.delta).sum();
+    let lines: f32 = mouse_wheel_reader
+        .iter()
+        .map(|e| match e.unit {
+            MouseScrollUnit::Line => e.y,
+            MouseScrollUnit::Pixel => e.y / PIXELS_PER_LINE,
+        })
+        .sum();
+
+    if !camera_controls.enabled {
+        return;
+    }
+
+    for (mut controller, mut transform, mut projection) in query.iter_mut() {
+        if mouse_buttons.pressed(MouseButton::Right) && delta != Vec2::ZERO {
+            controller.orbit(delta);
+            *transform = controller.transform();
+        }
+
+        if lines != 0.0 {
+            if let Projection::Orthographic(orthographic) = projection.as_mut() {
+                orthographic.scale = zoomed_scale(orthographic.scale, lines);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_controller_keeps_original_view() {
+        let transform = CameraController::default().transform();
+
+        assert!(transform
+            .translation
+            .abs_diff_eq(Vec3::new(50.0, 32.0, 0.0), 1e-3));
+    }
+
+    #[test]
+    fn orbit_keeps_camera_above_board() {
+        let mut controller = CameraController::default();
+
+        controller.orbit(Vec2::new(300.0, 10_000.0));
+        assert_eq!(controller.pitch, MAX_PITCH);
+        controller.orbit(Vec2::new(0.0, -20_000.0));
+        assert_eq!(controller.pitch, MIN_PITCH);
+
+        let transform = controller.transform();
+        assert!(transform.translation.y > 0.0);
+        assert!((transform.translation.length() - controller.distance).abs() < 1e-3);
+    }
+
+    #[test]
+    fn zoom_is_clamped() {
+        assert!(zoomed_scale(10.0, 1.0) < 10.0);
+        assert!(zoomed_scale(10.0, -1.0) > 10.0);
+        assert_eq!(zoomed_scale(10.0, 100.0), MIN_SCALE);
+        assert_eq!(zoomed_scale(10.0, -100.0), MAX_SCALE);
+    }
+}
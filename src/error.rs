@@ -0,0 +1,97 @@
+use std::fmt;
+
+/// Errors returned by operations on a board or a game state
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameError {
+    /// Region id is out of range of the board
+    InvalidRegion(usize),
+    /// Player index is out of range of the game
+    InvalidPlayer(usize),
+    /// Region doesn't belong to a player whose turn it is
+    NotYourRegion(usize),
+    /// Region has already attacked or retreated this turn
+    RegionAlreadyActed(usize),
+    /// Regions don't share a border or belong to the same player
+    NotOpponents { region_1: usize, region_2: usize },
+    /// Regions aren't connected through regions of the same owner
+    NotConnected { from: usize, to: usize },
+    /// Region doesn't have enough dice to give away
+    NotEnoughDice { region: usize, num_dice: usize },
+    /// Region would hold more dice than allowed
+    DiceCapExceeded { region: usize, num_dice: usize },
+    /// Region is already fortified to the maximum level
+    FortifyCapReached(usize),
+    /// Dice can't have this many sides
+    InvalidDiceSides(u32),
+    /// Replay can't be decoded
+    InvalidReplay(&'static str),
+    /// Board breaks one of its invariants
+    InvalidBoard { region: usize, reason: &'static str },
+    /// Board can't be generated with these settings
+    InvalidBoardConfig(&'static str),
+    /// Board generation found no room for a patch within its attempts
+    BoardGenerationFailed { patch: usize },
+    /// Character of a board map isn't an owner digit or `.`. Columns don't count whitespace.
+    InvalidMap { row: usize, column: usize },
+    /// Some player had no attack on every board generated within the attempts
+    NoPlayableBoard { attempts: usize },
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameError::InvalidRegion(id) => write!(f, "region {} doesn't exist", id),
+            GameError::InvalidPlayer(player) => write!(f, "player {} doesn't exist", player),
+            GameError::NotYourRegion(id) => {
+                write!(f, "region {} doesn't belong to the current player", id)
+            }
+            GameError::RegionAlreadyActed(id) => {
+                write!(f, "region {} has already acted this turn", id)
+            }
+            GameError::NotOpponents { region_1, region_2 } => {
+                write!(f, "regions {} and {} can't clash", region_1, region_2)
+            }
+            GameError::NotConnected { from, to } => {
+                write!(f, "regions {} and {} aren't connected", from, to)
+            }
+            GameError::NotEnoughDice { region, num_dice } => {
+                write!(f, "region {} can't give away {} dice", region, num_dice)
+            }
+            GameError::DiceCapExceeded { region, num_dice } => {
+                write!(f, "region {} can't hold {} dice", region, num_dice)
+            }
+            GameError::FortifyCapReached(id) => {
+                write!(f, "region {} can't be fortified any further", id)
+            }
+            GameError::InvalidDiceSides(sides) => {
+                write!(f, "dice can't have {} sides", sides)
+            }
+            GameError::InvalidReplay(reason) => write!(f, "invalid replay: {}", reason),
+            GameError::InvalidBoard { region, reason } => {
+                write!(f, "invalid board at region {}: {}", region, reason)
+            }
+            GameError::InvalidBoardConfig(reason) => {
+                write!(f, "invalid board settings: {}", reason)
+            }
+            GameError::BoardGenerationFailed { patch } => {
+                write!(f, "no room found for patch {} of the board", patch)
+            }
+            GameError::InvalidMap { row, column } => {
+                write!(
+                    f,
+                    "unexpected map character at row {}, column {}",
+                    row, column
+                )
+            }
+            GameError::NoPlayableBoard { attempts } => {
+                write!(
+                    f,
+                    "no board in {} attempts lets every player attack",
+                    attempts
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameError {}
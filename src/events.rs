@@ -1,59 +1,160 @@
-use std::collections::HashMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
-use rand::Rng;
-
 use bevy::prelude::*;
 use bevy_dice::{DiceRollResult, DiceRollStartEvent};
 use bevy_kira_audio::prelude::*;
+use bevy_mod_outline::Outline;
 use bevy_mod_picking::{PickingEvent, SelectionEvent};
+use rand::Rng;
 
-use crate::board::{draw_board, StackRankDiceGameBoardElement};
-use crate::game::{GameLogEntry, SelectedRegion};
-use crate::game::{GameState, Region};
-use crate::tiered_prng::PrngMapResource;
-use crate::ui::{DiceRollUI, StackRankDiceUI};
+use crate::ai::AiPlayers;
+use crate::audio::AudioSettings;
+use crate::board::{
+    player_color, CaptureAnimation, Palette, RedrawBoard, StackRankDiceGameBoardElement,
+};
+use crate::game::{DiceKind, GameAction, GameLogEntry, Rules, SelectedRegion, TieRule};
+use crate::game::{GameState, PlayerId, PlayerRoster, Region, RegionId, ReinforcementReserve};
+use crate::highlights::ClashSpotlight;
+use crate::recap::{recap_frames, Recap, RecapAutoplay, ReplayResource};
+use crate::rng_dice::DiceMode;
+use crate::stats::{MatchClock, SessionStats};
+use crate::tiered_prng::{PrngAiResource, PrngDiceResource, PrngEnvResource};
+use crate::ui::{
+    BoardCamera, DiceRollUI, EliminationBanner, HotSeat, PlayerNames, StackRankDiceUI,
+};
 
 /// Event that is fired when two regions on a map are entering a clash
-#[allow(dead_code)]
 pub(crate) struct EventPlayerMoveStart {
-    region_1: Region,
-    region_2: Region,
-    player_1: usize,
-    player_2: usize,
+    pub(crate) region_1: Region,
+    pub(crate) region_2: Region,
+    pub(crate) player_1: PlayerId,
+}
+
+/// Key transitions of a game, mirrored from internal events for external observers such as
+/// analytics overlays. Events of a clash are sent in order over several frames:
+/// `MoveStarted` when the dice are thrown, `DiceRolled` once they've settled, then
+/// `RegionCaptured` when the clash is resolved, followed by `TerritorySplit` if the losing
+/// player's regions fell apart. `TurnChanged` follows the clash or pass
+/// ending a turn, and `GameOver` is sent once, after the last events of the final clash.
+/// A rerolled tie is sent as two clashes, the first one without a `RegionCaptured`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum GameEvent {
+    MoveStarted {
+        player: PlayerId,
+        attacker: RegionId,
+        defender: RegionId,
+    },
+    /// Dice sums of both sides, the defender's including its fortify bonus
+    DiceRolled {
+        attacker_sum: usize,
+        defender_sum: usize,
+    },
+    /// A region changed hands from player `from` to player `to`
+    RegionCaptured {
+        region: RegionId,
+        from: PlayerId,
+        to: PlayerId,
+    },
+    /// Regions of a player fell apart into more groups after losing a region
+    TerritorySplit {
+        player: PlayerId,
+    },
+    TurnChanged {
+        player: PlayerId,
+    },
+    GameOver {
+        winner: PlayerId,
+    },
 }
 
+/// Resource for an optional confirmation step between choosing an attack and starting a clash
+#[derive(Resource)]
+pub struct AttackConfirmation {
+    pub enabled: bool,
+    /// Attacking and defending region ids of an attack waiting for confirmation
+    pub armed: Option<(RegionId, RegionId)>,
+}
+
+impl Default for AttackConfirmation {
+    fn default() -> Self {
+        AttackConfirmation {
+            enabled: true,
+            armed: None,
+        }
+    }
+}
+
+/// Resource with how long dice of a clash stay on the table before the clash is resolved
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DiceRollDuration(pub Duration);
+
+impl Default for DiceRollDuration {
+    fn default() -> Self {
+        DiceRollDuration(Duration::from_secs(3))
+    }
+}
+
+/// Resource with a region under the keyboard cursor, moved between neighbouring regions
+/// with arrow keys
+#[derive(Resource, Default)]
+pub struct RegionCursor {
+    pub current: Option<RegionId>,
+}
+
+/// Resource with the file a game is saved to whenever a turn ends
+#[derive(Resource, Default)]
+pub struct Autosave(pub Option<PathBuf>);
+
 /// Event that is fired when a clash between two regions on a map is resolved
 /// and the winner is determined
-#[allow(dead_code)]
 pub(crate) struct EventPlayerMoveEnd {
-    player_1: usize,
-    player_2: usize,
     region_1: Region,
     region_2: Region,
-    region_1_dice_result: Vec<usize>,
-    region_2_dice_result: Vec<usize>,
+    attacker_won: bool,
+}
+
+/// Event that is fired when a player pulls dice back from a region
+pub(crate) struct EventRetreat {
+    from_region: RegionId,
+    to_region: RegionId,
+    num_dice: usize,
+}
+
+/// Event that is fired when a player fortifies a region
+pub(crate) struct EventFortify {
+    region: RegionId,
+}
+
+/// Event that is fired when a player ends their turn with moves left
+pub(crate) struct EventPlayerPass {
+    // An index of a player
+    player: PlayerId,
+}
+
+/// Event that is fired when a player takes back the last move
+pub(crate) struct EventUndo;
+
+/// Event that is fired when a player loses their last region
+pub(crate) struct EventPlayerEliminated {
+    // An index of a player
+    player: PlayerId,
 }
 
 /// Event that is fired when a played has won a game
 pub(crate) struct EventGameOver {
     // An index of a winner
-    winner: usize,
+    winner: PlayerId,
 }
 
 /// Event that is fired when a turn of a player is started
-#[allow(dead_code)]
 pub(crate) struct EventTurnStart {
     // An index of a player
-    player: usize,
+    player: PlayerId,
 }
 
-/// Event that is fired when a turn of a player is started
-#[allow(dead_code)]
-pub(crate) struct EventTurnEnd {
-    // An index of a player
-    player: usize,
-}
+/// Event that is fired when a turn of a player is over
+pub(crate) struct EventTurnEnd;
 
 pub(crate) fn filter_just_selected_event(
     mut event_reader: EventReader<PickingEvent>,
@@ -67,35 +168,61 @@ pub(crate) fn filter_just_selected_event(
     None
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn event_region_selected(
     mut selected_region: ResMut<SelectedRegion>,
     picking_events: EventReader<PickingEvent>,
     regions: Query<(Entity, &Region)>,
     game_state: Res<GameState>,
+    keys: Res<Input<KeyCode>>,
+    hot_seat: Res<HotSeat>,
+    ai_players: Res<AiPlayers>,
+    replay: Option<Res<ReplayResource>>,
+    rules: Res<Rules>,
+    mut attack_confirmation: ResMut<AttackConfirmation>,
     mut event_writer: EventWriter<EventPlayerMoveStart>,
+    mut event_retreat_writer: EventWriter<EventRetreat>,
 ) {
     let selected_entity = filter_just_selected_event(picking_events);
 
-    if selected_entity.is_none() {
+    // Ignore picking until the next player confirms they are ready,
+    // during turns of AI players and replays
+    if selected_entity.is_none()
+        || hot_seat.waiting_for_ready
+        || ai_players.is_ai(game_state.turn_of_player)
+        || replay.is_some()
+    {
         return;
     }
 
     let region = regions.get(selected_entity.unwrap()).unwrap().1;
+    // Any pick other than the armed target cancels an armed attack
+    let armed = attack_confirmation.armed.take();
 
     if region.owner != game_state.turn_of_player {
-        if selected_region.region.is_some() {
-            let region_1 = selected_region.region.clone().unwrap();
-            let region_2 = region.clone();
-            if region_1.is_opponent(&region_2) {
-                // Attack a neighbour
-                let event = EventPlayerMoveStart {
-                    player_1: region_1.owner,
-                    player_2: region_2.owner,
-                    region_1,
-                    region_2,
-                };
-                event_writer.send(event);
-            }
+        pick_opponent_region(
+            region,
+            armed,
+            &mut selected_region,
+            &game_state,
+            &rules,
+            &mut attack_confirmation,
+            &mut event_writer,
+        );
+    } else if selected_region.region.is_some()
+        && (keys.pressed(KeyCode::LShift) || keys.pressed(KeyCode::RShift))
+    {
+        // Retreat all but one dice to another own region
+        let region_1 = selected_region.region.clone().unwrap();
+        if region_1.id != region.id {
+            event_retreat_writer.send(EventRetreat {
+                from_region: region_1.id,
+                to_region: region.id,
+                num_dice: usize::min(
+                    region_1.num_dice - 1,
+                    game_state.board.dice_cap(region) - region.num_dice,
+                ),
+            });
         }
 
         selected_region.deselect();
@@ -104,9 +231,340 @@ pub(crate) fn event_region_selected(
     }
 }
 
+pub(crate) fn attack_confirmation_input(
+    keys: Res<Input<KeyCode>>,
+    game_state: Res<GameState>,
+    mut selected_region: ResMut<SelectedRegion>,
+    mut attack_confirmation: ResMut<AttackConfirmation>,
+    mut event_writer: EventWriter<EventPlayerMoveStart>,
+) {
+    let (region_1_id, region_2_id) = match attack_confirmation.armed {
+        Some(attack) => attack,
+        None => return,
+    };
+
+    // Selection changed through other input, e.g. fortifying
+    if selected_region.region.as_ref().map(|r| r.id) != Some(region_1_id) {
+        attack_confirmation.armed = None;
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::Escape) {
+        attack_confirmation.armed = None;
+        selected_region.deselect();
+    } else if keys.just_pressed(KeyCode::Return) {
+        let region_1 = game_state.board.regions[region_1_id].clone();
+        let region_2 = game_state.board.regions[region_2_id].clone();
+
+        event_writer.send(EventPlayerMoveStart {
+            player_1: region_1.owner,
+            region_1,
+            region_2,
+        });
+
+        attack_confirmation.armed = None;
+        selected_region.deselect();
+    }
+}
+
+/// Move the region cursor with arrow keys, and pick the region under it with Enter the same
+/// way as picking it with a mouse
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn region_cursor_input(
+    keys: Res<Input<KeyCode>>,
+    mut region_cursor: ResMut<RegionCursor>,
+    mut selected_region: ResMut<SelectedRegion>,
+    regions: Query<(Entity, &Region)>,
+    camera_query: Query<&GlobalTransform, With<BoardCamera>>,
+    game_state: Res<GameState>,
+    (hot_seat, ai_players, replay): (Res<HotSeat>, Res<AiPlayers>, Option<Res<ReplayResource>>),
+    rules: Res<Rules>,
+    mut attack_confirmation: ResMut<AttackConfirmation>,
+    mut event_writer: EventWriter<EventPlayerMoveStart>,
+) {
+    if hot_seat.waiting_for_ready || ai_players.is_ai(game_state.turn_of_player) || replay.is_some()
+    {
+        return;
+    }
+
+    // Arrow keys follow the board as seen by the camera
+    let (forward, right) = match camera_query.get_single() {
+        Ok(transform) => (transform.forward(), transform.right()),
+        Err(_) => (Vec3::NEG_Z, Vec3::X),
+    };
+    let direction = if keys.just_pressed(KeyCode::Up) {
+        Some(forward)
+    } else if keys.just_pressed(KeyCode::Down) {
+        Some(-forward)
+    } else if keys.just_pressed(KeyCode::Left) {
+        Some(-right)
+    } else if keys.just_pressed(KeyCode::Right) {
+        Some(right)
+    } else {
+        None
+    };
+
+    if let Some(direction) = direction {
+        region_cursor.current = match region_cursor.current {
+            // Start from the selected region or the first region of the current player
+            None => selected_region.region.as_ref().map(|r| r.id).or_else(|| {
+                game_state
+                    .player_regions
+                    .regions_of(game_state.turn_of_player)
+                    .first()
+                    .copied()
+            }),
+            Some(current) => game_state
+                .board
+                .neighbour_in_direction(current, (direction.x, direction.z))
+                .or(Some(current)),
+        };
+        return;
+    }
+
+    // Enter confirms an armed attack instead, see `attack_confirmation_input`
+    if !keys.just_pressed(KeyCode::Return) || attack_confirmation.armed.is_some() {
+        return;
+    }
+
+    let (entity, region) = match regions
+        .iter()
+        .find(|(_, region)| Some(region.id) == region_cursor.current)
+    {
+        Some(picked) => picked,
+        None => return,
+    };
+
+    if region.owner == game_state.turn_of_player {
+        selected_region.select(entity, region.clone());
+        return;
+    }
+
+    pick_opponent_region(
+        region,
+        None,
+        &mut selected_region,
+        &game_state,
+        &rules,
+        &mut attack_confirmation,
+        &mut event_writer,
+    );
+}
+
+/// Attack a picked opponent region from the selected region. With attack confirmation the
+/// attack is armed first and made once the armed target is picked again, the selection stays
+/// until then. Otherwise the selection is cleared.
+fn pick_opponent_region(
+    region_2: &Region,
+    armed: Option<(RegionId, RegionId)>,
+    selected_region: &mut SelectedRegion,
+    game_state: &GameState,
+    rules: &Rules,
+    attack_confirmation: &mut AttackConfirmation,
+    event_writer: &mut EventWriter<EventPlayerMoveStart>,
+) {
+    if let Some(region_1) = selected_region.region.clone() {
+        if game_state.is_legal_move_with_rules(&region_1, region_2, rules) {
+            let attack = (region_1.id, region_2.id);
+            if attack_confirmation.enabled && armed != Some(attack) {
+                attack_confirmation.armed = Some(attack);
+                return;
+            }
+
+            // Attack a neighbour
+            event_writer.send(EventPlayerMoveStart {
+                player_1: region_1.owner,
+                region_1,
+                region_2: region_2.clone(),
+            });
+        }
+    }
+
+    selected_region.deselect();
+}
+
+/// Outline the region under the keyboard cursor
+pub(crate) fn region_cursor_outline_update(
+    region_cursor: Res<RegionCursor>,
+    mut regions: Query<(&Region, &mut Outline)>,
+) {
+    for (region, mut outline) in regions.iter_mut() {
+        let (colour, width) = match region_cursor.current == Some(region.id) {
+            true => (Color::WHITE, 3.0),
+            false => (Color::rgba(0.0, 0.0, 0.0, 1.0), 0.5),
+        };
+
+        // Outlines of a redrawn board start out unmarked
+        if outline.width != width {
+            outline.colour = colour;
+            outline.width = width;
+        }
+    }
+}
+
+pub(crate) fn fortify_input(
+    keys: Res<Input<KeyCode>>,
+    hot_seat: Res<HotSeat>,
+    mut selected_region: ResMut<SelectedRegion>,
+    mut event_fortify_writer: EventWriter<EventFortify>,
+) {
+    if hot_seat.waiting_for_ready || !keys.just_pressed(KeyCode::F) {
+        return;
+    }
+
+    if let Some(region) = selected_region.region.as_ref() {
+        event_fortify_writer.send(EventFortify { region: region.id });
+        selected_region.deselect();
+    }
+}
+
+/// Take back the last move with Ctrl+Z. Moves of AI players and clashes still rolling
+/// can't be undone.
+pub(crate) fn undo_input(
+    keys: Res<Input<KeyCode>>,
+    hot_seat: Res<HotSeat>,
+    game_state: Res<GameState>,
+    ai_players: Res<AiPlayers>,
+    replay: Option<Res<ReplayResource>>,
+    dice_roll_timer_query: Query<&DiceRollTimer>,
+    mut event_undo_writer: EventWriter<EventUndo>,
+) {
+    let ctrl = keys.pressed(KeyCode::LControl) || keys.pressed(KeyCode::RControl);
+    if hot_seat.waiting_for_ready
+        || replay.is_some()
+        || !ctrl
+        || !keys.just_pressed(KeyCode::Z)
+        || !dice_roll_timer_query.is_empty()
+    {
+        return;
+    }
+
+    match game_state.game_log.last() {
+        Some(gl) if !ai_players.is_ai(gl.turn_of_player) => {
+            event_undo_writer.send(EventUndo);
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn event_turn_start(
+    mut event_turn_start_reader: EventReader<EventTurnStart>,
+    mut hot_seat: ResMut<HotSeat>,
+    mut selected_region: ResMut<SelectedRegion>,
+    ai_players: Res<AiPlayers>,
+) {
+    for e in event_turn_start_reader.iter() {
+        info!("Turn of player {} started", e.player.0 + 1);
+
+        selected_region.deselect();
+        // AI players don't need to hide the board from anyone
+        if hot_seat.enabled && !ai_players.is_ai(e.player) {
+            hot_seat.waiting_for_ready = true;
+        }
+    }
+}
+
+/// Save the game to the file of [`Autosave`], if there's one, once a turn is over
+pub(crate) fn autosave_on_turn_end(
+    mut event_turn_end_reader: EventReader<EventTurnEnd>,
+    autosave: Res<Autosave>,
+    game_state: Res<GameState>,
+    rules: Res<Rules>,
+    reinforcement_reserve: Res<ReinforcementReserve>,
+) {
+    let path = match &autosave.0 {
+        Some(path) => path,
+        None => return,
+    };
+
+    if event_turn_end_reader.iter().count() == 0 {
+        return;
+    }
+
+    if let Err(err) = game_state.save_to_path(&rules, &reinforcement_reserve, path) {
+        error!("Can't save game to {}: {}", path.display(), err);
+    }
+}
+
+/// Show a banner naming a player who has just lost their last region
+pub(crate) fn event_player_eliminated(
+    mut event_player_eliminated_reader: EventReader<EventPlayerEliminated>,
+    game_state: Res<GameState>,
+    player_names: Res<PlayerNames>,
+    mut banner_query: Query<(&mut Text, &mut Visibility, &mut EliminationBanner)>,
+) {
+    for e in event_player_eliminated_reader.iter() {
+        let name = player_names.name(game_state.players.id_of(e.player));
+        info!("{} eliminated", name);
+
+        for (mut text, mut visibility, mut banner) in banner_query.iter_mut() {
+            text.sections[0].value = format!("{} ELIMINATED", name);
+            visibility.is_visible = true;
+            banner.timer.reset();
+        }
+    }
+}
+
+/// Let an AI controlled player attack once the previous clash is resolved
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn ai_turn(
+    ai_players: Res<AiPlayers>,
+    game_state: Res<GameState>,
+    rules: Res<Rules>,
+    mut ai_prng: ResMut<PrngAiResource>,
+    replay: Option<Res<ReplayResource>>,
+    mut awaiting_clash_end: Local<bool>,
+    mut region_clash_end_event_reader: EventReader<EventPlayerMoveEnd>,
+    mut event_writer: EventWriter<EventPlayerMoveStart>,
+    mut event_player_pass_writer: EventWriter<EventPlayerPass>,
+) {
+    if region_clash_end_event_reader.iter().count() > 0 {
+        *awaiting_clash_end = false;
+    }
+
+    if *awaiting_clash_end || replay.is_some() {
+        return;
+    }
+
+    if !ai_players.is_ai(game_state.turn_of_player) {
+        return;
+    }
+
+    // Nothing is left to play once a single player owns the whole board
+    if game_state.is_game_over().is_some() {
+        return;
+    }
+
+    if let Some((region_1_id, region_2_id)) =
+        ai_players.choose_move(&game_state, &rules, &mut ai_prng.rng)
+    {
+        let region_1 = game_state.board.regions[region_1_id].clone();
+        let region_2 = game_state.board.regions[region_2_id].clone();
+
+        event_writer.send(EventPlayerMoveStart {
+            player_1: region_1.owner,
+            region_1,
+            region_2,
+        });
+        *awaiting_clash_end = true;
+    } else {
+        event_player_pass_writer.send(EventPlayerPass {
+            player: game_state.turn_of_player,
+        });
+    }
+}
+
+/// How long thrown dice may keep rolling once the dice roll is over, before they're
+/// rolled again without physics
+const DICE_SETTLE_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Component)]
 pub(crate) struct DiceRollTimer {
     timer: Timer,
+    /// Finish the timer as soon as the dice have settled
+    skip_requested: bool,
+    /// Started once `timer` is over, in case the dice never settle
+    settle_timeout: Timer,
 }
 
 pub(crate) fn event_player_move_start(
@@ -115,6 +573,8 @@ pub(crate) fn event_player_move_start(
     mut dice_roll_started_writer: EventWriter<DiceRollStartEvent>,
     mut dice_roll_view_query: Query<(Entity, &mut Visibility, &DiceRollUI)>,
     mut game_state: ResMut<GameState>,
+    dice_roll_duration: Res<DiceRollDuration>,
+    mut game_event_writer: EventWriter<GameEvent>,
 ) {
     let turn_of_player = game_state.turn_of_player;
     let turn_counter = game_state.turn_counter;
@@ -132,36 +592,128 @@ pub(crate) fn event_player_move_start(
             v.is_visible = true;
         }
 
-        game_state.game_log.push(GameLogEntry {
-            turn_of_player,
-            region_1: event.region_1.clone(),
-            region_2: event.region_2.clone(),
-            region_1_dice_result: Vec::new(),
-            region_2_dice_result: Vec::new(),
+        game_state.game_log.push(GameLogEntry::new(
+            GameAction::Clash,
             turn_counter,
-        });
+            turn_of_player,
+            event.region_1.clone(),
+            event.region_2.clone(),
+        ));
 
         dice_roll_started_writer.send(dice_roll_started);
+        game_event_writer.send(GameEvent::MoveStarted {
+            player: turn_of_player,
+            attacker: event.region_1.id,
+            defender: event.region_2.id,
+        });
 
         commands.spawn(()).insert(DiceRollTimer {
-            timer: Timer::new(Duration::from_secs(3), TimerMode::Once),
+            timer: Timer::new(dice_roll_duration.0, TimerMode::Once),
+            skip_requested: false,
+            settle_timeout: Timer::new(DICE_SETTLE_TIMEOUT, TimerMode::Once),
         });
     }
 }
 
+/// Dim regions which are not involved in a clash. The board is redrawn once the clash
+/// is resolved, which restores original materials.
+pub(crate) fn event_clash_spotlight(
+    mut region_clash_event_reader: EventReader<EventPlayerMoveStart>,
+    clash_spotlight: Res<ClashSpotlight>,
+    game_state: Res<GameState>,
+    regions: Query<(&Region, &Handle<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for event in region_clash_event_reader.iter() {
+        if !clash_spotlight.enabled {
+            continue;
+        }
+
+        let spotlight = game_state
+            .board
+            .clash_spotlight(event.region_1.id, event.region_2.id);
+
+        for (region, material_handle) in regions.iter() {
+            if spotlight.contains(&region.id) {
+                continue;
+            }
+
+            if let Some(material) = materials.get_mut(material_handle) {
+                let [r, g, b, a] = material.base_color.as_rgba_f32();
+                let dim = clash_spotlight.dim_factor;
+                material.base_color = Color::rgba(r * dim, g * dim, b * dim, a);
+            }
+        }
+    }
+}
+
 pub(crate) fn event_dice_roll_result(
     mut dice_rolls: EventReader<DiceRollResult>,
     mut game_state: ResMut<GameState>,
     asset_server: Res<AssetServer>,
-    audio: Res<bevy_kira_audio::prelude::Audio>,
+    (audio, audio_settings): (Res<bevy_kira_audio::prelude::Audio>, Res<AudioSettings>),
+    rules: Res<Rules>,
+    (dice_mode, mut dice_prng): (Res<DiceMode>, ResMut<PrngDiceResource>),
+    mut game_event_writer: EventWriter<GameEvent>,
 ) {
     for event in dice_rolls.iter() {
+        // Thrown dice always show six faces, other dice kinds are drawn once they settle
+        let dice_kind = game_state.dice_kind;
+        let values: Vec<Vec<usize>> = match *dice_mode {
+            DiceMode::Physics if dice_kind != DiceKind::default() => event
+                .values
+                .iter()
+                .map(|faces| dice_kind.roll(faces.len(), &mut dice_prng.rng))
+                .collect(),
+            _ => event.values.clone(),
+        };
+
         let last_log_entry = game_state.game_log.last_mut().unwrap();
+        // Dice of a clash that was rolled again after a settle timeout
+        if !last_log_entry.region_1_dice_result.is_empty() {
+            continue;
+        }
 
-        audio.play(asset_server.load("sounds/throw.wav"));
+        let throw_sound = audio_settings
+            .throw_sound(last_log_entry.region_1.num_dice + last_log_entry.region_2.num_dice);
+        audio
+            .play(asset_server.load("sounds/throw.wav"))
+            .with_playback_rate(throw_sound.playback_rate)
+            .with_volume(throw_sound.volume);
 
-        last_log_entry.region_1_dice_result = event.values[0].clone();
-        last_log_entry.region_2_dice_result = event.values[1].clone();
+        record_dice_roll(last_log_entry, values, &rules, &mut game_event_writer);
+    }
+}
+
+/// Fill in dice results of a clash and announce its sums
+fn record_dice_roll(
+    log_entry: &mut GameLogEntry,
+    values: Vec<Vec<usize>>,
+    rules: &Rules,
+    game_event_writer: &mut EventWriter<GameEvent>,
+) {
+    log_entry.region_1_dice_result = values[0].clone();
+    log_entry.region_2_dice_result = values[1].clone();
+    log_entry.resolve_with_tie_rule(rules.tie_rule);
+
+    game_event_writer.send(GameEvent::DiceRolled {
+        attacker_sum: log_entry.region_1_dice_sum(),
+        defender_sum: log_entry.region_2_dice_sum() + log_entry.region_2.fortify_bonus(),
+    });
+}
+
+/// Cut the dice roll short with Space. The clash is still resolved by
+/// `event_dice_rolls_complete`, once the dice have settled.
+pub(crate) fn skip_dice_roll_input(
+    keys: Res<Input<KeyCode>>,
+    mut dice_roll_timer_query: Query<&mut DiceRollTimer>,
+) {
+    if !keys.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    for mut dice_roll_timer in dice_roll_timer_query.iter_mut() {
+        dice_roll_timer.skip_requested = true;
     }
 }
 
@@ -170,12 +722,47 @@ pub(crate) fn event_dice_rolls_complete(
     mut dice_roll_timer_query: Query<(Entity, &mut DiceRollTimer)>,
     mut dice_roll_ui_query: Query<(Entity, &mut Visibility, &mut DiceRollUI)>,
     time: Res<Time>,
-    mut region_clash_end_event_writer: EventWriter<EventPlayerMoveEnd>,
+    (mut region_clash_end_event_writer, mut region_clash_event_writer): (
+        EventWriter<EventPlayerMoveEnd>,
+        EventWriter<EventPlayerMoveStart>,
+    ),
     mut game_state: ResMut<GameState>,
+    (rules, mut dice_prng, mut game_event_writer): (
+        Res<Rules>,
+        ResMut<PrngDiceResource>,
+        EventWriter<GameEvent>,
+    ),
 ) {
+    // Dice results of a clash are filled in once the dice have settled
+    let mut dice_settled = game_state
+        .game_log
+        .last()
+        .is_some_and(|gl| !gl.region_1_dice_result.is_empty());
+
     for (entity, mut fuse_timer) in dice_roll_timer_query.iter_mut() {
         fuse_timer.timer.tick(time.delta());
-        if fuse_timer.timer.finished() {
+
+        // Dice stuck on their edge, or thrown off the table, would hold up the game
+        if fuse_timer.timer.finished() && !dice_settled {
+            fuse_timer.settle_timeout.tick(time.delta());
+            if fuse_timer.settle_timeout.finished() {
+                warn!("Dice didn't settle, rolling them again");
+                let dice_kind = game_state.dice_kind;
+                let last_log_entry = game_state.game_log.last_mut().unwrap();
+                let values = [&last_log_entry.region_1, &last_log_entry.region_2]
+                    .map(|region| dice_kind.roll(region.num_dice, &mut dice_prng.rng))
+                    .to_vec();
+                record_dice_roll(last_log_entry, values, &rules, &mut game_event_writer);
+                dice_settled = true;
+            }
+        }
+
+        if fuse_timer.skip_requested && dice_settled {
+            let remaining = fuse_timer.timer.remaining();
+            fuse_timer.timer.tick(remaining);
+        }
+
+        if fuse_timer.timer.finished() && dice_settled {
             commands.entity(entity).despawn();
 
             for (_, mut v, _) in dice_roll_ui_query.iter_mut() {
@@ -184,13 +771,21 @@ pub(crate) fn event_dice_rolls_complete(
 
             let last_log_entry = game_state.game_log.last_mut().unwrap();
 
+            // Fight the clash again, a tie doesn't count as a move
+            if last_log_entry.is_tie && rules.tie_rule == TieRule::Reroll {
+                let last_log_entry = game_state.game_log.pop().unwrap();
+                region_clash_event_writer.send(EventPlayerMoveStart {
+                    player_1: last_log_entry.region_1.owner,
+                    region_1: last_log_entry.region_1,
+                    region_2: last_log_entry.region_2,
+                });
+                continue;
+            }
+
             region_clash_end_event_writer.send(EventPlayerMoveEnd {
-                player_1: last_log_entry.region_1.owner,
-                player_2: last_log_entry.region_2.owner,
                 region_1: last_log_entry.region_1.clone(),
                 region_2: last_log_entry.region_2.clone(),
-                region_1_dice_result: last_log_entry.region_1_dice_result.clone(),
-                region_2_dice_result: last_log_entry.region_2_dice_result.clone(),
+                attacker_won: last_log_entry.attacker_won,
             })
         }
     }
@@ -200,173 +795,889 @@ pub(crate) fn event_dice_rolls_complete(
 pub(crate) fn event_player_move_end(
     mut region_clash_end_event_reader: EventReader<EventPlayerMoveEnd>,
     mut game_state: ResMut<GameState>,
-    mut game_elements_query: Query<(Entity, &StackRankDiceGameBoardElement)>,
     asset_server: Res<AssetServer>,
-    mut commands: Commands,
-    meshes: ResMut<Assets<Mesh>>,
-    map_prng: ResMut<PrngMapResource>,
-    materials: ResMut<Assets<StandardMaterial>>,
+    mut env_prng: ResMut<PrngEnvResource>,
     mut selected_region: ResMut<SelectedRegion>,
+    mut capture_animation: ResMut<CaptureAnimation>,
+    rules: Res<Rules>,
     audio: Res<bevy_kira_audio::prelude::Audio>,
     mut event_game_over_writer: EventWriter<EventGameOver>,
-    mut event_turn_end_writer: EventWriter<EventTurnEnd>,
-    mut event_turn_start_writer: EventWriter<EventTurnStart>,
+    mut reinforcement_reserve: ResMut<ReinforcementReserve>,
+    mut redraw_board_writer: EventWriter<RedrawBoard>,
+    (
+        mut event_turn_end_writer,
+        mut event_turn_start_writer,
+        mut game_event_writer,
+        mut event_player_eliminated_writer,
+    ): (
+        EventWriter<EventTurnEnd>,
+        EventWriter<EventTurnStart>,
+        EventWriter<GameEvent>,
+        EventWriter<EventPlayerEliminated>,
+    ),
+    (dice_roll_timer_query, mut region_clash_event_reader): (
+        Query<(), With<DiceRollTimer>>,
+        EventReader<EventPlayerMoveStart>,
+    ),
 ) {
-    let mut rng = rand::thread_rng();
     let mut redraw_board = false;
+    // A clash being rolled for already counts as its region's action, but the turn can't end
+    // before it's resolved
+    let clash_in_flight =
+        !dice_roll_timer_query.is_empty() || region_clash_event_reader.iter().count() > 0;
 
     for e in region_clash_end_event_reader.iter() {
-        let result_1: usize = e.region_1_dice_result.iter().sum();
-        let result_2: usize = e.region_2_dice_result.iter().sum();
-
-        if result_1 > result_2 {
-            // win a region
-            game_state.board.regions[e.region_2.id].owner = e.region_1.owner;
-            if e.region_1.num_dice > 1 {
-                game_state.board.regions[e.region_2.id].num_dice =
-                    rng.gen_range(1..e.region_1.num_dice);
-                game_state.board.regions[e.region_1.id].num_dice -=
-                    game_state.board.regions[e.region_2.id].num_dice - 1;
+        debug_assert!(
+            game_state.board.ids_match_indices(),
+            "region ids don't match their indices"
+        );
+
+        capture_animation.transfer = match game_state.resolve_clash(
+            e.region_1.id,
+            e.region_2.id,
+            e.attacker_won,
+            &rules,
+            &mut env_prng.rng,
+        ) {
+            Ok(capture_result) => {
+                game_state.log_capture(&capture_result);
+                for region in [&e.region_1, &e.region_2] {
+                    let owner = game_state.board.regions[region.id].owner;
+                    if owner != region.owner {
+                        game_event_writer.send(GameEvent::RegionCaptured {
+                            region: region.id,
+                            from: region.owner,
+                            to: owner,
+                        });
+                    }
+                }
+                if capture_result.split_occurred {
+                    game_event_writer.send(GameEvent::TerritorySplit {
+                        player: capture_result.losing_player,
+                    });
+                }
+                let losing_player = capture_result.losing_player;
+                if game_state
+                    .player_regions
+                    .regions_of(losing_player)
+                    .is_empty()
+                {
+                    event_player_eliminated_writer.send(EventPlayerEliminated {
+                        player: losing_player,
+                    });
+                }
+                capture_result.transfer
             }
+            Err(err) => {
+                error!("Failed to resolve a clash: {}", err);
+                None
+            }
+        };
 
+        if e.attacker_won {
             audio.play(asset_server.load("sounds/win.wav"));
         } else {
-            // lose a region
-            game_state.board.regions[e.region_1.id].owner = e.region_2.owner;
-            if e.region_2.num_dice > 1 {
-                game_state.board.regions[e.region_1.id].num_dice =
-                    rng.gen_range(1..e.region_2.num_dice);
-                game_state.board.regions[e.region_2.id].num_dice -=
-                    game_state.board.regions[e.region_1.id].num_dice - 1;
-            }
-
             audio.play(asset_server.load("sounds/loss.wav"));
         }
 
-        for (e, _) in game_elements_query.iter_mut() {
-            commands.entity(e).despawn_recursive();
-        }
-
         redraw_board = true;
     }
 
-    // check whether it's time to switch turn
-    let region_made_move_this_turn: Vec<Region> = game_state
-        .game_log
-        .iter()
-        .filter(|gl| {
-            gl.turn_counter == game_state.turn_counter
-                && gl.turn_of_player == game_state.turn_of_player
-        })
-        .map(|gl| gl.region_1.clone())
-        .collect();
+    // The game is over and has already been announced
+    if game_state.is_game_over().is_some() && !redraw_board {
+        return;
+    }
 
-    let regions_able_to_move_this_turn = game_state.board.regions.iter().filter(|gl| {
-        gl.owner == game_state.turn_of_player
-            && region_made_move_this_turn
-                .iter()
-                .filter(|r| r.id == gl.id)
-                .count()
-                == 0
-    });
+    let regions_able_to_move_this_turn = game_state
+        .player_regions
+        .regions_of(game_state.turn_of_player)
+        .iter()
+        .filter(|id| game_state.can_attack(**id, rules.capture_bonus))
+        .map(|id| &game_state.board.regions[*id])
+        .filter(|region| region.num_dice >= rules.min_attack_dice.0);
 
     // check whether it's time to switch turn
     let number_of_unblocked_regions = regions_able_to_move_this_turn
         .filter(|r1| {
             game_state
                 .board
-                .regions
+                .neighbours(r1.id)
                 .iter()
-                .filter(|r2| r2.is_opponent(r1))
-                .count()
-                > 0
+                .copied()
+                .any(|id| game_state.board.is_opponent(r1.id, id))
         })
         .count();
 
-    if number_of_unblocked_regions == 0 {
-        event_turn_end_writer.send(EventTurnEnd {
-            player: game_state.turn_of_player,
-        });
+    if number_of_unblocked_regions == 0 && !clash_in_flight {
+        end_turn(
+            &mut game_state,
+            &mut reinforcement_reserve,
+            &rules,
+            &mut env_prng.rng,
+            &mut event_turn_end_writer,
+            &mut event_turn_start_writer,
+            &mut game_event_writer,
+        );
+    }
 
-        game_state.turn_of_player += 1;
-        if game_state.turn_of_player >= game_state.number_of_players {
-            game_state.turn_of_player = 0;
-        }
+    // check whether it's time to end the game
+    if let Some(winner) = game_state.is_game_over() {
+        event_game_over_writer.send(EventGameOver { winner });
+        game_event_writer.send(GameEvent::GameOver { winner });
+        return;
+    }
 
-        event_turn_start_writer.send(EventTurnStart {
-            player: game_state.turn_of_player,
-        });
+    if redraw_board {
+        selected_region.deselect();
+        redraw_board_writer.send(RedrawBoard);
+    }
+}
 
-        game_state.turn_counter += 1;
+pub(crate) fn event_retreat(
+    mut event_retreat_reader: EventReader<EventRetreat>,
+    mut game_state: ResMut<GameState>,
+    mut redraw_board_writer: EventWriter<RedrawBoard>,
+) {
+    for e in event_retreat_reader.iter() {
+        match game_state.retreat(e.from_region, e.to_region, e.num_dice) {
+            Ok(()) => redraw_board_writer.send(RedrawBoard),
+            Err(err) => info!("Retreat rejected: {}", err),
+        }
     }
+}
 
-    // check whether it's time to end the game
-    let mut regions_by_player: HashMap<usize, usize> = HashMap::new();
-    for region in game_state.board.regions.iter() {
-        let current_count = *regions_by_player.get(&region.owner).unwrap_or(&0);
-        regions_by_player.insert(region.owner, current_count + 1);
+/// End the turn of the current player with [`GameState::end_turn`] and announce who
+/// plays next
+fn end_turn(
+    game_state: &mut GameState,
+    reinforcement_reserve: &mut ReinforcementReserve,
+    rules: &Rules,
+    rng: &mut impl Rng,
+    event_turn_end_writer: &mut EventWriter<EventTurnEnd>,
+    event_turn_start_writer: &mut EventWriter<EventTurnStart>,
+    game_event_writer: &mut EventWriter<GameEvent>,
+) {
+    game_state.end_turn(rules, reinforcement_reserve, rng);
+    event_turn_end_writer.send(EventTurnEnd);
+
+    event_turn_start_writer.send(EventTurnStart {
+        player: game_state.turn_of_player,
+    });
+    game_event_writer.send(GameEvent::TurnChanged {
+        player: game_state.turn_of_player,
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn event_player_pass(
+    mut event_player_pass_reader: EventReader<EventPlayerPass>,
+    mut game_state: ResMut<GameState>,
+    rules: Res<Rules>,
+    mut env_prng: ResMut<PrngEnvResource>,
+    mut reinforcement_reserve: ResMut<ReinforcementReserve>,
+    mut event_turn_end_writer: EventWriter<EventTurnEnd>,
+    mut event_turn_start_writer: EventWriter<EventTurnStart>,
+    mut game_event_writer: EventWriter<GameEvent>,
+    mut redraw_board_writer: EventWriter<RedrawBoard>,
+) {
+    for e in event_player_pass_reader.iter() {
+        // Stale pass of a turn that has already ended
+        if e.player != game_state.turn_of_player {
+            continue;
+        }
+
+        end_turn(
+            &mut game_state,
+            &mut reinforcement_reserve,
+            &rules,
+            &mut env_prng.rng,
+            &mut event_turn_end_writer,
+            &mut event_turn_start_writer,
+            &mut game_event_writer,
+        );
+        redraw_board_writer.send(RedrawBoard);
     }
+}
 
-    for (player, number_of_regions) in regions_by_player.iter() {
-        if *number_of_regions == game_state.board.regions.len() {
-            event_game_over_writer.send(EventGameOver { winner: *player });
-            return;
+pub(crate) fn event_fortify(
+    mut event_fortify_reader: EventReader<EventFortify>,
+    mut game_state: ResMut<GameState>,
+    mut redraw_board_writer: EventWriter<RedrawBoard>,
+) {
+    for e in event_fortify_reader.iter() {
+        match game_state.fortify(e.region) {
+            Ok(()) => redraw_board_writer.send(RedrawBoard),
+            Err(err) => info!("Fortify rejected: {}", err),
         }
     }
+}
 
-    if redraw_board {
-        selected_region.deselect();
-        draw_board(
-            asset_server,
-            commands,
-            meshes,
-            map_prng,
-            materials,
-            game_state,
-        );
+pub(crate) fn event_undo(
+    mut event_undo_reader: EventReader<EventUndo>,
+    mut game_state: ResMut<GameState>,
+    rules: Res<Rules>,
+    mut reinforcement_reserve: ResMut<ReinforcementReserve>,
+    mut selected_region: ResMut<SelectedRegion>,
+    mut redraw_board_writer: EventWriter<RedrawBoard>,
+    mut game_event_writer: EventWriter<GameEvent>,
+) {
+    for _ in event_undo_reader.iter() {
+        let turn_counter = game_state.turn_counter;
+        if let Some(gl) = game_state.undo_last_move(&rules, &mut reinforcement_reserve) {
+            info!(
+                "Player {} took back a move of region {}",
+                gl.turn_of_player.0 + 1,
+                gl.region_1.id.0
+            );
+            if game_state.turn_counter != turn_counter {
+                game_event_writer.send(GameEvent::TurnChanged {
+                    player: game_state.turn_of_player,
+                });
+            }
+            selected_region.deselect();
+            redraw_board_writer.send(RedrawBoard);
+        }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn event_game_over(
     mut commands: Commands,
     mut event_game_over_reader: EventReader<EventGameOver>,
     mut game_elements_query: Query<(Entity, &StackRankDiceGameBoardElement)>,
     mut game_ui_elements_query: Query<(Entity, &StackRankDiceUI)>,
     asset_server: Res<AssetServer>,
+    game_state: Res<GameState>,
+    recap_autoplay: Res<RecapAutoplay>,
+    mut session_stats: ResMut<SessionStats>,
+    mut match_clock: ResMut<MatchClock>,
+    palette: Res<Palette>,
+    player_names: Res<PlayerNames>,
     _audio: Res<bevy_kira_audio::prelude::Audio>,
 ) {
     for e in event_game_over_reader.iter() {
-        for (e, _) in game_elements_query.iter_mut() {
+        match_clock.stopped = true;
+        session_stats.record_game(&game_state.game_log, e.winner);
+
+        for (e, _) in game_ui_elements_query.iter_mut() {
             commands.entity(e).despawn_recursive();
         }
 
-        for (e, _) in game_ui_elements_query.iter_mut() {
+        // Stats are shown once the recap is over
+        if recap_autoplay.enabled {
+            commands.insert_resource(Recap::new(
+                recap_frames(&game_state),
+                e.winner,
+                recap_autoplay.step,
+            ));
+            continue;
+        }
+
+        for (e, _) in game_elements_query.iter_mut() {
             commands.entity(e).despawn_recursive();
         }
 
+        spawn_game_over_screen(
+            &mut commands,
+            &asset_server,
+            e.winner,
+            &session_stats,
+            &game_state.players,
+            *palette,
+            &player_names,
+        );
+
+        // _audio.play(asset_server.load("sounds/game_over.wav"));
+    }
+}
+
+/// Text with the winner and fun facts of the session
+pub(crate) fn spawn_game_over_screen(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    winner: PlayerId,
+    session_stats: &SessionStats,
+    players: &PlayerRoster,
+    palette: Palette,
+    player_names: &PlayerNames,
+) {
+    commands
+        .spawn(
+            TextBundle::from_section(
+                format!("{} wins!", player_names.name(players.id_of(winner))),
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 50.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_text_alignment(TextAlignment::TOP_CENTER)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Percent(50.0),
+                    left: Val::Percent(45.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(StackRankDiceUI);
+
+    if let Some(chain) = session_stats.longest_capture_chain {
         commands
             .spawn(
                 TextBundle::from_section(
-                    format!("Player {} wins!", e.winner + 1),
+                    format!(
+                        "Longest capture chain: {} took {} regions on turn {}",
+                        player_names.name(players.id_of(chain.player)),
+                        chain.captures,
+                        chain.turn_counter + 1
+                    ),
                     TextStyle {
                         font: asset_server.load("fonts/FiraSans-Bold.ttf"),
-                        font_size: 50.0,
-                        color: Color::WHITE,
+                        font_size: 30.0,
+                        color: player_color(players.id_of(chain.player), palette),
                     },
                 )
                 .with_text_alignment(TextAlignment::TOP_CENTER)
                 .with_style(Style {
                     position_type: PositionType::Absolute,
                     position: UiRect {
-                        bottom: Val::Percent(50.0),
-                        left: Val::Percent(45.0),
+                        bottom: Val::Percent(42.0),
+                        left: Val::Percent(35.0),
                         ..default()
                     },
                     ..default()
                 }),
             )
             .insert(StackRankDiceUI);
+    }
+}
 
-        // _audio.play(asset_server.load("sounds/game_over.wav"));
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use bevy::asset::AssetPlugin;
+    use bevy::core::CorePlugin;
+
+    use super::*;
+    use crate::game::{Board, PlayerRegions, PlayerRoster};
+    use crate::tiered_prng::{PrngPlugin, PrngResource};
+
+    fn app_with_region(waiting_for_ready: bool) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_event::<PickingEvent>()
+            .add_event::<EventPlayerMoveStart>()
+            .add_event::<EventRetreat>()
+            .init_resource::<SelectedRegion>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<AiPlayers>()
+            .init_resource::<AttackConfirmation>()
+            .init_resource::<Rules>()
+            .insert_resource(HotSeat {
+                enabled: true,
+                waiting_for_ready,
+            })
+            .insert_resource(GameState::from_board(Board::default(), 2))
+            .add_system(event_region_selected);
+
+        let entity = app.world.spawn(Region::default()).id();
+        (app, entity)
+    }
+
+    fn pick(app: &mut App, entity: Entity) {
+        app.world
+            .send_event(PickingEvent::Selection(SelectionEvent::JustSelected(
+                entity,
+            )));
+        app.update();
+    }
+
+    #[test]
+    fn picking_is_ignored_while_waiting_for_ready() {
+        let (mut app, entity) = app_with_region(true);
+
+        pick(&mut app, entity);
+
+        let selected_region = app.world.resource::<SelectedRegion>();
+        assert!(selected_region.entity.is_none());
+    }
+
+    #[test]
+    fn picking_selects_region_once_ready() {
+        let (mut app, entity) = app_with_region(false);
+
+        pick(&mut app, entity);
+
+        let selected_region = app.world.resource::<SelectedRegion>();
+        assert_eq!(selected_region.entity, Some(entity));
+    }
+
+    fn app_with_opponents(confirm_attacks: bool) -> (App, Entity, Entity) {
+        let (mut app, _) = app_with_region(false);
+        app.insert_resource(AttackConfirmation {
+            enabled: confirm_attacks,
+            armed: None,
+        });
+
+        let regions: Vec<Region> = (0..2)
+            .map(|id| Region::new(RegionId(id), PlayerId(id), 3, vec![(id as isize, 0)]))
+            .collect();
+        app.insert_resource(GameState::from_board(
+            Board::from_regions(regions.clone()),
+            2,
+        ));
+        let entities: Vec<Entity> = regions
+            .into_iter()
+            .map(|region| app.world.spawn(region).id())
+            .collect();
+
+        (app, entities[0], entities[1])
+    }
+
+    fn clash_started(app: &App) -> bool {
+        !app.world
+            .resource::<Events<EventPlayerMoveStart>>()
+            .is_empty()
+    }
+
+    #[test]
+    fn attack_starts_immediately_without_confirmation() {
+        let (mut app, own, opponent) = app_with_opponents(false);
+
+        pick(&mut app, own);
+        pick(&mut app, opponent);
+
+        assert!(clash_started(&app));
+    }
+
+    #[test]
+    fn attack_waits_for_confirmation() {
+        let (mut app, own, opponent) = app_with_opponents(true);
+
+        pick(&mut app, own);
+        pick(&mut app, opponent);
+
+        assert!(!clash_started(&app));
+        assert_eq!(
+            app.world.resource::<AttackConfirmation>().armed,
+            Some((RegionId(0), RegionId(1)))
+        );
+
+        // Picking the armed target again confirms the attack
+        pick(&mut app, opponent);
+
+        assert!(clash_started(&app));
+        assert!(app.world.resource::<AttackConfirmation>().armed.is_none());
+    }
+
+    /// Face shown by every dice of the attacker and of the defender, one entry per clash
+    #[derive(Resource, Default)]
+    struct ScriptedDice(VecDeque<[usize; 2]>);
+
+    /// Stands in for the dice plugin and answers every roll with the next scripted faces.
+    /// Dice never settle once the script runs out.
+    fn roll_scripted_dice(
+        mut dice_roll_start_reader: EventReader<DiceRollStartEvent>,
+        mut dice_roll_result_writer: EventWriter<DiceRollResult>,
+        mut scripted_dice: ResMut<ScriptedDice>,
+    ) {
+        for event in dice_roll_start_reader.iter() {
+            let faces = match scripted_dice.0.pop_front() {
+                Some(faces) => faces,
+                None => continue,
+            };
+            dice_roll_result_writer.send(DiceRollResult {
+                values: event
+                    .num_dice
+                    .iter()
+                    .zip(faces)
+                    .map(|(num_dice, face)| vec![face; *num_dice])
+                    .collect(),
+            });
+        }
+    }
+
+    /// A row of four regions: player 1, player 2, player 2, player 1
+    fn app_with_clash_pipeline(scripted_dice: Vec<[usize; 2]>) -> App {
+        let board = Board::from_regions(
+            [0, 1, 1, 0]
+                .into_iter()
+                .enumerate()
+                .map(|(id, owner)| {
+                    Region::new(RegionId(id), PlayerId(owner), 4, vec![(id as isize, 0)])
+                })
+                .collect(),
+        );
+
+        let mut app = App::new();
+        app.add_plugin(CorePlugin::default())
+            .add_plugin(AssetPlugin::default())
+            .insert_resource(PrngResource {
+                world_seed: 4242,
+                env_seed: 17,
+            })
+            .add_plugin(PrngPlugin)
+            .init_resource::<Time>()
+            .init_resource::<bevy_kira_audio::prelude::Audio>()
+            .init_resource::<AudioSettings>()
+            .init_resource::<SelectedRegion>()
+            .init_resource::<Rules>()
+            .init_resource::<ReinforcementReserve>()
+            .init_resource::<DiceRollDuration>()
+            .init_resource::<DiceMode>()
+            .init_resource::<Input<KeyCode>>()
+            .insert_resource(CaptureAnimation {
+                enabled: false,
+                ..default()
+            })
+            .insert_resource(ScriptedDice(scripted_dice.into()))
+            .insert_resource(GameState::from_board(board, 2))
+            .add_event::<EventPlayerMoveStart>()
+            .add_event::<EventPlayerMoveEnd>()
+            .add_event::<DiceRollStartEvent>()
+            .add_event::<DiceRollResult>()
+            .add_event::<EventGameOver>()
+            .add_event::<EventTurnStart>()
+            .add_event::<EventTurnEnd>()
+            .add_event::<EventPlayerEliminated>()
+            .add_event::<GameEvent>()
+            .add_event::<RedrawBoard>()
+            .add_system(event_player_move_start)
+            .add_system(roll_scripted_dice)
+            .add_system(event_dice_roll_result)
+            .add_system(skip_dice_roll_input.before(event_dice_rolls_complete))
+            .add_system(event_dice_rolls_complete)
+            .add_system(event_player_move_end);
+
+        app
+    }
+
+    #[derive(Resource, Default)]
+    struct ObservedGameEvents(Vec<GameEvent>);
+
+    /// Drain game events every frame, as an external observer would
+    fn observe_game_events(
+        mut game_event_reader: EventReader<GameEvent>,
+        mut observed: ResMut<ObservedGameEvents>,
+    ) {
+        observed.0.extend(game_event_reader.iter().cloned());
+    }
+
+    #[derive(Resource, Default)]
+    struct ObservedEliminations(Vec<PlayerId>);
+
+    fn observe_eliminations(
+        mut event_player_eliminated_reader: EventReader<EventPlayerEliminated>,
+        mut observed: ResMut<ObservedEliminations>,
+    ) {
+        observed
+            .0
+            .extend(event_player_eliminated_reader.iter().map(|e| e.player));
+    }
+
+    #[derive(Resource, Default)]
+    struct ObservedWinners(Vec<PlayerId>);
+
+    /// Game over events are dropped two frames after they're sent, so they're drained
+    /// every frame
+    fn observe_winners(
+        mut event_game_over_reader: EventReader<EventGameOver>,
+        mut observed: ResMut<ObservedWinners>,
+    ) {
+        observed
+            .0
+            .extend(event_game_over_reader.iter().map(|e| e.winner));
+    }
+
+    /// Start a clash between two regions and run the app until the dice timer is over
+    fn play_clash(app: &mut App, region_1: usize, region_2: usize) {
+        start_clash(app, region_1, region_2);
+        run_for_seconds(app, 6);
+    }
+
+    fn start_clash(app: &mut App, region_1: usize, region_2: usize) {
+        let board = &app.world.resource::<GameState>().board;
+        let region_1 = board.regions[region_1].clone();
+        let region_2 = board.regions[region_2].clone();
+        app.world.send_event(EventPlayerMoveStart {
+            player_1: region_1.owner,
+            region_1,
+            region_2,
+        });
+    }
+
+    fn run_for_seconds(app: &mut App, seconds: usize) {
+        run_frames(app, seconds, Duration::from_secs(1));
+    }
+
+    fn run_frames(app: &mut App, frames: usize, frame_time: Duration) {
+        for _ in 0..frames {
+            let mut time = app.world.resource_mut::<Time>();
+            let now = time.last_update().unwrap_or_else(|| time.startup());
+            time.update_with_instant(now + frame_time);
+            app.update();
+        }
+    }
+
+    fn captures(app: &App) -> usize {
+        app.world
+            .resource::<ObservedGameEvents>()
+            .0
+            .iter()
+            .filter(|e| matches!(e, GameEvent::RegionCaptured { .. }))
+            .count()
+    }
+
+    fn owners(app: &App) -> Vec<usize> {
+        let game_state = app.world.resource::<GameState>();
+        game_state.board.regions.iter().map(|r| r.owner.0).collect()
+    }
+
+    fn num_dice(app: &App) -> Vec<usize> {
+        let game_state = app.world.resource::<GameState>();
+        game_state
+            .board
+            .regions
+            .iter()
+            .map(|r| r.num_dice)
+            .collect()
+    }
+
+    #[test]
+    fn tie_is_rerolled() {
+        let mut app = app_with_clash_pipeline(vec![[3, 3], [6, 1]]);
+        app.insert_resource(Rules {
+            tie_rule: TieRule::Reroll,
+            ..default()
+        });
+
+        play_clash(&mut app, 0, 1);
+        run_for_seconds(&mut app, 6);
+
+        // Only the rerolled clash is logged
+        let game_state = app.world.resource::<GameState>();
+        assert_eq!(game_state.game_log.len(), 1);
+        assert_eq!(game_state.game_log[0].region_1_dice_result, vec![6; 4]);
+        assert_eq!(owners(&app), vec![0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn same_seeds_give_same_clash_outcome() {
+        let mut app_1 = app_with_clash_pipeline(vec![[6, 1]]);
+        let mut app_2 = app_with_clash_pipeline(vec![[6, 1]]);
+
+        play_clash(&mut app_1, 0, 1);
+        play_clash(&mut app_2, 0, 1);
+
+        assert_eq!(owners(&app_1), vec![0, 0, 1, 0]);
+        assert_eq!(num_dice(&app_1), num_dice(&app_2));
+    }
+
+    #[test]
+    fn scripted_game_plays_through_the_clash_pipeline() {
+        let mut app = app_with_clash_pipeline(vec![[6, 1], [1, 6], [6, 1], [6, 1]]);
+        app.init_resource::<ObservedWinners>()
+            .add_system(observe_winners.after(event_player_move_end));
+
+        // Player 1 captures region 1, then loses region 3 attacking region 2
+        play_clash(&mut app, 0, 1);
+        assert_eq!(owners(&app), vec![0, 0, 1, 0]);
+        play_clash(&mut app, 3, 2);
+        assert_eq!(owners(&app), vec![0, 0, 1, 1]);
+
+        assert!(app.world.resource::<ObservedWinners>().0.is_empty());
+
+        // Freshly captured region 1 pushes on until player 2 has nothing left
+        play_clash(&mut app, 1, 2);
+        play_clash(&mut app, 2, 3);
+        assert_eq!(owners(&app), vec![0, 0, 0, 0]);
+
+        let game_state = app.world.resource::<GameState>();
+        let clashes: Vec<&GameLogEntry> = game_state
+            .game_log
+            .iter()
+            .filter(|gl| gl.action == GameAction::Clash)
+            .collect();
+        assert_eq!(clashes.len(), 4);
+        assert!(clashes.iter().all(|gl| gl.region_1.owner == PlayerId(0)));
+
+        let winners = &app.world.resource::<ObservedWinners>().0;
+        assert!(!winners.is_empty());
+        assert!(winners.iter().all(|winner| *winner == PlayerId(0)));
+    }
+
+    #[test]
+    fn clash_is_mirrored_into_game_events() {
+        let mut app = app_with_clash_pipeline(vec![[6, 1], [1, 6], [6, 1], [6, 1]]);
+        app.init_resource::<ObservedGameEvents>()
+            .add_system(observe_game_events.after(event_player_move_end));
+
+        play_clash(&mut app, 0, 1);
+        assert_eq!(
+            app.world.resource::<ObservedGameEvents>().0,
+            vec![
+                GameEvent::MoveStarted {
+                    player: PlayerId(0),
+                    attacker: RegionId(0),
+                    defender: RegionId(1),
+                },
+                GameEvent::DiceRolled {
+                    attacker_sum: 24,
+                    defender_sum: 4,
+                },
+                GameEvent::RegionCaptured {
+                    region: RegionId(1),
+                    from: PlayerId(1),
+                    to: PlayerId(0),
+                },
+            ]
+        );
+
+        play_clash(&mut app, 3, 2);
+        play_clash(&mut app, 1, 2);
+        play_clash(&mut app, 2, 3);
+
+        // The game ends with a single announcement, however long the app keeps running
+        let observed = &app.world.resource::<ObservedGameEvents>().0;
+        assert_eq!(
+            observed.last(),
+            Some(&GameEvent::GameOver {
+                winner: PlayerId(0)
+            })
+        );
+        let game_overs = observed
+            .iter()
+            .filter(|e| matches!(e, GameEvent::GameOver { .. }))
+            .count();
+        assert_eq!(game_overs, 1);
+    }
+
+    #[test]
+    fn attacker_losing_their_last_region_is_eliminated() {
+        let mut app = app_with_clash_pipeline(vec![[1, 6]]);
+        app.init_resource::<ObservedEliminations>()
+            .add_system(observe_eliminations.after(event_player_move_end));
+
+        // A row of four regions: player 1, player 2, player 3, player 3
+        let mut game_state = app.world.resource_mut::<GameState>();
+        for (region, owner) in game_state.board.regions.iter_mut().zip([0, 1, 2, 2]) {
+            region.owner = PlayerId(owner);
+        }
+        game_state.player_regions = PlayerRegions::from_board(&game_state.board);
+        game_state.players = PlayerRoster::new(3);
+        game_state.number_of_players = 3;
+        game_state.turn_of_player = PlayerId(1);
+
+        // Player 2 attacks player 1 from their only region and loses it
+        play_clash(&mut app, 1, 0);
+        assert_eq!(owners(&app), vec![0, 0, 2, 2]);
+        assert_eq!(
+            app.world.resource::<ObservedEliminations>().0,
+            vec![PlayerId(1)]
+        );
+    }
+
+    #[test]
+    fn eliminated_player_is_skipped() {
+        let mut app = app_with_clash_pipeline(vec![[6, 1]]);
+        app.add_event::<EventPlayerPass>()
+            .init_resource::<ObservedEliminations>()
+            .add_system(observe_eliminations.after(event_player_move_end))
+            .add_system(event_player_pass.after(event_player_move_end));
+
+        // A row of four regions: player 1, player 2, player 3, player 3
+        let mut game_state = app.world.resource_mut::<GameState>();
+        for (region, owner) in game_state.board.regions.iter_mut().zip([0, 1, 2, 2]) {
+            region.owner = PlayerId(owner);
+        }
+        game_state.player_regions = PlayerRegions::from_board(&game_state.board);
+        game_state.players = PlayerRoster::new(3);
+        game_state.number_of_players = 3;
+
+        // Player 1 takes the only region of player 2
+        play_clash(&mut app, 0, 1);
+        assert_eq!(owners(&app), vec![0, 0, 2, 2]);
+        assert_eq!(
+            app.world.resource::<ObservedEliminations>().0,
+            vec![PlayerId(1)]
+        );
+
+        app.world.send_event(EventPlayerPass {
+            player: PlayerId(0),
+        });
+        app.update();
+
+        assert_eq!(
+            app.world.resource::<GameState>().turn_of_player,
+            PlayerId(2)
+        );
+    }
+
+    #[test]
+    fn space_skips_the_dice_roll() {
+        let mut app = app_with_clash_pipeline(vec![[6, 1]]);
+        app.init_resource::<ObservedGameEvents>()
+            .add_system(observe_game_events.after(event_player_move_end));
+
+        start_clash(&mut app, 0, 1);
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Space);
+        // Well within the 3 seconds of a roll
+        run_frames(&mut app, 10, Duration::from_millis(100));
+
+        assert_eq!(owners(&app), vec![0, 0, 1, 0]);
+        assert_eq!(captures(&app), 1);
+
+        // Nothing is resolved again once the roll would have been over
+        run_for_seconds(&mut app, 6);
+        assert_eq!(captures(&app), 1);
+        assert_eq!(app.world.resource::<GameState>().game_log.len(), 1);
+    }
+
+    #[test]
+    fn skipping_as_the_roll_ends_resolves_the_clash_once() {
+        let mut app = app_with_clash_pipeline(vec![[6, 1]]);
+        app.insert_resource(DiceRollDuration(Duration::from_millis(500)))
+            .init_resource::<ObservedGameEvents>()
+            .add_system(observe_game_events.after(event_player_move_end));
+
+        start_clash(&mut app, 0, 1);
+        app.world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::Space);
+        run_for_seconds(&mut app, 6);
+
+        assert_eq!(owners(&app), vec![0, 0, 1, 0]);
+        assert_eq!(captures(&app), 1);
+    }
+
+    #[test]
+    fn dice_that_never_settle_are_rolled_again() {
+        let mut app = app_with_clash_pipeline(Vec::new());
+        app.init_resource::<ObservedGameEvents>()
+            .add_system(observe_game_events.after(event_player_move_end));
+
+        play_clash(&mut app, 0, 1);
+        let game_state = app.world.resource::<GameState>();
+        assert!(game_state.game_log[0].region_1_dice_result.is_empty());
+
+        run_for_seconds(&mut app, 6);
+
+        let game_state = app.world.resource::<GameState>();
+        assert_eq!(game_state.game_log[0].region_1_dice_result.len(), 4);
+        assert_eq!(game_state.game_log[0].region_2_dice_result.len(), 4);
+        let observed = &app.world.resource::<ObservedGameEvents>().0;
+        assert!(observed
+            .iter()
+            .any(|e| matches!(e, GameEvent::DiceRolled { .. })));
+        // The clash is resolved, whoever won it
+        assert_eq!(
+            app.world.query::<&DiceRollTimer>().iter(&app.world).count(),
+            0
+        );
     }
 }
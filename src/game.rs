@@ -1,75 +1,1785 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io;
+use std::ops::{Index, IndexMut};
+use std::panic;
+use std::path::Path;
+use std::thread;
 
-use bevy::prelude::{Component, Entity, Resource};
-use rand::{seq::IteratorRandom, Rng};
+use bevy_ecs::prelude::{Component, Entity, Resource};
+use bevy_utils::tracing::warn;
+use clap::ValueEnum;
+use rand::{
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
+};
 use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
 
+use crate::error::GameError;
+use crate::geometry::center;
 use crate::hex::HexCoord;
+use crate::odds::clash_win_probability_with_dice;
+use crate::tiered_prng::{get_randomness, preview_seed};
 
-const BOARD_SIZE: isize = 20;
-const NUMBER_OF_PATCHES: usize = 16;
-const HALF_BOARD_SIZE: isize = BOARD_SIZE / 2 - 1;
+/// Tries at placing a single patch before board generation gives up
+pub const MAX_PATCH_ATTEMPTS: usize = 1000;
+/// Distance between seeds of candidate boards generated at once
+const CANDIDATE_SEED_STEP: u64 = 0x9e37_79b9_7f4a_7c15;
+/// Boards generated at most until every player has an attack
+pub const MAX_BOARD_ATTEMPTS: usize = 8;
 
-#[derive(Default, Clone)]
+/// Maximum number of dice a region can hold
+pub const MAX_DICE: usize = 8;
+
+/// Smallest side of a board, leaving room for lakes in its interior
+const MIN_BOARD_SIZE: isize = 6;
+
+/// Maximum fortify level of a region
+pub const MAX_FORTIFY: u8 = 3;
+
+/// Most sides a [`DiceKind`] can have
+pub const MAX_DICE_SIDES: u32 = 20;
+
+/// Heat a region gains each time it changes hands
+const HEAT_PER_CAPTURE: f32 = 1.0;
+
+/// Share of heat a region keeps from one turn to the next
+const HEAT_DECAY: f32 = 0.8;
+
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct Board {
-    pub hexes: HashMap<(isize, isize), usize>,
+    #[serde(with = "hexes_as_entries")]
+    pub hexes: HashMap<(isize, isize), RegionId>,
     pub regions: Vec<Region>,
+    /// Unoccupied hexes (lakes) regions grow around. Regions on both sides of a lake
+    /// don't border each other.
+    pub holes: HashSet<(isize, isize)>,
+    /// How many dice each region can hold
+    pub dice_cap_rule: DiceCapRule,
+    /// Heat of regions that changed hands, cooling down every turn
+    pub heat: HashMap<RegionId, f32>,
+    /// Ids of regions bordering each region in ascending order, filled in by
+    /// [`Board::compute_adjacency`]
+    #[serde(skip)]
+    adjacency: HashMap<RegionId, Vec<RegionId>>,
+}
+
+impl Board {
+    pub fn total_regions(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Number of hexes occupied by regions
+    pub fn hex_count(&self) -> usize {
+        self.hexes.len()
+    }
+
+    /// Occupied hexes relative to the area of a board generated with `config`.
+    /// Patches may grow past the edges, so it can exceed 1.
+    pub fn coverage(&self, config: &BoardConfig) -> f32 {
+        self.hex_count() as f32 / config.board_area() as f32
+    }
+
+    /// Worth of a region: 1 for quiet ground, more for regions fought over recently
+    pub fn region_value(&self, region_id: RegionId) -> f32 {
+        1.0 + self.heat.get(&region_id).copied().unwrap_or(0.0)
+    }
+
+    /// Heat up a region that just changed hands
+    pub fn heat_up(&mut self, region_id: RegionId) {
+        *self.heat.entry(region_id).or_insert(0.0) += HEAT_PER_CAPTURE;
+    }
+
+    /// Let heat of all regions decay, called once per turn
+    pub fn cool_down(&mut self) {
+        for heat in self.heat.values_mut() {
+            *heat *= HEAT_DECAY;
+        }
+    }
+
+    /// Fraction of regions owned by a player
+    pub fn player_share(&self, player: PlayerId) -> f32 {
+        if self.regions.is_empty() {
+            return 0.0;
+        }
+
+        let owned = self.regions.iter().filter(|r| r.owner == player).count();
+        owned as f32 / self.total_regions() as f32
+    }
+
+    /// Player owning the most regions, `None` for an empty board or a tie
+    pub fn leader(&self) -> Option<PlayerId> {
+        let mut regions_by_player: HashMap<PlayerId, usize> = HashMap::new();
+        for region in self.regions.iter() {
+            *regions_by_player.entry(region.owner).or_insert(0) += 1;
+        }
+
+        let most_regions = *regions_by_player.values().max()?;
+        let mut leaders = regions_by_player
+            .iter()
+            .filter(|(_, count)| **count == most_regions);
+
+        match (leaders.next(), leaders.next()) {
+            (Some((player, _)), None) => Some(*player),
+            _ => None,
+        }
+    }
+
+    /// Ids of regions sharing a border with a given region in ascending order.
+    /// Panics if the adjacency graph wasn't computed, see [`Board::compute_adjacency`].
+    pub fn neighbours(&self, region_id: RegionId) -> &[RegionId] {
+        &self.adjacency[&region_id]
+    }
+
+    /// Work out which regions border each other. Hexes of regions never change during a game,
+    /// so it's done once a board is generated.
+    pub fn compute_adjacency(&mut self) {
+        let mut owners: HashMap<(isize, isize), RegionId> = HashMap::new();
+        for region in self.regions.iter() {
+            for hex in region.hexes.iter() {
+                owners.insert(*hex, region.id);
+            }
+        }
+
+        let mut adjacency: HashMap<RegionId, Vec<RegionId>> = HashMap::new();
+        for region in self.regions.iter() {
+            let mut neighbours: Vec<RegionId> = Vec::new();
+            for hex in region.hexes.iter() {
+                for neighbour in HexCoord::new(hex.0, hex.1).neighbors() {
+                    match owners.get(&(neighbour.q, neighbour.r)) {
+                        Some(id) if *id != region.id => neighbours.push(*id),
+                        _ => {}
+                    }
+                }
+            }
+            neighbours.sort_unstable();
+            neighbours.dedup();
+            adjacency.insert(region.id, neighbours);
+        }
+
+        self.adjacency = adjacency;
+    }
+
+    /// Neighbour of a region lying the most in `direction`, given as `(x, z)` on the plane of
+    /// the rendered board. Neighbours 90° or more off `direction` don't count.
+    pub fn neighbour_in_direction(
+        &self,
+        region_id: RegionId,
+        direction: (f32, f32),
+    ) -> Option<RegionId> {
+        let from = self.regions[region_id].world_center_of_mass();
+        let direction_length = direction.0.hypot(direction.1);
+
+        self.neighbours(region_id)
+            .iter()
+            .copied()
+            .map(|id| {
+                let to = self.regions[id].world_center_of_mass();
+                let (dx, dz) = (to.0 - from.0, to.1 - from.1);
+                let cos = (dx * direction.0 + dz * direction.1) / (dx.hypot(dz) * direction_length);
+                (id, cos)
+            })
+            .filter(|(_, cos)| *cos > 0.0)
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+            .map(|(id, _)| id)
+    }
+
+    /// Whether two regions of different players border each other
+    pub fn is_opponent(&self, region_1_id: RegionId, region_2_id: RegionId) -> bool {
+        self.regions[region_1_id].owner != self.regions[region_2_id].owner
+            && self.neighbours(region_1_id).contains(&region_2_id)
+    }
+
+    /// Whether reflecting the board through its center hex maps every region onto a region
+    /// with as many dice, owned by a consistent counterpart of its owner, and lakes onto lakes
+    pub fn is_point_symmetric(&self) -> bool {
+        let mut counterparts: HashMap<PlayerId, PlayerId> = HashMap::new();
+        let regions_match = self.regions.iter().all(|region| {
+            let mut mirrored: Vec<(isize, isize)> =
+                region.hexes.iter().map(|(q, r)| (-q, -r)).collect();
+            let other = match mirrored.first().and_then(|hex| self.hexes.get(hex)) {
+                Some(id) => &self.regions[*id],
+                None => return false,
+            };
+
+            let mut other_hexes = other.hexes.clone();
+            mirrored.sort_unstable();
+            other_hexes.sort_unstable();
+
+            mirrored == other_hexes
+                && other.num_dice == region.num_dice
+                && *counterparts.entry(region.owner).or_insert(other.owner) == other.owner
+        });
+
+        regions_match
+            && self
+                .holes
+                .iter()
+                .all(|(q, r)| self.holes.contains(&(-q, -r)))
+    }
+
+    /// Owner of the region with the most hexes, `None` if several regions are the largest
+    pub fn largest_region_owner(&self) -> Option<PlayerId> {
+        let largest = self.regions.iter().map(|r| r.hexes.len()).max()?;
+        let mut largest_regions = self.regions.iter().filter(|r| r.hexes.len() == largest);
+
+        match (largest_regions.next(), largest_regions.next()) {
+            (Some(region), None) => Some(region.owner),
+            _ => None,
+        }
+    }
+
+    /// Region that changed hands most often, the lowest id among equally contested ones.
+    /// `None` until some region gets captured.
+    pub fn most_contested_region(&self) -> Option<RegionId> {
+        self.regions
+            .iter()
+            .filter(|r| r.capture_count > 0)
+            .min_by_key(|r| (std::cmp::Reverse(r.capture_count), r.id))
+            .map(|r| r.id)
+    }
+
+    /// Number of opponent regions bordering a given region
+    pub fn enemy_neighbour_count(&self, region_id: RegionId) -> usize {
+        self.neighbours(region_id)
+            .iter()
+            .copied()
+            .filter(|id| self.is_opponent(region_id, *id))
+            .count()
+    }
+
+    /// Total dice of opponent regions bordering a given region
+    pub fn adjacent_enemy_dice(&self, region_id: RegionId) -> usize {
+        self.neighbours(region_id)
+            .iter()
+            .copied()
+            .filter(|id| self.is_opponent(region_id, *id))
+            .map(|id| self.regions[id].num_dice)
+            .sum()
+    }
+
+    /// Ids of regions to keep lit during a clash: both participants and their neighbours
+    pub fn clash_spotlight(
+        &self,
+        region_1_id: RegionId,
+        region_2_id: RegionId,
+    ) -> HashSet<RegionId> {
+        let mut spotlight = HashSet::from([region_1_id, region_2_id]);
+        spotlight.extend(self.neighbours(region_1_id));
+        spotlight.extend(self.neighbours(region_2_id));
+        spotlight
+    }
+
+    /// Ids of regions reachable from a given region through regions of the same owner
+    pub fn connected_regions(&self, region_id: RegionId) -> HashSet<RegionId> {
+        let owner = self.regions[region_id].owner;
+
+        let mut connected = HashSet::from([region_id]);
+        let mut frontier = vec![region_id];
+        while let Some(current) = frontier.pop() {
+            for &neighbour in self.neighbours(current) {
+                if self.regions[neighbour].owner == owner && connected.insert(neighbour) {
+                    frontier.push(neighbour);
+                }
+            }
+        }
+
+        connected
+    }
+
+    /// Groups of bordering regions owned by a player, each in ascending order of region ids.
+    /// Groups are ordered by their smallest region id.
+    pub fn connected_components(&self, player: PlayerId) -> Vec<Vec<RegionId>> {
+        let mut seen: HashSet<RegionId> = HashSet::new();
+        let mut components = Vec::new();
+        for region in self.regions.iter().filter(|r| r.owner == player) {
+            if seen.contains(&region.id) {
+                continue;
+            }
+
+            let mut component: Vec<RegionId> =
+                self.connected_regions(region.id).into_iter().collect();
+            component.sort_unstable();
+            seen.extend(component.iter().copied());
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Whether all regions of a player border each other through regions of the same player.
+    /// A player without regions counts as connected.
+    pub fn is_connected(&self, player: PlayerId) -> bool {
+        self.connected_components(player).len() <= 1
+    }
+
+    /// Shortest chain of bordering regions from one region to another, both included.
+    /// `None` if either id is invalid or there is no path.
+    pub fn region_path(&self, from_id: RegionId, to_id: RegionId) -> Option<Vec<RegionId>> {
+        self.region_path_through(from_id, to_id, |_| true)
+    }
+
+    /// Like [`Board::region_path`], but only steps onto regions for which `passable` holds.
+    /// The start region is always allowed.
+    pub fn region_path_through(
+        &self,
+        from_id: RegionId,
+        to_id: RegionId,
+        passable: impl Fn(&Region) -> bool,
+    ) -> Option<Vec<RegionId>> {
+        if from_id.0 >= self.regions.len() || to_id.0 >= self.regions.len() {
+            return None;
+        }
+
+        let mut previous: HashMap<RegionId, RegionId> = HashMap::from([(from_id, from_id)]);
+        let mut queue = VecDeque::from([from_id]);
+        while let Some(current) = queue.pop_front() {
+            if current == to_id {
+                let mut path = vec![to_id];
+                let mut step = to_id;
+                while step != from_id {
+                    step = previous[&step];
+                    path.push(step);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for &neighbour in self.neighbours(current) {
+                if !previous.contains_key(&neighbour) && passable(&self.regions[neighbour]) {
+                    previous.insert(neighbour, current);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Check invariants of a board: region ids match their indices, each region is a
+    /// contiguous set of hexes not shared with other regions, owners and dice are in range.
+    pub fn validate(&self, number_of_players: usize) -> Result<(), GameError> {
+        let mut seen_hexes: HashSet<(isize, isize)> = HashSet::new();
+
+        for (index, region) in self.regions.iter().enumerate() {
+            let invalid = |reason| GameError::InvalidBoard {
+                region: index,
+                reason,
+            };
+
+            if region.id != RegionId(index) {
+                return Err(invalid("region id doesn't match its index"));
+            }
+            if region.hexes.is_empty() {
+                return Err(invalid("region has no hexes"));
+            }
+            if region.owner.0 >= number_of_players {
+                return Err(invalid("region owner is out of range"));
+            }
+            if region.num_dice == 0 || region.num_dice > self.dice_cap(region) {
+                return Err(invalid("region dice count is out of range"));
+            }
+
+            for hex in region.hexes.iter() {
+                if !seen_hexes.insert(*hex) {
+                    return Err(invalid("hex belongs to more than one region"));
+                }
+                if !self.hexes.contains_key(hex) {
+                    return Err(invalid("hex is missing from the board"));
+                }
+            }
+
+            if !region.is_contiguous() {
+                return Err(invalid("region hexes aren't contiguous"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether every region id matches its index in `regions`, which code looking regions
+    /// up by id relies on
+    pub fn ids_match_indices(&self) -> bool {
+        self.regions
+            .iter()
+            .enumerate()
+            .all(|(index, region)| region.id == RegionId(index))
+    }
+
+    /// Most dice a region can hold under the dice cap rule of this board
+    pub fn dice_cap(&self, region: &Region) -> usize {
+        region.dice_cap(self.dice_cap_rule)
+    }
+
+    /// Add up to `n` dice to a region without going over its dice cap.
+    /// Returns the number of dice that didn't fit.
+    pub fn add_dice(&mut self, region_id: RegionId, n: usize) -> usize {
+        let cap = self.dice_cap(&self.regions[region_id]);
+        self.regions[region_id].add_dice_up_to(n, cap)
+    }
+
+    /// Region with a given id, or an error if it doesn't exist
+    pub fn region(&self, region_id: RegionId) -> Result<&Region, GameError> {
+        self.regions
+            .get(region_id.0)
+            .ok_or(GameError::InvalidRegion(region_id.0))
+    }
+
+    /// Board made up of given regions, whose ids have to match their indices
+    pub fn from_regions(regions: Vec<Region>) -> Board {
+        let mut board = Board::default();
+        for region in regions.iter() {
+            for hex in region.hexes.iter() {
+                board.hexes.insert(*hex, region.id);
+            }
+        }
+        board.regions = regions;
+        board.compute_adjacency();
+        board
+    }
+
+    /// Parse a board drawn with a character per hex: a digit with the owner of a hex, or `.`
+    /// for an empty one. Rows are laid out on an offset grid with odd rows shifted half a hex
+    /// to the right, whitespace is ignored. Bordering hexes of the same owner make up
+    /// a region, every region starts with a single dice.
+    pub fn from_ascii(s: &str) -> Result<Board, GameError> {
+        let mut owners: HashMap<(isize, isize), PlayerId> = HashMap::new();
+        let mut reading_order: Vec<(isize, isize)> = Vec::new();
+        for (row, line) in s.lines().enumerate() {
+            let r = row as isize;
+            for (column, c) in line.chars().filter(|c| !c.is_whitespace()).enumerate() {
+                if c == '.' {
+                    continue;
+                }
+                let owner = c
+                    .to_digit(10)
+                    .ok_or(GameError::InvalidMap { row, column })?;
+
+                let coord = (column as isize - r.div_euclid(2), r);
+                owners.insert(coord, PlayerId(owner as usize));
+                reading_order.push(coord);
+            }
+        }
+
+        let mut regions: Vec<Region> = Vec::new();
+        let mut taken: HashSet<(isize, isize)> = HashSet::new();
+        for start in reading_order {
+            if !taken.insert(start) {
+                continue;
+            }
+
+            let owner = owners[&start];
+            let mut hexes = Vec::new();
+            let mut frontier = vec![start];
+            while let Some(hex) = frontier.pop() {
+                hexes.push(hex);
+                for neighbour in HexCoord::new(hex.0, hex.1).neighbors() {
+                    let coord = (neighbour.q, neighbour.r);
+                    if owners.get(&coord) == Some(&owner) && taken.insert(coord) {
+                        frontier.push(coord);
+                    }
+                }
+            }
+            hexes.sort_unstable_by_key(|(q, r)| (*r, *q));
+
+            regions.push(Region::new(RegionId(regions.len()), owner, 1, hexes));
+        }
+
+        Ok(Board::from_regions(regions))
+    }
+
+    /// Draw owners of hexes in the format read by [`Board::from_ascii`], with cells separated
+    /// by spaces. Borders between bordering regions of the same owner aren't kept.
+    pub fn to_ascii(&self) -> String {
+        let mut owners: HashMap<(isize, isize), PlayerId> = HashMap::new();
+        for region in self.regions.iter() {
+            for hex in region.hexes.iter() {
+                // Offset coordinates of a hex
+                owners.insert((hex.0 + hex.1.div_euclid(2), hex.1), region.owner);
+            }
+        }
+
+        if owners.is_empty() {
+            return String::new();
+        }
+        let min_column = owners.keys().map(|(column, _)| *column).min().unwrap();
+        let max_column = owners.keys().map(|(column, _)| *column).max().unwrap();
+        let min_row = owners.keys().map(|(_, row)| *row).min().unwrap();
+        let max_row = owners.keys().map(|(_, row)| *row).max().unwrap();
+
+        // Start on an even row, so odd rows stay shifted to the right
+        let mut ascii = String::new();
+        for row in min_row - min_row.rem_euclid(2)..=max_row {
+            let cells: Vec<String> = (min_column..=max_column)
+                .map(|column| match owners.get(&(column, row)) {
+                    Some(owner) => owner.0.to_string(),
+                    None => ".".to_string(),
+                })
+                .collect();
+
+            if row.rem_euclid(2) == 1 {
+                ascii.push(' ');
+            }
+            ascii.push_str(&cells.join(" "));
+            ascii.push('\n');
+        }
+
+        ascii
+    }
+
+    /// Move dice between two connected regions of the same owner.
+    /// At least one dice stays behind and the target region can't exceed its dice cap.
+    pub fn move_dice(
+        &mut self,
+        from_id: RegionId,
+        to_id: RegionId,
+        count: usize,
+    ) -> Result<(), GameError> {
+        let from_dice = self.region(from_id)?.num_dice;
+        let to_dice = self.region(to_id)?.num_dice;
+        let to_cap = self.dice_cap(self.region(to_id)?);
+
+        if from_id == to_id || !self.connected_regions(from_id).contains(&to_id) {
+            return Err(GameError::NotConnected {
+                from: from_id.0,
+                to: to_id.0,
+            });
+        }
+
+        if count == 0 || from_dice <= count {
+            return Err(GameError::NotEnoughDice {
+                region: from_id.0,
+                num_dice: count,
+            });
+        }
+
+        if to_dice + count > to_cap {
+            return Err(GameError::DiceCapExceeded {
+                region: to_id.0,
+                num_dice: to_dice + count,
+            });
+        }
+
+        self.regions[from_id].num_dice -= count;
+        self.add_dice(to_id, count);
+        Ok(())
+    }
+}
+
+/// JSON only has string keys, so hexes are stored as a list of `(coordinates, region id)` pairs
+mod hexes_as_entries {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::RegionId;
+
+    pub fn serialize<S: Serializer>(
+        hexes: &HashMap<(isize, isize), RegionId>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(hexes.iter())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<(isize, isize), RegionId>, D::Error> {
+        let entries: Vec<((isize, isize), RegionId)> = Vec::deserialize(deserializer)?;
+        Ok(entries.into_iter().collect())
+    }
+}
+
+#[derive(Clone, Resource, Serialize, Deserialize)]
+pub struct GameState {
+    pub board: Board,
+    pub turn_of_player: PlayerId,
+    /// Player who took the first turn of the match
+    #[serde(default)]
+    pub first_player: PlayerId,
+    pub turn_counter: usize,
+    pub number_of_players: usize,
+    pub game_log: Vec<GameLogEntry>,
+    /// Region ids owned by each player, kept in sync with the board on captures
+    pub player_regions: PlayerRegions,
+    /// Stable ids of players, used wherever a player has to be recognized across the match
+    pub players: PlayerRoster,
+    /// Dice both sides of a clash roll
+    #[serde(default)]
+    pub dice_kind: DiceKind,
+}
+
+/// Id of a region, which is also its index in [`Board::regions`]
+#[derive(
+    Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize,
+)]
+pub struct RegionId(pub usize);
+
+impl From<RegionId> for usize {
+    fn from(region_id: RegionId) -> Self {
+        region_id.0
+    }
+}
+
+impl Index<RegionId> for Vec<Region> {
+    type Output = Region;
+
+    fn index(&self, region_id: RegionId) -> &Region {
+        &self[region_id.0]
+    }
+}
+
+impl IndexMut<RegionId> for Vec<Region> {
+    fn index_mut(&mut self, region_id: RegionId) -> &mut Region {
+        &mut self[region_id.0]
+    }
+}
+
+/// Player owning regions and taking turns, numbered from 0. The [`PlayerRoster`] keeps
+/// the identity of a player for the whole match.
+#[derive(
+    Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize,
+)]
+pub struct PlayerId(pub usize);
+
+/// Maps owner indices used on the board to stable player ids
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PlayerRoster {
+    ids: Vec<PlayerId>,
+}
+
+impl PlayerRoster {
+    pub fn new(number_of_players: usize) -> Self {
+        PlayerRoster {
+            ids: (0..number_of_players).map(PlayerId).collect(),
+        }
+    }
+
+    /// Id of the player currently at the given owner index
+    pub fn id_of(&self, owner: PlayerId) -> PlayerId {
+        self.ids.get(owner.0).copied().unwrap_or(owner)
+    }
+
+    /// Remove a player from the roster. Players after it move down by one owner index
+    /// and keep their ids.
+    pub fn eliminate(&mut self, owner: PlayerId) {
+        if owner.0 < self.ids.len() {
+            self.ids.remove(owner.0);
+        }
+    }
+}
+
+/// Cache of region ids owned by each player, so looking up regions of a player
+/// doesn't need a scan of the whole board
+#[derive(Clone, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct PlayerRegions {
+    by_player: HashMap<PlayerId, Vec<RegionId>>,
+}
+
+impl PlayerRegions {
+    pub fn from_board(board: &Board) -> Self {
+        let mut by_player: HashMap<PlayerId, Vec<RegionId>> = HashMap::new();
+        for region in board.regions.iter() {
+            by_player.entry(region.owner).or_default().push(region.id);
+        }
+
+        PlayerRegions { by_player }
+    }
+
+    /// Ids of regions owned by a player in ascending order
+    pub fn regions_of(&self, player: PlayerId) -> &[RegionId] {
+        self.by_player
+            .get(&player)
+            .map_or(&[], |ids| ids.as_slice())
+    }
+
+    pub(crate) fn transfer(
+        &mut self,
+        region_id: RegionId,
+        from_player: PlayerId,
+        to_player: PlayerId,
+    ) {
+        if let Some(ids) = self.by_player.get_mut(&from_player) {
+            ids.retain(|id| *id != region_id);
+        }
+
+        let ids = self.by_player.entry(to_player).or_default();
+        if let Err(index) = ids.binary_search(&region_id) {
+            ids.insert(index, region_id);
+        }
+    }
+}
+
+impl GameState {
+    /// Game on `board` between `number_of_players` players, at the first turn of player 0
+    pub fn from_board(board: Board, number_of_players: usize) -> Self {
+        GameState {
+            player_regions: PlayerRegions::from_board(&board),
+            players: PlayerRoster::new(number_of_players),
+            dice_kind: DiceKind::default(),
+            board,
+            number_of_players,
+            turn_of_player: PlayerId(0),
+            first_player: PlayerId(0),
+            turn_counter: 0,
+            game_log: Vec::new(),
+        }
+    }
+
+    /// Save the game as JSON, to be picked up later with [`GameState::load_from_path`]. The
+    /// rules and the reinforcement reserve aren't kept on the game state, so they are passed
+    /// in by the caller.
+    pub fn save_to_path(
+        &self,
+        rules: &Rules,
+        reinforcement_reserve: &ReinforcementReserve,
+        path: &Path,
+    ) -> io::Result<()> {
+        let saved_game = SavedGame {
+            game_state: self.clone(),
+            rules: *rules,
+            reinforcement_reserve: reinforcement_reserve.clone(),
+        };
+        fs::write(path, serde_json::to_string(&saved_game)?)
+    }
+
+    /// Read a game saved with [`GameState::save_to_path`]
+    pub fn load_from_path(path: &Path) -> io::Result<SavedGame> {
+        let mut saved_game: SavedGame = serde_json::from_str(&fs::read_to_string(path)?)?;
+        // Saves don't carry the adjacency graph, it follows from the hexes
+        saved_game.game_state.board.compute_adjacency();
+        Ok(saved_game)
+    }
+
+    /// Write everything the game was set up with and its log into a `.srd` file that can be
+    /// shared and replayed, read back with [`load_replay`]. Seeds, rules and the board config
+    /// aren't kept on the game state, so they are passed in by the caller.
+    pub fn export_replay(
+        &self,
+        world_seed: u64,
+        env_seed: u64,
+        rules: &Rules,
+        board_config: &BoardConfig,
+        path: &Path,
+    ) -> io::Result<()> {
+        let replay_data = ReplayData {
+            world_seed,
+            env_seed,
+            number_of_players: self.number_of_players,
+            first_player: self.first_player,
+            dice_kind: self.dice_kind,
+            rules: *rules,
+            board_config: board_config.clone(),
+            game_log: self.game_log.clone(),
+        };
+        fs::write(path, serde_json::to_string(&replay_data)?)
+    }
+
+    // Enumerates a list of possible moves for a player
+    pub fn possible_moves(self) -> Vec<(Region, Region)> {
+        self.possible_moves_with_min_dice(MinAttackDice::default())
+    }
+
+    /// Same as [`GameState::possible_moves`], leaving out attackers with fewer than
+    /// `min_attack_dice` dice
+    pub fn possible_moves_with_min_dice(
+        self,
+        min_attack_dice: MinAttackDice,
+    ) -> Vec<(Region, Region)> {
+        let regions_owned_by_player: Vec<Region> = self
+            .player_regions
+            .regions_of(self.turn_of_player)
+            .iter()
+            .map(|id| self.board.regions[*id].clone())
+            .filter(|region| region.num_dice >= min_attack_dice.0)
+            .collect();
+
+        let mut possible_moves: Vec<(Region, Region)> = Vec::new();
+
+        for region1 in regions_owned_by_player.iter() {
+            for &id in self.board.neighbours(region1.id) {
+                if self.board.is_opponent(region1.id, id) {
+                    possible_moves.push((region1.clone(), self.board.regions[id].clone()));
+                }
+            }
+        }
+
+        possible_moves
+    }
+
+    /// Ids of regions a player can see with fog of war: its own regions and their neighbours,
+    /// in ascending order
+    pub fn visible_regions(&self, player: PlayerId) -> Vec<RegionId> {
+        let mut visible: HashSet<RegionId> = HashSet::new();
+        for id in self.player_regions.regions_of(player).iter() {
+            visible.insert(*id);
+            visible.extend(self.board.neighbours(*id));
+        }
+
+        let mut visible: Vec<RegionId> = visible.into_iter().collect();
+        visible.sort_unstable();
+        visible
+    }
+
+    /// Whether a region has already attacked or retreated this turn
+    pub fn has_acted(&self, region_id: RegionId) -> bool {
+        self.game_log
+            .iter()
+            .any(|gl| self.is_action_of_turn(gl, region_id))
+    }
+
+    /// Whether a region can still attack this turn. Each region acts once per turn,
+    /// unless `capture_bonus` grants it another attack right after a capture.
+    pub fn can_attack(&self, region_id: RegionId, capture_bonus: CaptureBonus) -> bool {
+        let last_action = self
+            .game_log
+            .iter()
+            .rev()
+            .find(|gl| self.is_action_of_turn(gl, region_id));
+
+        match (last_action, capture_bonus) {
+            (None, _) => true,
+            (Some(gl), CaptureBonus::ExtraAction) => gl.attacker_won,
+            (Some(_), CaptureBonus::Disabled) => false,
+        }
+    }
+
+    /// Whether a logged entry is an action a region took this turn, which reinforcements aren't
+    fn is_action_of_turn(&self, gl: &GameLogEntry, region_id: RegionId) -> bool {
+        gl.turn_counter == self.turn_counter
+            && gl.region_1.id == region_id
+            && gl.action != GameAction::Reinforce
+    }
+
+    /// Whether the current player may attack `defender` from `attacker` under default rules
+    pub fn is_legal_move(&self, attacker: &Region, defender: &Region) -> bool {
+        self.is_legal_move_with_rules(attacker, defender, &Rules::default())
+    }
+
+    /// Same as [`GameState::is_legal_move`], with the attacker needing at least
+    /// [`Rules::min_attack_dice`] and [`Rules::capture_bonus`] deciding whether it may attack
+    /// again. Regions are looked up on the board, so stale copies of them are checked as they
+    /// are now.
+    pub fn is_legal_move_with_rules(
+        &self,
+        attacker: &Region,
+        defender: &Region,
+        rules: &Rules,
+    ) -> bool {
+        let region_1 = match self.board.regions.get(attacker.id.0) {
+            Some(region) => region,
+            None => return false,
+        };
+        if self.board.regions.get(defender.id.0).is_none() {
+            return false;
+        }
+
+        region_1.owner == self.turn_of_player
+            && self.board.is_opponent(attacker.id, defender.id)
+            && region_1.num_dice >= rules.min_attack_dice.0
+            && self.can_attack(attacker.id, rules.capture_bonus)
+    }
+
+    /// Number of regions in the biggest group of bordering regions owned by a player
+    pub fn largest_connected_group(&self, player: PlayerId) -> usize {
+        let mut seen: HashSet<RegionId> = HashSet::new();
+        let mut largest = 0;
+        for id in self.player_regions.regions_of(player) {
+            if seen.contains(id) {
+                continue;
+            }
+
+            let group = self.board.connected_regions(*id);
+            largest = largest.max(group.len());
+            seen.extend(group);
+        }
+
+        largest
+    }
+
+    /// Players owning a region that borders an opponent, the only ones who can ever attack
+    pub fn players_with_moves(&self) -> Vec<PlayerId> {
+        (0..self.number_of_players)
+            .map(PlayerId)
+            .filter(|player| {
+                self.player_regions
+                    .regions_of(*player)
+                    .iter()
+                    .any(|id| self.board.enemy_neighbour_count(*id) > 0)
+            })
+            .collect()
+    }
+
+    /// Winner of the game once a single player owns every region, `None` while it's contested
+    pub fn is_game_over(&self) -> Option<PlayerId> {
+        let owner = self.board.regions.first()?.owner;
+        self.board
+            .regions
+            .iter()
+            .all(|region| region.owner == owner)
+            .then_some(owner)
+    }
+
+    /// Standing of every player, including ones without regions left
+    pub fn player_stats(&self) -> Vec<PlayerStats> {
+        (0..self.number_of_players)
+            .map(PlayerId)
+            .map(|player| {
+                let (region_count, total_dice) = self
+                    .board
+                    .regions
+                    .iter()
+                    .filter(|region| region.owner == player)
+                    .fold((0, 0), |(count, dice), region| {
+                        (count + 1, dice + region.num_dice)
+                    });
+
+                PlayerStats {
+                    player,
+                    region_count,
+                    total_dice,
+                    largest_group: self.largest_connected_group(player),
+                }
+            })
+            .collect()
+    }
+
+    /// Give a player one dice per region of their largest connected group plus `reserve`
+    /// dice left from earlier turns. Each dice goes to a random region of the player with
+    /// room for it. Returns dice which didn't fit anywhere, to be kept in reserve.
+    pub fn reinforce(&mut self, player: PlayerId, reserve: usize, rng: &mut impl Rng) -> usize {
+        self.place_dice(player, self.largest_connected_group(player) + reserve, rng)
+    }
+
+    /// Hand out `dice` one at a time to random regions of a player with room for them, logging
+    /// every region that got some. Returns dice which didn't fit anywhere.
+    fn place_dice(&mut self, player: PlayerId, mut dice: usize, rng: &mut impl Rng) -> usize {
+        let regions = self.player_regions.regions_of(player).to_vec();
+        let before: Vec<Region> = regions
+            .iter()
+            .map(|id| self.board.regions[*id].clone())
+            .collect();
+
+        while dice > 0 {
+            let board = &self.board;
+            let open: Vec<RegionId> = regions
+                .iter()
+                .copied()
+                .filter(|id| board.regions[*id].num_dice < board.dice_cap(&board.regions[*id]))
+                .collect();
+
+            match open.choose(rng) {
+                Some(id) => {
+                    self.board.add_dice(*id, 1);
+                }
+                None => break,
+            }
+            dice -= 1;
+        }
+
+        for region in before {
+            let dice_after = self.board.regions[region.id].num_dice;
+            if dice_after == region.num_dice {
+                continue;
+            }
+            self.game_log.push(GameLogEntry {
+                region_1_dice_after: dice_after,
+                region_2_dice_after: dice_after,
+                ..GameLogEntry::new(
+                    GameAction::Reinforce,
+                    self.turn_counter,
+                    player,
+                    region.clone(),
+                    region,
+                )
+            });
+        }
+
+        dice
+    }
+
+    /// Dice a player receives at the end of their turn: one per region of their largest
+    /// connected group, whatever they hold in `reserve`, and one more if `largest_army_bonus`
+    /// is on and they own the single largest region
+    pub fn pending_reinforcements(
+        &self,
+        player: PlayerId,
+        reserve: &ReinforcementReserve,
+        largest_army_bonus: LargestArmyBonus,
+    ) -> usize {
+        let bonus = largest_army_bonus.0 && self.board.largest_region_owner() == Some(player);
+        self.largest_connected_group(player)
+            + reserve.by_player.get(&player).copied().unwrap_or(0)
+            + bonus as usize
+    }
+
+    /// End the turn of the current player: they are reinforced with their
+    /// [`GameState::pending_reinforcements`], dice that don't fit are kept in `reserve`, and
+    /// the turn passes to the next player with regions left. Returns the player whose turn
+    /// ended.
+    pub fn end_turn(
+        &mut self,
+        rules: &Rules,
+        reserve: &mut ReinforcementReserve,
+        rng: &mut impl Rng,
+    ) -> PlayerId {
+        let player = self.turn_of_player;
+        let dice = self.pending_reinforcements(player, reserve, rules.largest_army_bonus);
+        reserve.by_player.remove(&player);
+        match self.place_dice(player, dice, rng) {
+            0 => {}
+            left => {
+                reserve.by_player.insert(player, left);
+            }
+        }
+
+        self.turn_of_player = self.next_player();
+        self.turn_counter += 1;
+        self.board.cool_down();
+
+        player
+    }
+
+    /// Player after the current one in turn order, skipping players without regions left
+    pub fn next_player(&self) -> PlayerId {
+        let after = |offset| PlayerId((self.turn_of_player.0 + offset) % self.number_of_players);
+        (1..=self.number_of_players)
+            .map(after)
+            .find(|player| !self.player_regions.regions_of(*player).is_empty())
+            .unwrap_or_else(|| after(1))
+    }
+
+    /// Pass the turn to a given player
+    pub fn set_turn(&mut self, player: PlayerId) -> Result<(), GameError> {
+        if player.0 >= self.number_of_players {
+            return Err(GameError::InvalidPlayer(player.0));
+        }
+
+        self.turn_of_player = player;
+        Ok(())
+    }
+
+    /// Pull dice back from a region to another connected region of the current player.
+    /// Costs the source region its action for this turn.
+    pub fn retreat(
+        &mut self,
+        from_id: RegionId,
+        to_id: RegionId,
+        count: usize,
+    ) -> Result<(), GameError> {
+        if self.board.region(from_id)?.owner != self.turn_of_player {
+            return Err(GameError::NotYourRegion(from_id.0));
+        }
+
+        if self.has_acted(from_id) {
+            return Err(GameError::RegionAlreadyActed(from_id.0));
+        }
+
+        // Log entries hold region states from before the action, same as clashes
+        let region_1 = self.board.region(from_id)?.clone();
+        let region_2 = self.board.region(to_id)?.clone();
+
+        self.board.move_dice(from_id, to_id, count)?;
+
+        self.game_log.push(GameLogEntry::new(
+            GameAction::Retreat,
+            self.turn_counter,
+            self.turn_of_player,
+            region_1,
+            region_2,
+        ));
+        self.log_dice_after();
+
+        Ok(())
+    }
+
+    /// Raise fortify level of a region of the current player.
+    /// Costs the region its action for this turn.
+    pub fn fortify(&mut self, region_id: RegionId) -> Result<(), GameError> {
+        let region = self.board.region(region_id)?;
+        if region.owner != self.turn_of_player {
+            return Err(GameError::NotYourRegion(region_id.0));
+        }
+
+        if self.has_acted(region_id) {
+            return Err(GameError::RegionAlreadyActed(region_id.0));
+        }
+
+        if region.fortify >= MAX_FORTIFY {
+            return Err(GameError::FortifyCapReached(region_id.0));
+        }
+
+        let region = region.clone();
+        self.board.regions[region_id].fortify += 1;
+
+        self.game_log.push(GameLogEntry {
+            region_1_dice_after: region.num_dice,
+            region_2_dice_after: region.num_dice,
+            ..GameLogEntry::new(
+                GameAction::Fortify,
+                self.turn_counter,
+                self.turn_of_player,
+                region.clone(),
+                region,
+            )
+        });
+
+        Ok(())
+    }
+
+    /// Take back the last move: regions it touched, including the shelter of retreating dice,
+    /// get their owner, dice and fortify level from before the move. If the move ended its
+    /// turn, the turn goes back to its player first: dice they were reinforced with are taken
+    /// off the board and `reserve` gets back what it held under `rules`. A turn ended without
+    /// any move is given back on its own. Nothing is taken back in a won game, or past a turn
+    /// which ended without leaving anything in the log.
+    pub fn undo_last_move(
+        &mut self,
+        rules: &Rules,
+        reserve: &mut ReinforcementReserve,
+    ) -> Option<GameLogEntry> {
+        let last = self.game_log.last()?;
+        let turn_counter = last.turn_counter;
+        if self.is_game_over().is_some()
+            || (turn_counter != self.turn_counter && turn_counter + 1 != self.turn_counter)
+        {
+            return None;
+        }
+
+        if turn_counter + 1 == self.turn_counter {
+            let reinforcement = self.undo_end_turn(turn_counter, rules, reserve);
+            let has_move = self
+                .game_log
+                .last()
+                .is_some_and(|gl| gl.turn_counter == turn_counter);
+            if !has_move {
+                return reinforcement;
+            }
+        }
+
+        let entry = self.game_log.pop()?;
+
+        if entry.action == GameAction::Clash {
+            let loser_id = match entry.attacker_won {
+                true => entry.region_2.id,
+                false => entry.region_1.id,
+            };
+            if let Some(heat) = self.board.heat.get_mut(&loser_id) {
+                *heat = (*heat - HEAT_PER_CAPTURE).max(0.0);
+            }
+        }
+
+        for region in [&entry.region_1, &entry.region_2]
+            .into_iter()
+            .chain(entry.shelter.as_ref())
+        {
+            let owner = self.board.regions[region.id].owner;
+            if owner != region.owner {
+                self.player_regions.transfer(region.id, owner, region.owner);
+            }
+            self.board.regions[region.id] = region.clone();
+        }
+
+        Some(entry)
+    }
+
+    /// Give turn `turn_counter` back to its player, undoing [`GameState::end_turn`]. Returns
+    /// the last reinforcement taken back, if the player got any dice.
+    fn undo_end_turn(
+        &mut self,
+        turn_counter: usize,
+        rules: &Rules,
+        reserve: &mut ReinforcementReserve,
+    ) -> Option<GameLogEntry> {
+        let player = self.game_log.last()?.turn_of_player;
+
+        let mut placed = 0;
+        let mut reinforcement = None;
+        while let Some(gl) = self.game_log.last() {
+            if gl.action != GameAction::Reinforce || gl.turn_counter != turn_counter {
+                break;
+            }
+            let gl = self.game_log.pop()?;
+            placed += gl.region_1_dice_after - gl.region_1.num_dice;
+            self.board.regions[gl.region_1.id] = gl.region_1.clone();
+            reinforcement = Some(gl);
+        }
+
+        // Dice that didn't fit were kept in reserve, on top of what the player had earned
+        let earned = self.pending_reinforcements(
+            player,
+            &ReinforcementReserve::default(),
+            rules.largest_army_bonus,
+        );
+        let left = reserve.by_player.remove(&player).unwrap_or(0);
+        match (left + placed).saturating_sub(earned) {
+            0 => {}
+            kept => {
+                reserve.by_player.insert(player, kept);
+            }
+        }
+
+        self.turn_of_player = player;
+        self.turn_counter = turn_counter;
+        for heat in self.board.heat.values_mut() {
+            *heat /= HEAT_DECAY;
+        }
+
+        reinforcement
+    }
+
+    /// Record dice counts of both regions of the last logged action, and of its shelter if
+    /// any, as they are on the board now. Called once a clash is applied, so a log can be
+    /// replayed without the final board.
+    pub fn log_dice_after(&mut self) {
+        let regions = &self.board.regions;
+        if let Some(gl) = self.game_log.last_mut() {
+            gl.region_1_dice_after = regions[gl.region_1.id].num_dice;
+            gl.region_2_dice_after = regions[gl.region_2.id].num_dice;
+            if let Some(shelter) = &gl.shelter {
+                gl.shelter_dice_after = regions[shelter.id].num_dice;
+            }
+        }
+    }
+
+    /// Record the outcome of a clash resolved by [`GameState::resolve_clash`] on its log entry
+    pub fn log_capture(&mut self, capture_result: &CaptureResult) {
+        if let Some(gl) = self.game_log.last_mut() {
+            gl.shelter = capture_result.shelter.clone();
+        }
+        self.log_dice_after();
+    }
+
+    /// Apply a resolved clash to the board. The losing region is taken over by the winner,
+    /// which moves some of its dice onto it.
+    pub fn apply_clash(
+        &mut self,
+        region_1_id: RegionId,
+        region_2_id: RegionId,
+        attacker_won: bool,
+        rng: &mut impl Rng,
+    ) -> Result<Option<DiceTransfer>, GameError> {
+        self.apply_clash_with_retreat_rule(
+            region_1_id,
+            region_2_id,
+            attacker_won,
+            RetreatRule::Disabled,
+            rng,
+        )
+    }
+
+    /// Fight a clash without dice on the table: roll both sides from `dice_rng`, decide it
+    /// by `rules`, log the clash and apply it to the board. Ties to be rerolled are rolled
+    /// again until they are decided, only the deciding roll is logged. Nothing is logged if
+    /// the regions can't clash.
+    pub fn play_clash(
+        &mut self,
+        region_1_id: RegionId,
+        region_2_id: RegionId,
+        rules: &Rules,
+        dice_rng: &mut impl Rng,
+        env_rng: &mut impl Rng,
+    ) -> Result<CaptureResult, GameError> {
+        let region_1 = self.board.region(region_1_id)?.clone();
+        let region_2 = self.board.region(region_2_id)?.clone();
+        let mut log_entry = GameLogEntry::new(
+            GameAction::Clash,
+            self.turn_counter,
+            self.turn_of_player,
+            region_1,
+            region_2,
+        );
+        loop {
+            log_entry.region_1_dice_result =
+                self.dice_kind.roll(log_entry.region_1.num_dice, dice_rng);
+            log_entry.region_2_dice_result =
+                self.dice_kind.roll(log_entry.region_2.num_dice, dice_rng);
+            log_entry.resolve_with_tie_rule(rules.tie_rule);
+            if !(log_entry.is_tie && rules.tie_rule == TieRule::Reroll) {
+                break;
+            }
+        }
+        let attacker_won = log_entry.attacker_won;
+        self.game_log.push(log_entry);
+
+        match self.resolve_clash(region_1_id, region_2_id, attacker_won, rules, env_rng) {
+            Ok(capture_result) => {
+                self.log_capture(&capture_result);
+                Ok(capture_result)
+            }
+            Err(err) => {
+                self.game_log.pop();
+                Err(err)
+            }
+        }
+    }
+
+    /// Same as [`GameState::apply_clash_with_retreat_rule`] with the retreat rule of `rules`,
+    /// and also tell whether losing the region split the regions of its former owner into more
+    /// groups than before
+    pub fn resolve_clash(
+        &mut self,
+        region_1_id: RegionId,
+        region_2_id: RegionId,
+        attacker_won: bool,
+        rules: &Rules,
+        rng: &mut impl Rng,
+    ) -> Result<CaptureResult, GameError> {
+        let loser_id = match attacker_won {
+            true => region_2_id,
+            false => region_1_id,
+        };
+        let losing_player = self.board.region(loser_id)?.owner;
+        let groups_before = self.board.connected_components(losing_player).len();
+
+        let (transfer, shelter) = self.apply_clash_sheltered(
+            region_1_id,
+            region_2_id,
+            attacker_won,
+            rules.retreat_rule,
+            rng,
+        )?;
+
+        Ok(CaptureResult {
+            transfer,
+            losing_player,
+            split_occurred: self.board.connected_components(losing_player).len() > groups_before,
+            shelter,
+        })
+    }
+
+    /// Same as [`GameState::apply_clash`], but a defeated defender may save some of its dice
+    /// by moving them to an adjacent region of its owner according to `retreat_rule`
+    pub fn apply_clash_with_retreat_rule(
+        &mut self,
+        region_1_id: RegionId,
+        region_2_id: RegionId,
+        attacker_won: bool,
+        retreat_rule: RetreatRule,
+        rng: &mut impl Rng,
+    ) -> Result<Option<DiceTransfer>, GameError> {
+        self.apply_clash_sheltered(region_1_id, region_2_id, attacker_won, retreat_rule, rng)
+            .map(|(transfer, _)| transfer)
+    }
+
+    /// Same as [`GameState::apply_clash_with_retreat_rule`], also giving the shelter of
+    /// retreating dice as it was before the clash
+    fn apply_clash_sheltered(
+        &mut self,
+        region_1_id: RegionId,
+        region_2_id: RegionId,
+        attacker_won: bool,
+        retreat_rule: RetreatRule,
+        rng: &mut impl Rng,
+    ) -> Result<(Option<DiceTransfer>, Option<Region>), GameError> {
+        self.board.region(region_1_id)?;
+        self.board.region(region_2_id)?;
+        if !self.board.is_opponent(region_1_id, region_2_id) {
+            return Err(GameError::NotOpponents {
+                region_1: region_1_id.0,
+                region_2: region_2_id.0,
+            });
+        }
+
+        let (winner_id, loser_id) = match attacker_won {
+            true => (region_1_id, region_2_id),
+            false => (region_2_id, region_1_id),
+        };
+
+        let shelter = match attacker_won {
+            true => self.retreat_defeated_dice(region_2_id, retreat_rule),
+            false => None,
+        };
+
+        self.board.heat_up(loser_id);
+
+        let regions = &mut self.board.regions;
+        self.player_regions
+            .transfer(loser_id, regions[loser_id].owner, regions[winner_id].owner);
+        regions[loser_id].owner = regions[winner_id].owner;
+        regions[loser_id].fortify = 0;
+        regions[loser_id].capture_count += 1;
+
+        let winner_dice = regions[winner_id].num_dice;
+        if winner_dice <= 1 {
+            return Ok((None, shelter));
+        }
+
+        let loser_cap = regions[loser_id].dice_cap(self.board.dice_cap_rule);
+        regions[loser_id].num_dice = rng.gen_range(1..winner_dice).min(loser_cap);
+        regions[winner_id].num_dice -= regions[loser_id].num_dice - 1;
+
+        let transfer = DiceTransfer {
+            from_region: winner_id,
+            to_region: loser_id,
+            num_dice: regions[loser_id].num_dice,
+        };
+        Ok((Some(transfer), shelter))
+    }
+
+    /// Move a share of dice of a defeated region to the adjacent region of the same owner
+    /// with the most room left, the lowest id among equals. One dice always stays behind, so
+    /// the captured region isn't left empty when the winner has no dice to move onto it.
+    /// Gives the shelter as it was before taking in the dice.
+    fn retreat_defeated_dice(
+        &mut self,
+        region_id: RegionId,
+        retreat_rule: RetreatRule,
+    ) -> Option<Region> {
+        let fraction = match retreat_rule {
+            RetreatRule::Disabled => return None,
+            RetreatRule::Fraction(fraction) => fraction.clamp(0.0, 1.0),
+        };
+
+        let board = &self.board;
+        let regions = &board.regions;
+        let owner = regions[region_id].owner;
+        let room = |id: RegionId| board.dice_cap(&regions[id]) - regions[id].num_dice;
+        let shelter = board
+            .neighbours(region_id)
+            .iter()
+            .copied()
+            .filter(|id| regions[*id].owner == owner && room(*id) > 0)
+            .max_by_key(|id| (room(*id), std::cmp::Reverse(*id)));
+
+        let shelter_id = shelter?;
+        let shelter = regions[shelter_id].clone();
+        let num_dice = regions[region_id].num_dice;
+        let num_dice = ((num_dice as f32 * fraction) as usize).min(num_dice.saturating_sub(1));
+
+        let overflow = self.board.add_dice(shelter_id, num_dice);
+        self.board.regions[region_id].num_dice -= num_dice - overflow;
+
+        Some(shelter)
+    }
+}
+
+/// Rule deciding how many dice a region can hold
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum DiceCapRule {
+    /// Every region holds up to [`MAX_DICE`]
+    #[default]
+    Global,
+    /// Bigger regions hold more dice, up to [`MAX_DICE`]
+    RegionSize,
+}
+
+/// How starting dice of a player are spread over their regions. Every strategy hands out
+/// the same number of dice per player, only where they end up differs.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum DiceAllocation {
+    /// One to three dice per region at random
+    #[default]
+    Uniform,
+    /// More dice on regions bordering more enemy regions
+    FrontloadBorders,
+    /// Most dice piled up in a few strongholds, the rest of the regions get one each
+    Clustered,
+}
+
+/// Least number of dice a region needs to attack
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct MinAttackDice(pub usize);
+
+impl Default for MinAttackDice {
+    fn default() -> Self {
+        MinAttackDice(1)
+    }
+}
+
+/// Resource with reinforcement dice of each player that didn't fit on the board yet
+#[derive(Resource, Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ReinforcementReserve {
+    pub by_player: HashMap<PlayerId, usize>,
+}
+
+/// Resource choosing who takes the first turn of a match
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FirstPlayer {
+    Player(PlayerId),
+    /// Drawn from the env PRNG, so a fixed `env_seed` always picks the same player
+    Random,
+}
+
+impl Default for FirstPlayer {
+    fn default() -> Self {
+        FirstPlayer::Player(PlayerId(0))
+    }
+}
+
+/// Rule deciding a clash where both sides rolled the same sum
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize, ValueEnum)]
+pub enum TieRule {
+    #[default]
+    DefenderWins,
+    AttackerWins,
+    /// Both sides roll again
+    Reroll,
+}
+
+/// Optional rule rewarding a capture with another attack
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum CaptureBonus {
+    /// Every region attacks at most once per turn
+    #[default]
+    Disabled,
+    /// A region that captured its target may attack again in the same turn
+    ExtraAction,
+}
+
+/// Optional rule giving the owner of the single largest region one more reinforcement
+/// dice per turn
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub struct LargestArmyBonus(pub bool);
+
+/// Optional rule letting a defeated defender save some of its dice
+#[derive(Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub enum RetreatRule {
+    /// Defeated defender loses all of its dice
+    #[default]
+    Disabled,
+    /// Share of the defender's dice, rounded down, retreating to an adjacent friendly region
+    Fraction(f32),
 }
 
-#[derive(Clone, Resource)]
-pub struct GameState {
-    pub board: Board,
-    pub turn_of_player: usize,
-    pub turn_counter: usize,
-    pub number_of_players: usize,
-    pub game_log: Vec<GameLogEntry>,
+/// Resource with the optional rules a match is played by
+#[derive(Resource, Clone, Copy, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct Rules {
+    pub min_attack_dice: MinAttackDice,
+    pub capture_bonus: CaptureBonus,
+    pub tie_rule: TieRule,
+    pub largest_army_bonus: LargestArmyBonus,
+    pub retreat_rule: RetreatRule,
 }
 
-impl GameState {
-    // Enumerates a list of possible moves for a player
-    #[allow(dead_code)]
-    pub fn possible_moves(self) -> Vec<(Region, Region)> {
-        let regions_owned_by_player: Vec<Region> = self
-            .board
-            .regions
-            .iter()
-            .filter(|region| region.owner == self.turn_of_player)
-            .cloned()
-            .collect();
+/// Standing of a player during a game
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PlayerStats {
+    pub player: PlayerId,
+    pub region_count: usize,
+    pub total_dice: usize,
+    /// Number of regions in the player's largest group of bordering regions
+    pub largest_group: usize,
+}
 
-        let mut possible_moves: Vec<(Region, Region)> = Vec::new();
+/// Dice moved from a winning region onto a region it has captured
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DiceTransfer {
+    pub from_region: RegionId,
+    pub to_region: RegionId,
+    pub num_dice: usize,
+}
 
-        for region1 in regions_owned_by_player.iter() {
-            for region2 in self.board.regions.iter() {
-                if region1.is_opponent(region2) {
-                    possible_moves.push((region1.clone(), region2.clone()));
-                }
-            }
-        }
+/// Outcome of a clash applied to the board by [`GameState::resolve_clash`]
+#[derive(Clone, PartialEq, Debug)]
+pub struct CaptureResult {
+    pub transfer: Option<DiceTransfer>,
+    /// Player who lost a region in the clash
+    pub losing_player: PlayerId,
+    /// Whether regions of the losing player fell apart into more groups
+    pub split_occurred: bool,
+    /// Region that took in retreating dice, as it was before the clash
+    pub shelter: Option<Region>,
+}
 
-        possible_moves
-    }
+/// Kind of action recorded by a [`GameLogEntry`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum GameAction {
+    /// Region 1 attacked region 2
+    #[default]
+    Clash,
+    /// Dice were pulled back from region 1 to region 2
+    Retreat,
+    /// Region 1, which is also region 2, was fortified
+    Fortify,
+    /// Region 1, which is also region 2, was reinforced at the end of a turn
+    Reinforce,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct GameLogEntry {
+    /// Saves from older versions only logged clashes
+    #[serde(default)]
+    pub action: GameAction,
     pub turn_counter: usize,
-    pub turn_of_player: usize,
+    pub turn_of_player: PlayerId,
     pub region_1: Region,
     pub region_2: Region,
     pub region_1_dice_result: Vec<usize>,
     pub region_2_dice_result: Vec<usize>,
+    /// Dice of region 1 once the action is applied, filled in by [`GameState::log_dice_after`]
+    #[serde(default)]
+    pub region_1_dice_after: usize,
+    /// Dice of region 2 once the action is applied
+    #[serde(default)]
+    pub region_2_dice_after: usize,
+    /// Whether the attacker (region 1) won the clash, decided once dice results are known
+    pub attacker_won: bool,
+    /// Whether both sides rolled the same sum
+    pub is_tie: bool,
+    /// Region of the defender that took in dice retreating from the captured region,
+    /// as it was before the clash
+    #[serde(default)]
+    pub shelter: Option<Region>,
+    /// Dice of the shelter once the clash is applied
+    #[serde(default)]
+    pub shelter_dice_after: usize,
+}
+
+impl GameLogEntry {
+    /// Entry of an action that hasn't been applied yet, without dice results
+    pub fn new(
+        action: GameAction,
+        turn_counter: usize,
+        turn_of_player: PlayerId,
+        region_1: Region,
+        region_2: Region,
+    ) -> Self {
+        GameLogEntry {
+            action,
+            turn_counter,
+            turn_of_player,
+            region_1,
+            region_2,
+            region_1_dice_result: Vec::new(),
+            region_2_dice_result: Vec::new(),
+            region_1_dice_after: 0,
+            region_2_dice_after: 0,
+            attacker_won: false,
+            is_tie: false,
+            shelter: None,
+            shelter_dice_after: 0,
+        }
+    }
+
+    /// Sum of dice rolled by region 1, 0 until the dice have settled
+    pub fn region_1_dice_sum(&self) -> usize {
+        self.region_1_dice_result.iter().sum()
+    }
+
+    /// Sum of dice rolled by region 2 without its fortify bonus, 0 until the dice have settled
+    pub fn region_2_dice_sum(&self) -> usize {
+        self.region_2_dice_result.iter().sum()
+    }
+
+    /// Decide the outcome of a clash from dice results. Defender's fortify level is added to
+    /// its sum. In case of a tie, the attacker loses.
+    pub fn resolve(&mut self) {
+        self.resolve_with_tie_rule(TieRule::DefenderWins);
+    }
+
+    /// Same as [`GameLogEntry::resolve`], with ties decided by `tie_rule`.
+    /// A tie to be rerolled is left as lost by the attacker.
+    pub fn resolve_with_tie_rule(&mut self, tie_rule: TieRule) {
+        let result_1 = self.region_1_dice_sum();
+        let result_2 = self.region_2_dice_sum() + self.region_2.fortify_bonus();
+
+        self.is_tie = result_1 == result_2;
+        self.attacker_won =
+            result_1 > result_2 || (self.is_tie && tie_rule == TieRule::AttackerWins);
+    }
+}
+
+/// Game written by [`GameState::save_to_path`], with what a game in progress needs on top of
+/// its state to go on by the same rules
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub game_state: GameState,
+    pub rules: Rules,
+    pub reinforcement_reserve: ReinforcementReserve,
+}
+
+/// Contents of a replay file written by [`GameState::export_replay`]. The board is generated
+/// again from the seeds and the board config, then the log is replayed on it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ReplayData {
+    pub world_seed: u64,
+    pub env_seed: u64,
+    pub number_of_players: usize,
+    /// Replays from older versions were only played with default settings
+    #[serde(default)]
+    pub first_player: PlayerId,
+    #[serde(default)]
+    pub dice_kind: DiceKind,
+    #[serde(default)]
+    pub rules: Rules,
+    #[serde(default)]
+    pub board_config: BoardConfig,
+    pub game_log: Vec<GameLogEntry>,
+}
+
+/// Read a replay file written by [`GameState::export_replay`]
+pub fn load_replay(path: &Path) -> io::Result<ReplayData> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
 }
 
-#[derive(Default, Component, Clone)]
+#[derive(Default, Component, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Region {
     pub hexes: Vec<(isize, isize)>,
-    pub owner: usize,
+    pub owner: PlayerId,
     pub num_dice: usize,
-    pub id: usize,
+    pub id: RegionId,
+    /// Defensive bonus added to the dice sum of this region when it's attacked
+    pub fortify: u8,
+    /// Number of times this region changed hands during the match
+    #[serde(default)]
+    pub capture_count: usize,
 }
 
 impl Region {
+    /// Region without fortifications that hasn't changed hands yet
+    pub fn new(id: RegionId, owner: PlayerId, num_dice: usize, hexes: Vec<(isize, isize)>) -> Self {
+        Region {
+            hexes,
+            owner,
+            num_dice,
+            id,
+            fortify: 0,
+            capture_count: 0,
+        }
+    }
+
+    /// Most dice this region can hold under `rule`
+    pub fn dice_cap(&self, rule: DiceCapRule) -> usize {
+        match rule {
+            DiceCapRule::Global => MAX_DICE,
+            DiceCapRule::RegionSize => (self.hexes.len() + 2).min(MAX_DICE),
+        }
+    }
+
+    /// Add up to `n` dice without going over [`MAX_DICE`], regardless of the dice cap rule.
+    /// Returns the number of dice that didn't fit.
+    pub fn add_dice(&mut self, n: usize) -> usize {
+        self.add_dice_up_to(n, MAX_DICE)
+    }
+
+    fn add_dice_up_to(&mut self, n: usize, cap: usize) -> usize {
+        let added = n.min(cap.saturating_sub(self.num_dice));
+        self.num_dice += added;
+        n - added
+    }
+
+    /// Probability of each possible sum of `n` six-sided dice.
+    /// Element `i` holds the probability of rolling a sum of `n + i`.
+    pub fn dice_sum_distribution(n: usize) -> Vec<f64> {
+        DiceKind::default().sum_distribution(n)
+    }
+
+    /// Probability of winning a clash against `defender`. Ties go to the defender.
+    pub fn attack_win_probability(&self, defender: &Region) -> f64 {
+        self.attack_win_probability_with(defender, DiceKind::default())
+    }
+
+    /// Same as [`Region::attack_win_probability`], with both sides rolling `dice_kind`
+    pub fn attack_win_probability_with(&self, defender: &Region, dice_kind: DiceKind) -> f64 {
+        clash_win_probability_with_dice(
+            dice_kind,
+            self.num_dice,
+            defender.num_dice,
+            defender.fortify_bonus(),
+        )
+    }
+
     pub fn center_of_mass(&self) -> (f32, f32) {
         let mut x = 0.0;
         let mut y = 0.0;
@@ -81,6 +1791,19 @@ impl Region {
         (x / self.hexes.len() as f32, y / self.hexes.len() as f32)
     }
 
+    /// Center of mass as `(x, z)` on the plane of the rendered board
+    pub fn world_center_of_mass(&self) -> (f32, f32) {
+        let mut x = 0.0;
+        let mut z = 0.0;
+        for (q, r) in self.hexes.iter() {
+            let [hex_x, _, hex_z] = center(1.0, &HexCoord::new(*q, *r), &[0.0, 0.0, 0.0]);
+            x += hex_x;
+            z += hex_z;
+        }
+
+        (x / self.hexes.len() as f32, z / self.hexes.len() as f32)
+    }
+
     pub fn center_hex(&self) -> HexCoord {
         let center = self.center_of_mass();
         let mut nearest_hex: (isize, isize) = (0, 0);
@@ -98,11 +1821,46 @@ impl Region {
         HexCoord::new(nearest_hex.0, nearest_hex.1)
     }
 
-    pub fn is_opponent(&self, other: &Region) -> bool {
-        if self.owner == other.owner {
-            return false;
+    /// Bonus to the dice sum when defending, capped at [`MAX_FORTIFY`]
+    pub fn fortify_bonus(&self) -> usize {
+        self.fortify.min(MAX_FORTIFY) as usize
+    }
+
+    /// Whether all hexes of a region are reachable from each other
+    pub fn is_contiguous(&self) -> bool {
+        let mut reached = HashSet::new();
+        let mut frontier: Vec<(isize, isize)> = self.hexes.iter().take(1).cloned().collect();
+        while let Some(hex) = frontier.pop() {
+            if !reached.insert(hex) {
+                continue;
+            }
+            for neighbour in HexCoord::new(hex.0, hex.1).neighbors() {
+                let coord = (neighbour.q, neighbour.r);
+                if self.hexes.contains(&coord) && !reached.contains(&coord) {
+                    frontier.push(coord);
+                }
+            }
         }
 
+        reached.len() == self.hexes.len()
+    }
+
+    /// Fewest steps from a hex of this region to a hex of another one,
+    /// `usize::MAX` if either region has no hexes
+    pub fn distance_to(&self, other: &Region) -> usize {
+        self.hexes
+            .iter()
+            .flat_map(|hex| {
+                other.hexes.iter().map(move |other_hex| {
+                    HexCoord::new(hex.0, hex.1).distance(&HexCoord::new(other_hex.0, other_hex.1))
+                })
+            })
+            .min()
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Whether two regions share a border, regardless of their owners
+    pub fn is_neighbour(&self, other: &Region) -> bool {
         for hex in self.hexes.iter() {
             let hex_coord = HexCoord::new(hex.0, hex.1);
             for neighbour_coord in hex_coord.neighbors() {
@@ -119,32 +1877,449 @@ impl Region {
     }
 }
 
-pub fn generate_board(number_of_players: usize, mut rng: ChaCha20Rng) -> Board {
-    // Roughly half of the board occupied by patches (regions)
-    let patch_size: isize =
-        (BOARD_SIZE * BOARD_SIZE) / (NUMBER_OF_PATCHES * number_of_players * 2) as isize;
+/// Dice clashes are fought with. Only the logical rolls follow it, dice thrown by physics
+/// always show six faces.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(try_from = "DiceSides")]
+pub struct DiceKind {
+    sides: u32,
+}
+
+/// Serialized [`DiceKind`], checked with [`DiceKind::new`] once read
+#[derive(Deserialize)]
+struct DiceSides {
+    sides: u32,
+}
+
+impl TryFrom<DiceSides> for DiceKind {
+    type Error = GameError;
+
+    fn try_from(dice_sides: DiceSides) -> Result<Self, GameError> {
+        DiceKind::new(dice_sides.sides)
+    }
+}
+
+impl Default for DiceKind {
+    fn default() -> Self {
+        DiceKind { sides: 6 }
+    }
+}
+
+impl DiceKind {
+    /// Dice with `sides` faces numbered from 1, from 2 to [`MAX_DICE_SIDES`] of them
+    pub fn new(sides: u32) -> Result<Self, GameError> {
+        match (2..=MAX_DICE_SIDES).contains(&sides) {
+            true => Ok(DiceKind { sides }),
+            false => Err(GameError::InvalidDiceSides(sides)),
+        }
+    }
+
+    /// Number of faces of each dice
+    pub fn sides(&self) -> u32 {
+        self.sides
+    }
+
+    /// Values of `num_dice` dice of this kind
+    pub fn roll(&self, num_dice: usize, rng: &mut impl Rng) -> Vec<usize> {
+        (0..num_dice)
+            .map(|_| rng.gen_range(1..=self.sides as usize))
+            .collect()
+    }
+
+    /// Probability of each possible sum of `n` dice of this kind.
+    /// Element `i` holds the probability of rolling a sum of `n + i`.
+    pub fn sum_distribution(&self, n: usize) -> Vec<f64> {
+        let sides = self.sides as usize;
+        let mut distribution = vec![1.0];
+        for _ in 0..n {
+            let mut next = vec![0.0; distribution.len() + sides - 1];
+            for (sum, p) in distribution.iter().enumerate() {
+                for face in 0..sides {
+                    next[sum + face] += p / sides as f64;
+                }
+            }
+            distribution = next;
+        }
+
+        distribution
+    }
+}
+
+/// Values of `num_dice` six-sided dice
+pub fn roll_dice(num_dice: usize, rng: &mut impl Rng) -> Vec<usize> {
+    DiceKind::default().roll(num_dice, rng)
+}
+
+/// The faces of a dice drawn without replacement. The bag is refilled and shuffled
+/// once empty, so every face comes up exactly once in a full round of draws.
+#[derive(Clone, Default, Debug)]
+pub struct DiceBag {
+    faces: Vec<usize>,
+    dice_kind: DiceKind,
+}
+
+impl DiceBag {
+    /// Empty bag of faces of `dice_kind`, a default bag holds six faces
+    pub fn new(dice_kind: DiceKind) -> Self {
+        DiceBag {
+            faces: Vec::new(),
+            dice_kind,
+        }
+    }
+
+    pub fn draw(&mut self, rng: &mut impl Rng) -> usize {
+        if self.faces.is_empty() {
+            self.faces = (1..=self.dice_kind.sides as usize).collect();
+            self.faces.shuffle(rng);
+        }
+
+        self.faces.pop().unwrap()
+    }
+}
+
+/// Outline of the area the generator places patches in
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum BoardShape {
+    /// Square with a side of [`BoardConfig::board_size`]. Patches may grow past its edges.
+    #[default]
+    Square,
+    /// `w` by `h` hexes around the origin
+    Rectangle { w: isize, h: isize },
+    /// Hexes at most `radius` steps away from the origin
+    Hexagon { radius: isize },
+}
+
+impl BoardShape {
+    /// Lowest and one past the highest coordinate on both axes where patches can start
+    fn bounds(&self, board_size: isize) -> ((isize, isize), (isize, isize)) {
+        match *self {
+            BoardShape::Square => {
+                let half_board_size = board_size / 2 - 1;
+                (
+                    (-half_board_size, half_board_size),
+                    (-half_board_size, half_board_size),
+                )
+            }
+            BoardShape::Rectangle { w, h } => ((-w / 2, w - w / 2), (-h / 2, h - h / 2)),
+            BoardShape::Hexagon { radius } => ((-radius, radius + 1), (-radius, radius + 1)),
+        }
+    }
+
+    /// Whether patches may cover a hex
+    pub fn contains(&self, coord: (isize, isize)) -> bool {
+        match *self {
+            BoardShape::Square => true,
+            BoardShape::Rectangle { .. } => {
+                let ((q_min, q_max), (r_min, r_max)) = self.bounds(0);
+                (q_min..q_max).contains(&coord.0) && (r_min..r_max).contains(&coord.1)
+            }
+            BoardShape::Hexagon { radius } => {
+                HexCoord::new(coord.0, coord.1).distance(&HexCoord::origin()) <= radius as usize
+            }
+        }
+    }
+}
+
+/// Settings of board generation
+#[derive(Clone, Resource, Serialize, Deserialize)]
+pub struct BoardConfig {
+    /// Side of the square the generator places patches in, in hexes
+    pub board_size: isize,
+    /// Outline of the board, [`BoardShape::Square`] uses `board_size`
+    pub shape: BoardShape,
+    /// Number of patches (regions) each player gets
+    pub number_of_patches: usize,
+    /// Patches of all players cover roughly `1 / patch_size_divisor` of the board
+    pub patch_size_divisor: usize,
+    /// Upper bound on the number of regions. Generation stops adding patches once it's reached.
+    pub max_regions: usize,
+    /// Number of interior hexes left empty as lakes
+    pub holes: usize,
+    pub dice_cap_rule: DiceCapRule,
+    pub dice_allocation: DiceAllocation,
+    /// Hand single-hex regions cut off from the rest of their owner's regions over to
+    /// the player owning most of their neighbours
+    pub reassign_isolated_regions: bool,
+    /// Reflect one half of the board through its center, handing mirrored regions to the
+    /// player half a round of turns away, so every player gets the same start
+    pub mirror: bool,
+}
+
+impl BoardConfig {
+    /// Number of hexes in the shape the generator places patches in
+    pub fn board_area(&self) -> usize {
+        match self.shape {
+            BoardShape::Square => (self.board_size * self.board_size) as usize,
+            BoardShape::Rectangle { w, h } => (w.max(0) * h.max(0)) as usize,
+            BoardShape::Hexagon { radius } => {
+                (3 * radius.max(0) * (radius.max(0) + 1) + 1) as usize
+            }
+        }
+    }
+
+    /// Number of hexes a patch grows to, not counting the one it starts from
+    fn patch_size(&self, number_of_players: usize) -> usize {
+        self.board_area() / (self.number_of_patches * number_of_players * self.patch_size_divisor)
+    }
+
+    /// Check that patches of `number_of_players` players fit on the board
+    pub fn validate(&self, number_of_players: usize) -> Result<(), GameError> {
+        // Only square boards take their size from `board_size`
+        let big_enough = match self.shape {
+            BoardShape::Square => self.board_size >= MIN_BOARD_SIZE,
+            BoardShape::Rectangle { w, h } => w >= MIN_BOARD_SIZE && h >= MIN_BOARD_SIZE,
+            BoardShape::Hexagon { radius } => radius >= MIN_BOARD_SIZE / 2,
+        };
+        if !big_enough {
+            return Err(GameError::InvalidBoardConfig("board is too small"));
+        }
+        if number_of_players == 0 || self.number_of_patches == 0 || self.patch_size_divisor == 0 {
+            return Err(GameError::InvalidBoardConfig(
+                "players, patches and patch size divisor can't be 0",
+            ));
+        }
+        if self.mirror && !number_of_players.is_multiple_of(2) {
+            return Err(GameError::InvalidBoardConfig(
+                "mirrored boards need an even number of players",
+            ));
+        }
+        if self.number_of_patches * number_of_players * self.patch_size_divisor > self.board_area()
+        {
+            return Err(GameError::InvalidBoardConfig(
+                "patches of all players don't fit on the board",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        BoardConfig {
+            board_size: 20,
+            shape: BoardShape::Square,
+            number_of_patches: 16,
+            patch_size_divisor: 2,
+            max_regions: 256,
+            holes: 0,
+            dice_cap_rule: DiceCapRule::Global,
+            dice_allocation: DiceAllocation::Uniform,
+            reassign_isolated_regions: false,
+            mirror: false,
+        }
+    }
+}
+
+/// Candidate board of a new game, rerolled until the player settles on a layout
+#[derive(Clone)]
+pub struct BoardPreview {
+    pub world_seed: u64,
+    pub board: Board,
+    number_of_players: usize,
+    config: BoardConfig,
+}
+
+impl BoardPreview {
+    pub fn new(
+        world_seed: u64,
+        number_of_players: usize,
+        config: &BoardConfig,
+    ) -> Result<Self, GameError> {
+        Ok(BoardPreview {
+            world_seed,
+            board: generate_playable_board(
+                number_of_players,
+                config,
+                world_seed,
+                generation_threads(),
+            )?,
+            number_of_players,
+            config: config.clone(),
+        })
+    }
+
+    /// Replace the board with the next candidate. The sequence of candidates only
+    /// depends on the first world seed. The board is kept when no candidate can be generated.
+    pub fn reroll(&mut self) -> Result<(), GameError> {
+        *self = BoardPreview::new(
+            preview_seed(self.world_seed),
+            self.number_of_players,
+            &self.config,
+        )?;
+        Ok(())
+    }
+
+    /// World seed to start the game with
+    pub fn commit(self) -> u64 {
+        self.world_seed
+    }
+}
+
+/// Generate a board of randomly grown patches (regions).
+///
+/// # Panics
+///
+/// If `config` doesn't pass [`BoardConfig::validate`] for `number_of_players`, or a patch
+/// can't be placed, see [`try_generate_board`].
+pub fn generate_board(number_of_players: usize, config: &BoardConfig, rng: ChaCha20Rng) -> Board {
+    match try_generate_board(number_of_players, config, rng) {
+        Ok(board) => board,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Generate a board from `world_seed`, and if that fails, from `threads - 1` seeds derived
+/// from it at once, keeping the board of the first seed that succeeded. The board is the one
+/// of [`generate_board`] whenever that one succeeds. Without threads, as on wasm32, only
+/// `world_seed` is tried.
+pub fn generate_board_parallel(
+    number_of_players: usize,
+    config: &BoardConfig,
+    world_seed: u64,
+    threads: usize,
+) -> Result<Board, GameError> {
+    let first = try_generate_board(number_of_players, config, get_randomness(world_seed));
+    if first.is_ok() || threads <= 1 || cfg!(target_arch = "wasm32") {
+        return first;
+    }
+
+    let mut results: Vec<Result<Board, GameError>> = thread::scope(|scope| {
+        let handles: Vec<_> = (1..threads as u64)
+            .map(|i| {
+                let seed = world_seed.wrapping_add(i.wrapping_mul(CANDIDATE_SEED_STEP));
+                scope.spawn(move || {
+                    try_generate_board(number_of_players, config, get_randomness(seed))
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| panic::resume_unwind(err))
+            })
+            .collect()
+    });
+
+    match results.iter().position(|result| result.is_ok()) {
+        Some(index) => results.swap_remove(index),
+        None => first,
+    }
+}
+
+/// Threads a game generates its board on. Previews use as many, so they show the very board
+/// the game starts on.
+pub fn generation_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Generate a board with [`generate_board_parallel`] on which every player has an attack.
+/// A player walled in by their own regions could never attack, so the game wouldn't end.
+/// Such boards are generated again from a seed derived from the last one, and after
+/// [`MAX_BOARD_ATTEMPTS`] boards generation gives up with an error.
+pub fn generate_playable_board(
+    number_of_players: usize,
+    config: &BoardConfig,
+    world_seed: u64,
+    threads: usize,
+) -> Result<Board, GameError> {
+    let mut seed = world_seed;
+    for _ in 0..MAX_BOARD_ATTEMPTS {
+        let board = generate_board_parallel(number_of_players, config, seed, threads)?;
+        let game_state = GameState::from_board(board, number_of_players);
+        if game_state.players_with_moves().len() == number_of_players {
+            return Ok(game_state.board);
+        }
+
+        seed = get_randomness(seed).gen();
+    }
+
+    Err(GameError::NoPlayableBoard {
+        attempts: MAX_BOARD_ATTEMPTS,
+    })
+}
+
+/// Same as [`generate_board`], but an invalid `config` or a patch without room after
+/// [`MAX_PATCH_ATTEMPTS`] tries is returned as an error
+pub fn try_generate_board(
+    number_of_players: usize,
+    config: &BoardConfig,
+    mut rng: ChaCha20Rng,
+) -> Result<Board, GameError> {
+    config.validate(number_of_players)?;
+
+    let patch_size = config.patch_size(number_of_players);
+    let ((q_min, q_max), (r_min, r_max)) = config.shape.bounds(config.board_size);
+
+    let mut board = Board {
+        dice_cap_rule: config.dice_cap_rule,
+        ..Default::default()
+    };
+
+    // Carve lakes before patches grow, so regions grow around them. Lakes keep off the edge
+    // of the shape, so they're always surrounded by land.
+    let is_interior = |(q, r): (isize, isize)| {
+        config.shape.contains((q, r))
+            && HexCoord::new(q, r)
+                .neighbors()
+                .all(|neighbor| config.shape.contains((neighbor.q, neighbor.r)))
+    };
+    let interior_area = (q_min + 1..q_max - 1)
+        .flat_map(|q| (r_min + 1..r_max - 1).map(move |r| (q, r)))
+        .filter(|coord| is_interior(*coord))
+        .count();
+    while board.holes.len() < usize::min(config.holes, interior_area) {
+        let coord = (
+            rng.gen_range(q_min + 1..q_max - 1),
+            rng.gen_range(r_min + 1..r_max - 1),
+        );
+        if is_interior(coord) {
+            board.holes.insert(coord);
+        }
+    }
+    let is_free = |hexes: &HashMap<(isize, isize), RegionId>, coord: &(isize, isize)| {
+        !hexes.contains_key(coord) && !board.holes.contains(coord) && config.shape.contains(*coord)
+    };
+
+    'patches: for patch in 0..config.number_of_patches {
+        for player in (0..number_of_players).map(PlayerId) {
+            if board.regions.len() >= config.max_regions {
+                break 'patches;
+            }
 
-    let mut board = Board::default();
+            // Small boards may fill up before every patch is placed
+            let has_free_starting_point = (q_min..q_max)
+                .flat_map(|q| (r_min..r_max).map(move |r| (q, r)))
+                .any(|coord| is_free(&board.hexes, &coord));
+            if !has_free_starting_point {
+                break 'patches;
+            }
 
-    for patch in 0..NUMBER_OF_PATCHES {
-        for player in 0..number_of_players {
             let mut is_starting_point_valid = false;
+            let mut attempts = 0;
 
             while !is_starting_point_valid {
                 let mut has_neighbours = false;
 
                 while !has_neighbours {
+                    attempts += 1;
+                    if attempts > MAX_PATCH_ATTEMPTS {
+                        return Err(GameError::BoardGenerationFailed {
+                            patch: board.regions.len(),
+                        });
+                    }
+
                     let mut hex_snapshot = board.hexes.clone();
 
                     // check if starting position is empty
-                    let initial_coord = (
-                        rng.gen_range(-HALF_BOARD_SIZE..HALF_BOARD_SIZE),
-                        rng.gen_range(-HALF_BOARD_SIZE..HALF_BOARD_SIZE),
-                    );
+                    let initial_coord = (rng.gen_range(q_min..q_max), rng.gen_range(r_min..r_max));
 
-                    if board.hexes.get(&initial_coord).is_none() {
+                    if is_free(&board.hexes, &initial_coord) {
                         is_starting_point_valid = true;
-                        hex_snapshot.insert(initial_coord, player);
+                        hex_snapshot.insert(initial_coord, RegionId(board.regions.len()));
                     } else {
                         // try over
                         continue;
@@ -163,7 +2338,7 @@ pub fn generate_board(number_of_players: usize, mut rng: ChaCha20Rng) -> Board {
                             let hex = HexCoord::new(coord.0, coord.1);
                             // iterate over all neighbors and find a free one
                             for neighbor in hex.neighbors() {
-                                if hex_snapshot.get(&(neighbor.q, neighbor.r)).is_none() {
+                                if is_free(&hex_snapshot, &(neighbor.q, neighbor.r)) {
                                     neightbour_hex = Some(hex.clone());
                                     break;
                                 }
@@ -184,13 +2359,13 @@ pub fn generate_board(number_of_players: usize, mut rng: ChaCha20Rng) -> Board {
                         let mut candidates: Vec<(isize, isize)> = vec![];
                         for neighbour in neightbour_hex.unwrap().neighbors() {
                             let neighbour_coord = (neighbour.q, neighbour.r);
-                            if hex_snapshot.get(&neighbour_coord).is_none() {
+                            if is_free(&hex_snapshot, &neighbour_coord) {
                                 candidates.push(neighbour_coord);
                             }
                         }
                         let candidate = candidates.iter().choose(&mut rng).unwrap();
                         patch_hexes.push(*candidate);
-                        hex_snapshot.insert(*candidate, player);
+                        hex_snapshot.insert(*candidate, RegionId(board.regions.len()));
                     }
 
                     if patch_hexes.len() == 1 {
@@ -210,7 +2385,7 @@ pub fn generate_board(number_of_players: usize, mut rng: ChaCha20Rng) -> Board {
 
                     // if could not generate a patch with a neightbours, start over
                     // except for the first patch
-                    if player == 0 && patch == 0 {
+                    if player == PlayerId(0) && patch == 0 {
                         has_neighbours = true;
                     }
 
@@ -218,12 +2393,8 @@ pub fn generate_board(number_of_players: usize, mut rng: ChaCha20Rng) -> Board {
                     // else, start over
                     if has_neighbours {
                         board.hexes = hex_snapshot;
-                        board.regions.push(Region {
-                            hexes: patch_hexes,
-                            owner: player,
-                            num_dice: 0,
-                            id: board.regions.len(),
-                        });
+                        let id = RegionId(board.regions.len());
+                        board.regions.push(Region::new(id, player, 0, patch_hexes));
                         break;
                     }
                 }
@@ -231,18 +2402,240 @@ pub fn generate_board(number_of_players: usize, mut rng: ChaCha20Rng) -> Board {
         }
     }
 
-    // allocate dice
-    let mut dice_budget: HashMap<usize, usize> = HashMap::new();
-    for p in 0..number_of_players {
-        dice_budget.insert(p, NUMBER_OF_PATCHES * 4);
+    board.compute_adjacency();
+    check_connectivity(&mut board, number_of_players, config);
+    allocate_dice(&mut board, number_of_players, config, &mut rng);
+
+    if config.mirror {
+        board = mirror_board(&board, number_of_players);
+    }
+
+    Ok(board)
+}
+
+/// Hexes, owner and number of dice of a region cut to one half of a mirrored board
+type RegionHalf = (Vec<(isize, isize)>, PlayerId, usize);
+
+/// Keep the half of a board with `r > 0`, and `q > 0` on the middle row, and reflect it
+/// through the center hex, which is left empty. Regions are cut to their part in the half
+/// connected to their first hex there.
+fn mirror_board(board: &Board, number_of_players: usize) -> Board {
+    let in_half = |(q, r): (isize, isize)| r > 0 || (r == 0 && q > 0);
+
+    let mut halves: Vec<RegionHalf> = Vec::new();
+    for region in board.regions.iter() {
+        let hexes: HashSet<(isize, isize)> = region
+            .hexes
+            .iter()
+            .copied()
+            .filter(|h| in_half(*h))
+            .collect();
+        let start = match region.hexes.iter().find(|h| hexes.contains(h)) {
+            Some(start) => *start,
+            None => continue,
+        };
+
+        let mut kept = vec![start];
+        let mut seen = HashSet::from([start]);
+        let mut i = 0;
+        while i < kept.len() {
+            for neighbour in HexCoord::new(kept[i].0, kept[i].1).neighbors() {
+                let coord = (neighbour.q, neighbour.r);
+                if hexes.contains(&coord) && seen.insert(coord) {
+                    kept.push(coord);
+                }
+            }
+            i += 1;
+        }
+
+        halves.push((kept, region.owner, region.num_dice));
+    }
+
+    let mirrored_halves: Vec<RegionHalf> = halves
+        .iter()
+        .map(|(hexes, owner, num_dice)| {
+            (
+                hexes.iter().map(|(q, r)| (-q, -r)).collect(),
+                PlayerId((owner.0 + number_of_players / 2) % number_of_players),
+                *num_dice,
+            )
+        })
+        .collect();
+
+    let mut mirrored = Board {
+        dice_cap_rule: board.dice_cap_rule,
+        holes: board
+            .holes
+            .iter()
+            .filter(|h| in_half(**h))
+            .flat_map(|(q, r)| [(*q, *r), (-q, -r)])
+            .collect(),
+        ..Default::default()
+    };
+    for (hexes, owner, num_dice) in halves.into_iter().chain(mirrored_halves) {
+        let id = RegionId(mirrored.regions.len());
+        for hex in hexes.iter() {
+            mirrored.hexes.insert(*hex, id);
+        }
+
+        let mut region = Region::new(id, owner, 0, hexes);
+        // Cut regions may hold fewer dice under the region size rule
+        region.num_dice = num_dice.min(region.dice_cap(mirrored.dice_cap_rule));
+        mirrored.regions.push(region);
+    }
+    mirrored.compute_adjacency();
+
+    mirrored
+}
+
+/// Spread dice of a player's regions again under `allocation`, keeping their total
+/// Give every region one to three dice out of a budget of its owner, then spread them
+/// according to the dice allocation of `config`
+fn allocate_dice(
+    board: &mut Board,
+    number_of_players: usize,
+    config: &BoardConfig,
+    rng: &mut ChaCha20Rng,
+) {
+    let mut dice_budget: HashMap<PlayerId, usize> = HashMap::new();
+    for p in (0..number_of_players).map(PlayerId) {
+        dice_budget.insert(p, config.number_of_patches * 4);
     }
 
     for region in board.regions.iter_mut() {
-        region.num_dice = rng.gen_range(1..usize::min(4, dice_budget[&region.owner]));
-        dice_budget.insert(region.owner, dice_budget[&region.owner] - region.num_dice);
+        let budget = dice_budget[&region.owner];
+        // Reassigned regions may leave a player more regions than the budget was made for.
+        // Once it's spent, regions keep the single dice every region needs.
+        region.num_dice = match budget {
+            0 => 1,
+            _ => rng
+                .gen_range(1..=budget.clamp(1, 3))
+                .min(region.dice_cap(config.dice_cap_rule)),
+        };
+        dice_budget.insert(region.owner, budget.saturating_sub(region.num_dice));
+    }
+
+    for player in (0..number_of_players).map(PlayerId) {
+        reallocate_dice(board, player, config.dice_allocation, rng);
+    }
+}
+
+fn reallocate_dice(
+    board: &mut Board,
+    player: PlayerId,
+    allocation: DiceAllocation,
+    rng: &mut ChaCha20Rng,
+) {
+    // Dice are already spread uniformly
+    if allocation == DiceAllocation::Uniform {
+        return;
+    }
+
+    let ids: Vec<RegionId> = board
+        .regions
+        .iter()
+        .filter(|region| region.owner == player)
+        .map(|region| region.id)
+        .collect();
+    let total: usize = ids.iter().map(|id| board.regions[*id].num_dice).sum();
+
+    // Every region keeps at least one dice
+    for id in ids.iter() {
+        board.regions[*id].num_dice = 1;
+    }
+    let dice = total - ids.len();
+
+    match allocation {
+        DiceAllocation::Uniform => {}
+        DiceAllocation::FrontloadBorders => {
+            let enemy_neighbours: HashMap<RegionId, usize> = ids
+                .iter()
+                .map(|id| (*id, board.enemy_neighbour_count(*id)))
+                .collect();
+            spread_dice(board, &ids, dice, |id| enemy_neighbours[&id] + 1, rng);
+        }
+        DiceAllocation::Clustered => {
+            let number_of_strongholds = (ids.len() / 4).max(1);
+            let strongholds: Vec<RegionId> = ids
+                .choose_multiple(rng, number_of_strongholds)
+                .copied()
+                .collect();
+            let left = spread_dice(board, &strongholds, dice, |_| 1, rng);
+            spread_dice(board, &ids, left, |_| 1, rng);
+        }
+    }
+}
+
+/// Hand `dice` out one by one to regions with room for them, picked at random with
+/// chances proportional to `weight`. Returns dice which didn't fit anywhere.
+fn spread_dice(
+    board: &mut Board,
+    ids: &[RegionId],
+    mut dice: usize,
+    weight: impl Fn(RegionId) -> usize,
+    rng: &mut ChaCha20Rng,
+) -> usize {
+    while dice > 0 {
+        let open: Vec<RegionId> = ids
+            .iter()
+            .copied()
+            .filter(|id| board.regions[*id].num_dice < board.dice_cap(&board.regions[*id]))
+            .collect();
+
+        match open.choose_weighted(rng, |id| weight(*id)) {
+            Ok(id) => {
+                board.add_dice(*id, 1);
+            }
+            Err(_) => break,
+        }
+        dice -= 1;
     }
 
-    board
+    dice
+}
+
+/// Warn about players whose regions are split into several groups, and reassign
+/// isolated single-hex regions if `config` asks for it
+fn check_connectivity(board: &mut Board, number_of_players: usize, config: &BoardConfig) {
+    for player in (0..number_of_players).map(PlayerId) {
+        let components = board.connected_components(player);
+        if components.len() <= 1 {
+            continue;
+        }
+
+        warn!(
+            "Regions of player {} are split into {} groups",
+            player.0 + 1,
+            components.len()
+        );
+
+        if !config.reassign_isolated_regions {
+            continue;
+        }
+
+        // The largest group always stays, so no player is left without regions
+        let largest = components.iter().map(|c| c.len()).max().unwrap_or(0);
+        let kept = components.iter().position(|c| c.len() == largest);
+        for (i, component) in components.iter().enumerate() {
+            let id = component[0];
+            if Some(i) == kept || component.len() > 1 || board.regions[id].hexes.len() > 1 {
+                continue;
+            }
+
+            let mut bordering: HashMap<PlayerId, usize> = HashMap::new();
+            for &neighbour in board.neighbours(id) {
+                *bordering.entry(board.regions[neighbour].owner).or_insert(0) += 1;
+            }
+
+            // Most bordering regions first, lower player index on ties
+            if let Some((owner, _)) = bordering
+                .into_iter()
+                .max_by_key(|(owner, count)| (*count, std::cmp::Reverse(*owner)))
+            {
+                board.regions[id].owner = owner;
+            }
+        }
+    }
 }
 
 #[derive(Default, Resource)]
@@ -262,3 +2655,51 @@ impl SelectedRegion {
         self.region = None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reassigned_regions_beyond_the_dice_budget_keep_a_dice() {
+        // Player 1 has four lone regions around a long region of player 0. Three of them
+        // are handed over, leaving player 0 with more regions than its single patch
+        // budgets dice for.
+        let config = BoardConfig {
+            number_of_patches: 1,
+            reassign_isolated_regions: true,
+            ..Default::default()
+        };
+        let mut board = Board::from_regions(vec![
+            Region::new(
+                RegionId(0),
+                PlayerId(0),
+                0,
+                vec![(0, 0), (1, 0), (2, 0), (3, 0)],
+            ),
+            Region::new(RegionId(1), PlayerId(1), 0, vec![(0, 1)]),
+            Region::new(RegionId(2), PlayerId(1), 0, vec![(2, 1)]),
+            Region::new(RegionId(3), PlayerId(1), 0, vec![(0, -1)]),
+            Region::new(RegionId(4), PlayerId(1), 0, vec![(2, -1)]),
+        ]);
+
+        check_connectivity(&mut board, 2, &config);
+        assert_eq!(board.connected_components(PlayerId(0)).len(), 1);
+        assert_eq!(
+            board
+                .regions
+                .iter()
+                .filter(|r| r.owner == PlayerId(0))
+                .count(),
+            4
+        );
+
+        for seed in 0..20 {
+            let mut board = board.clone();
+            allocate_dice(&mut board, 2, &config, &mut get_randomness(seed));
+            for region in board.regions.iter() {
+                assert!((1..=3).contains(&region.num_dice), "seed {}", seed);
+            }
+        }
+    }
+}
@@ -1,8 +1,10 @@
 // This code taken from https://github.com/Quantumplation/bevy-hex-example
 
+use serde::{Deserialize, Serialize};
+
 /// A coordinate on a hex grid, representing distances along the various directions of travel
 /// Invariant: In order to represent a valid hex coordinate, q + r + s must equal 0
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct HexCoord {
     pub q: isize,
     pub r: isize,
@@ -12,7 +14,6 @@ pub struct HexCoord {
 // The directions you can move on a hex grid
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum Direction {
-    #[allow(dead_code)]
     None,
     North,
     South,
@@ -22,7 +23,6 @@ pub enum Direction {
     Southeast,
 }
 impl Direction {
-    #[allow(dead_code)]
     pub fn opposite(&self) -> Self {
         use Direction::*;
         match self {
@@ -49,7 +49,6 @@ pub const DIRECTIONS: &[Direction] = &[
 
 impl HexCoord {
     /// The origin of an infinite hex grid
-    #[allow(dead_code)]
     pub fn origin() -> Self {
         HexCoord { q: 0, r: 0, s: 0 }
     }
@@ -117,6 +116,51 @@ impl HexCoord {
             iter: DIRECTIONS.iter(),
         }
     }
+
+    /// Number of steps between two coordinates
+    pub fn distance(&self, other: &HexCoord) -> usize {
+        ((self.q - other.q).abs() + (self.r - other.r).abs() + (self.s - other.s).abs()) as usize
+            / 2
+    }
+
+    /// Coordinates on a straight line between two coordinates, both ends included
+    pub fn line_to(&self, other: &HexCoord) -> Vec<HexCoord> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![self.clone()];
+        }
+
+        // Nudge the start, so points exactly between two hexes always round the same way
+        let (q, r, s) = (
+            self.q as f64 + 1e-6,
+            self.r as f64 + 2e-6,
+            self.s as f64 - 3e-6,
+        );
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                HexCoord::round(
+                    q + (other.q as f64 - q) * t,
+                    r + (other.r as f64 - r) * t,
+                    s + (other.s as f64 - s) * t,
+                )
+            })
+            .collect()
+    }
+
+    /// Nearest coordinate to a fractional one
+    fn round(q: f64, r: f64, s: f64) -> Self {
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+
+        // Fix the component with the biggest rounding error to keep q + r + s = 0
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        HexCoord::new(rq as isize, rr as isize)
+    }
 }
 
 #[cfg(test)]
@@ -145,4 +189,38 @@ mod tests {
             assert_eq!(point, neighbor.neighbor(dir.opposite()));
         }
     }
+
+    #[test]
+    fn distance_to_neighbors_and_self() {
+        let point = HexCoord::new(3, -5);
+        assert_eq!(point.distance(&point), 0);
+        for neighbor in point.neighbors() {
+            assert_eq!(point.distance(&neighbor), 1);
+        }
+    }
+
+    #[test]
+    fn distance_of_known_offsets() {
+        let origin = HexCoord::origin();
+        assert_eq!(origin.distance(&HexCoord::new(2, 0)), 2);
+        assert_eq!(origin.distance(&HexCoord::new(2, -2)), 2);
+        assert_eq!(origin.distance(&HexCoord::new(3, -1)), 3);
+        assert_eq!(origin.distance(&HexCoord::new(-2, 5)), 5);
+        assert_eq!(HexCoord::new(1, 1).distance(&HexCoord::new(-1, -1)), 4);
+    }
+
+    #[test]
+    fn line_steps_through_neighbors() {
+        let from = HexCoord::new(-2, 1);
+        let to = HexCoord::new(3, -3);
+        let line = from.line_to(&to);
+
+        assert_eq!(line.len(), from.distance(&to) + 1);
+        assert_eq!(line.first(), Some(&from));
+        assert_eq!(line.last(), Some(&to));
+        for step in line.windows(2) {
+            assert_eq!(step[0].distance(&step[1]), 1);
+        }
+        assert_eq!(from.line_to(&from), vec![from]);
+    }
 }
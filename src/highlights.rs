@@ -1,10 +1,10 @@
 use bevy::{app::PluginGroupBuilder, asset::Asset, ecs::schedule::ShouldRun, prelude::*};
 use bevy_mod_picking::{
-    highlight::get_initial_mesh_highlight_asset, Highlighting,
-    InteractablePickingPlugin, PausedForBlockers, PickingPlugin, PickingPluginsState,
-    PickingSystem, Selection,
+    highlight::get_initial_mesh_highlight_asset, Highlighting, InteractablePickingPlugin,
+    PausedForBlockers, PickingPlugin, PickingPluginsState, PickingSystem, Selection,
 };
 
+use crate::board::{highlight_colors, Palette};
 use crate::game::{GameState, Region, SelectedRegion};
 
 // This code is based on bevy_mod_picking. Standard use-case for bevy_mod_picking is limited
@@ -23,14 +23,36 @@ pub struct StackRankDiceDefaultHighlighting<T: StackRankDiceHighlightable + ?Siz
 
 impl<T: StackRankDiceHighlightable> FromWorld for StackRankDiceDefaultHighlighting<T> {
     fn from_world(world: &mut World) -> Self {
-        T::highlight_defaults(T::materials(world))
+        let palette = world.get_resource::<Palette>().copied().unwrap_or_default();
+        T::highlight_defaults(T::materials(world), palette)
+    }
+}
+
+/// Resource that configures dimming of the board while a clash is being resolved.
+/// Only the two clashing regions and their neighbours stay lit.
+#[derive(Resource)]
+pub struct ClashSpotlight {
+    pub enabled: bool,
+    /// Brightness multiplier applied to regions outside of the spotlight
+    pub dim_factor: f32,
+}
+
+impl Default for ClashSpotlight {
+    fn default() -> Self {
+        ClashSpotlight {
+            enabled: false,
+            dim_factor: 0.3,
+        }
     }
 }
 
 /// This trait makes it possible for highlighting to be generic over any type of asset.
 pub trait StackRankDiceHighlightable: Default + Asset {
     /// The asset used to highlight the picked object. For a 3D mesh, this would probably be [`StandardMaterial`].
-    fn highlight_defaults(materials: Mut<Assets<Self>>) -> StackRankDiceDefaultHighlighting<Self>;
+    fn highlight_defaults(
+        materials: Mut<Assets<Self>>,
+        palette: Palette,
+    ) -> StackRankDiceDefaultHighlighting<Self>;
     fn materials(world: &mut World) -> Mut<Assets<Self>> {
         world
             .get_resource_mut::<Assets<Self>>()
@@ -40,28 +62,30 @@ pub trait StackRankDiceHighlightable: Default + Asset {
 impl StackRankDiceHighlightable for StandardMaterial {
     fn highlight_defaults(
         mut materials: Mut<Assets<Self>>,
+        palette: Palette,
     ) -> StackRankDiceDefaultHighlighting<Self> {
+        let colors = highlight_colors(palette);
         StackRankDiceDefaultHighlighting {
             hovered: materials.add(StandardMaterial {
-                base_color: Color::rgb(0.85, 0.0, 0.85),
+                base_color: colors.hovered,
                 metallic: 0.0,
                 reflectance: 0.0,
                 ..default()
             }),
             pressed: materials.add(StandardMaterial {
-                base_color: Color::rgb(0.85, 0.0, 0.85),
+                base_color: colors.hovered,
                 metallic: 0.0,
                 reflectance: 0.0,
                 ..default()
             }),
             selected: materials.add(StandardMaterial {
-                base_color: Color::rgb(0.95, 0.0, 0.85),
+                base_color: colors.selected,
                 metallic: 0.01,
                 reflectance: 0.0,
                 ..default()
             }),
             opponent: materials.add(StandardMaterial {
-                base_color: Color::rgba(0.95, 0.0, 0.0, 0.7),
+                base_color: colors.opponent,
                 metallic: 0.0,
                 reflectance: 0.0,
                 ..default()
@@ -73,22 +97,24 @@ impl StackRankDiceHighlightable for StandardMaterial {
 impl StackRankDiceHighlightable for ColorMaterial {
     fn highlight_defaults(
         mut materials: Mut<Assets<Self>>,
+        palette: Palette,
     ) -> StackRankDiceDefaultHighlighting<Self> {
+        let colors = highlight_colors(palette);
         StackRankDiceDefaultHighlighting {
             hovered: materials.add(ColorMaterial {
-                color: Color::rgb(0.85, 0.0, 0.85),
+                color: colors.hovered,
                 ..default()
             }),
             pressed: materials.add(ColorMaterial {
-                color: Color::rgb(0.85, 0.0, 0.85),
+                color: colors.hovered,
                 ..default()
             }),
             selected: materials.add(ColorMaterial {
-                color: Color::rgb(0.95, 0.0, 0.85),
+                color: colors.selected,
                 ..default()
             }),
             opponent: materials.add(ColorMaterial {
-                color: Color::rgba(0.95, 0.0, 0.0, 0.7),
+                color: colors.opponent,
                 ..default()
             }),
         }
@@ -205,10 +231,10 @@ pub fn mesh_highlighting<T: 'static + StackRankDiceHighlightable + Send + Sync>(
                     &global_default_highlight.selected
                 } else if selected_region.entity.is_some()
                     && region.is_ok()
-                    && region
-                        .unwrap()
-                        .1
-                        .is_opponent(selected_region.region.as_ref().unwrap())
+                    && game_state.board.is_opponent(
+                        region.unwrap().1.id,
+                        selected_region.region.as_ref().unwrap().id,
+                    )
                 {
                     &global_default_highlight.opponent
                 } else if region.is_ok() && region.unwrap().1.owner != game_state.turn_of_player {
@@ -1,9 +1,37 @@
+pub mod ai;
+#[cfg(feature = "render")]
 pub mod app;
+#[cfg(feature = "render")]
+pub mod assets;
+#[cfg(feature = "render")]
+pub mod audio;
+#[cfg(feature = "render")]
 pub mod board;
+#[cfg(feature = "render")]
+pub mod camera;
+pub mod error;
+#[cfg(feature = "render")]
 pub mod events;
 pub mod game;
 pub mod geometry;
 pub mod hex;
+#[cfg(feature = "render")]
 pub mod highlights;
+#[cfg(feature = "render")]
+pub mod minimap;
+pub mod odds;
+#[cfg(feature = "render")]
+pub mod recap;
+pub mod replay;
+#[cfg(feature = "render")]
+pub mod rng_dice;
+pub mod simulate;
+#[cfg(feature = "render")]
+pub mod snapshots;
+#[cfg(feature = "render")]
+pub mod stats;
 pub mod tiered_prng;
+#[cfg(feature = "render")]
+pub mod trace;
+#[cfg(feature = "render")]
 pub mod ui;
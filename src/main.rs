@@ -1,17 +1,27 @@
-mod app;
-mod board;
-mod events;
-mod game;
-mod geometry;
-mod hex;
-mod highlights;
-mod tiered_prng;
-mod ui;
-
-use app::build_app;
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{ArgAction, CommandFactory, Parser};
 use rand::rngs::OsRng;
 use rand::RngCore;
+use stackrankdice::ai::{AiPlayers, AiStrategy, DEFAULT_ROLLOUTS, DEFAULT_SEARCH_DEPTH};
+use stackrankdice::app::{build_app, GameSettings};
+use stackrankdice::board::{CaptureAnimation, FogOfWar, Palette, MAX_PLAYERS};
+use stackrankdice::camera::CameraControls;
+use stackrankdice::error::GameError;
+use stackrankdice::events::{AttackConfirmation, Autosave, DiceRollDuration};
+use stackrankdice::game::{
+    load_replay, BoardConfig, BoardPreview, CaptureBonus, DiceKind, FirstPlayer, GameState,
+    LargestArmyBonus, MinAttackDice, PlayerId, Rules, TieRule, MAX_DICE_SIDES,
+};
+use stackrankdice::minimap::Minimap;
+use stackrankdice::recap::{RecapAutoplay, ReplayExport, ReplayResource};
+use stackrankdice::rng_dice::DiceMode;
+use stackrankdice::tiered_prng::seed_from_phrase;
+use stackrankdice::trace::TraceLog;
+use stackrankdice::ui::{MatchClockDisplay, PlayerNames, RevealStyle};
+
+use std::io;
+use std::path::PathBuf;
+use std::time::Duration;
 
 use bevy::prelude::*;
 
@@ -23,10 +33,217 @@ struct Args {
 
     #[arg(short, long, default_value_t = 0)]
     env_seed: u64,
+
+    /// Memorable map name, e.g. "dragon valley", used to derive both seeds
+    #[arg(short, long)]
+    phrase: Option<String>,
+
+    /// Players controlled by AI, counted from 0 like `--first-player`, e.g. `--ai-players 1`
+    #[arg(long, value_delimiter = ',')]
+    ai_players: Vec<usize>,
+
+    /// How AI players pick their moves
+    #[arg(long, value_enum, default_value_t = AiStrategy::MonteCarlo { rollouts: DEFAULT_ROLLOUTS })]
+    ai_strategy: AiStrategy,
+
+    /// Number of playouts an AI player runs before each move
+    #[arg(long, default_value_t = DEFAULT_ROLLOUTS)]
+    ai_rollouts: usize,
+
+    /// Opponent replies a minimax AI player looks ahead, from 1 to 3
+    #[arg(long, default_value_t = DEFAULT_SEARCH_DEPTH, value_parser = parse_ai_depth)]
+    ai_depth: usize,
+
+    /// Require a second click or Enter before an attack starts, `--confirm-attacks false`
+    /// attacks on the first click
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    confirm_attacks: bool,
+
+    /// Let a region attack again right after capturing its target
+    #[arg(long)]
+    capture_bonus: bool,
+
+    /// Give the owner of the single largest region one more reinforcement dice per turn
+    #[arg(long)]
+    largest_army_bonus: bool,
+
+    /// Who wins a clash when both sides roll the same sum
+    #[arg(long, value_enum, default_value_t = TieRule::DefenderWins)]
+    ties: TieRule,
+
+    /// Least number of dice a region needs to attack
+    #[arg(long, default_value_t = 1)]
+    min_attack_dice: usize,
+
+    /// Reveal the defender's dice sum this many milliseconds after the attacker's
+    #[arg(long)]
+    reveal_delay: Option<u64>,
+
+    /// Seconds dice of a clash stay on the table, Space skips the rest
+    #[arg(long, default_value_t = 3.0, value_parser = parse_roll_seconds)]
+    roll_seconds: f32,
+
+    /// Draw dice values without a physics simulation
+    #[arg(long, alias = "no-physics-dice")]
+    rng_dice: bool,
+
+    /// Draw dice values from a shuffled bag of faces per region, without physics
+    #[arg(long, conflicts_with = "rng_dice")]
+    shuffle_bag_dice: bool,
+
+    /// Faces of the dice clashes are fought with, from 2 to 20. Thrown dice still show six
+    /// faces, their values are drawn again for other dice.
+    #[arg(long, default_value = "6", value_parser = parse_dice_sides)]
+    dice_sides: DiceKind,
+
+    /// Replay the whole match quickly once it's over
+    #[arg(long)]
+    recap: bool,
+
+    /// Hide the turn counter and the match clock
+    #[arg(long)]
+    hide_clock: bool,
+
+    /// Name of a player in a hot seat game, repeated in player order,
+    /// e.g. `--name Alice --name Bob`
+    #[arg(long = "name")]
+    names: Vec<String>,
+
+    /// Number of players, from 2 to 8
+    #[arg(long, default_value_t = 2, value_parser = parse_number_of_players)]
+    players: usize,
+
+    /// Player taking the first turn, counted from 0, or `random` to draw one from the env seed
+    #[arg(long, default_value = "0", value_parser = parse_first_player)]
+    first_player: FirstPlayer,
+
+    /// Reflect half of the board through its center for a symmetric start, needs an even
+    /// number of players
+    #[arg(long)]
+    mirror: bool,
+
+    /// Colors of players and highlighted regions
+    #[arg(long, value_enum, default_value_t = Palette::Default)]
+    palette: Palette,
+
+    /// Only show regions bordering the territory of the player whose turn it is
+    #[arg(long)]
+    fog_of_war: bool,
+
+    /// Animate dice moving onto a captured region and point arrows at targets of AI attacks,
+    /// `--animations false` redraws the board at once
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    animations: bool,
+
+    /// Show an overview of the whole board in a corner of the screen
+    #[arg(long)]
+    minimap: bool,
+
+    /// Keep the camera still, e.g. for deterministic screenshots
+    #[arg(long)]
+    lock_camera: bool,
+
+    /// Write a trace of RNG states and game events to a file, for debugging nondeterminism
+    #[arg(long)]
+    trace: Option<PathBuf>,
+
+    /// Write a replay of the match to a `.srd` file once it's won
+    #[arg(long)]
+    save_replay: Option<PathBuf>,
+
+    /// Watch the match of a `.srd` replay file instead of playing, stepping through it with
+    /// the right arrow key. Seeds, players and rules come from the file.
+    #[arg(long)]
+    replay: Option<PathBuf>,
+
+    /// Save the game to a file whenever a turn ends
+    #[arg(long)]
+    autosave: Option<PathBuf>,
+
+    /// Go on with a game saved by `--autosave`, by the rules it was saved with
+    #[arg(long, conflicts_with = "replay")]
+    load: Option<PathBuf>,
+
+    /// Show candidate boards in the terminal and pick one before the game starts
+    #[arg(long, conflicts_with_all = ["replay", "load"])]
+    preview_board: bool,
+}
+
+fn parse_number_of_players(s: &str) -> Result<usize, String> {
+    let number_of_players: usize = s
+        .parse()
+        .map_err(|_| format!("`{}` isn't a number of players", s))?;
+
+    match number_of_players {
+        2..=MAX_PLAYERS => Ok(number_of_players),
+        _ => Err(format!("a game needs from 2 to {} players", MAX_PLAYERS)),
+    }
+}
+
+fn parse_ai_depth(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(depth) if (1..=3).contains(&depth) => Ok(depth),
+        _ => Err(format!("`{}` isn't a search depth from 1 to 3", s)),
+    }
+}
+
+fn parse_dice_sides(s: &str) -> Result<DiceKind, String> {
+    s.parse::<u32>()
+        .ok()
+        .and_then(|sides| DiceKind::new(sides).ok())
+        .ok_or_else(|| {
+            format!(
+                "`{}` isn't a number of sides from 2 to {}",
+                s, MAX_DICE_SIDES
+            )
+        })
+}
+
+fn parse_first_player(s: &str) -> Result<FirstPlayer, String> {
+    match s {
+        "random" => Ok(FirstPlayer::Random),
+        _ => s
+            .parse()
+            .map(|player| FirstPlayer::Player(PlayerId(player)))
+            .map_err(|_| format!("`{}` isn't a player or `random`", s)),
+    }
+}
+
+fn parse_roll_seconds(s: &str) -> Result<f32, String> {
+    match s.parse::<f32>() {
+        Ok(seconds) if seconds.is_finite() && seconds >= 0.0 => Ok(seconds),
+        _ => Err(format!("`{}` isn't a number of seconds", s)),
+    }
+}
+
+/// Print candidate boards until the player settles on one, and return its world seed
+fn pick_board(
+    world_seed: u64,
+    number_of_players: usize,
+    config: &BoardConfig,
+) -> Result<u64, GameError> {
+    let mut preview = BoardPreview::new(world_seed, number_of_players, config)?;
+    loop {
+        println!("{}", preview.board.to_ascii());
+        println!("Press Enter for another board, or type `play` to start on this one");
+
+        let mut answer = String::new();
+        match io::stdin().read_line(&mut answer) {
+            Ok(0) | Err(_) => break,
+            Ok(_) if answer.trim() == "play" => break,
+            Ok(_) => preview.reroll()?,
+        }
+    }
+    Ok(preview.commit())
 }
 
 fn main() {
     let mut args = Args::parse();
+    if let Some(phrase) = args.phrase.as_ref() {
+        args.world_seed = seed_from_phrase(phrase);
+        args.env_seed = seed_from_phrase(&format!("{} env", phrase));
+    }
+
     if args.world_seed == 0 || args.env_seed == 0 {
         let mut key = [0u8; 16];
         OsRng.fill_bytes(&mut key);
@@ -42,7 +259,173 @@ fn main() {
         }
     }
 
+    if let FirstPlayer::Player(player) = args.first_player {
+        if player.0 >= args.players {
+            Args::command()
+                .error(
+                    ErrorKind::ValueValidation,
+                    format!("--first-player must be less than {}", args.players),
+                )
+                .exit();
+        }
+    }
+
+    if let Some(player) = args.ai_players.iter().find(|p| **p >= args.players) {
+        Args::command()
+            .error(
+                ErrorKind::ValueValidation,
+                format!("--ai-players {} must be less than {}", player, args.players),
+            )
+            .exit();
+    }
+
+    if args.mirror && !args.players.is_multiple_of(2) {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--mirror needs an even number of --players",
+            )
+            .exit();
+    }
+
     let app = &mut App::new();
-    build_app(app, args.world_seed, args.env_seed, 2, false);
+
+    let mut ai_players = AiPlayers::default();
+    let ai_strategy = match args.ai_strategy {
+        AiStrategy::MonteCarlo { .. } => AiStrategy::MonteCarlo {
+            rollouts: args.ai_rollouts,
+        },
+        AiStrategy::Minimax { .. } => AiStrategy::Minimax {
+            depth: args.ai_depth,
+        },
+        strategy => strategy,
+    };
+    for player in &args.ai_players {
+        ai_players.add_bot(PlayerId(*player), Box::new(ai_strategy.clone()));
+    }
+    app.insert_resource(AttackConfirmation {
+        enabled: args.confirm_attacks,
+        armed: None,
+    });
+    app.insert_resource(RecapAutoplay {
+        enabled: args.recap,
+        ..default()
+    });
+    app.insert_resource(DiceRollDuration(Duration::from_secs_f32(args.roll_seconds)));
+    app.insert_resource(Rules {
+        min_attack_dice: MinAttackDice(args.min_attack_dice),
+        capture_bonus: if args.capture_bonus {
+            CaptureBonus::ExtraAction
+        } else {
+            CaptureBonus::Disabled
+        },
+        tie_rule: args.ties,
+        largest_army_bonus: LargestArmyBonus(args.largest_army_bonus),
+        ..default()
+    });
+    app.insert_resource(args.palette);
+    app.insert_resource(FogOfWar(args.fog_of_war));
+    app.insert_resource(Minimap(args.minimap));
+    app.insert_resource(PlayerNames(args.names.clone()));
+    app.insert_resource(CaptureAnimation {
+        enabled: args.animations,
+        ..default()
+    });
+    app.insert_resource(CameraControls {
+        enabled: !args.lock_camera,
+    });
+    app.insert_resource(MatchClockDisplay {
+        enabled: !args.hide_clock,
+    });
+    let board_config = BoardConfig {
+        mirror: args.mirror,
+        ..default()
+    };
+    if args.preview_board {
+        args.world_seed =
+            pick_board(args.world_seed, args.players, &board_config).unwrap_or_else(|err| {
+                Args::command()
+                    .error(
+                        ErrorKind::ValueValidation,
+                        format!("Can't preview boards: {}", err),
+                    )
+                    .exit()
+            });
+    }
+    let dice_mode = if args.rng_dice {
+        DiceMode::RngOnly
+    } else if args.shuffle_bag_dice {
+        DiceMode::ShuffleBag
+    } else {
+        DiceMode::default()
+    };
+    if let Some(path) = args.trace {
+        match TraceLog::to_file(&path) {
+            Ok(trace_log) => {
+                app.insert_resource(trace_log);
+            }
+            Err(err) => Args::command()
+                .error(
+                    ErrorKind::Io,
+                    format!("Can't write trace to {}: {}", path.display(), err),
+                )
+                .exit(),
+        }
+    }
+    if let Some(delay) = args.reveal_delay {
+        app.insert_resource(RevealStyle::Sequential {
+            delay: Duration::from_millis(delay),
+        });
+    }
+    app.insert_resource(ReplayExport(args.save_replay));
+    app.insert_resource(Autosave(args.autosave));
+    let mut saved_game = None;
+    if let Some(path) = args.load {
+        let saved = GameState::load_from_path(&path).unwrap_or_else(|err| {
+            Args::command()
+                .error(
+                    ErrorKind::Io,
+                    format!("Can't load game from {}: {}", path.display(), err),
+                )
+                .exit()
+        });
+        args.players = saved.game_state.number_of_players;
+        saved_game = Some(saved);
+    }
+    let mut settings = GameSettings {
+        ai_players,
+        board_config,
+        dice_mode,
+        first_player: args.first_player,
+        dice_kind: args.dice_sides,
+        ..GameSettings::new(args.world_seed, args.env_seed, args.players)
+    };
+    // Set the game up the way the replayed one was
+    if let Some(path) = args.replay {
+        let replay_data = load_replay(&path).unwrap_or_else(|err| {
+            Args::command()
+                .error(
+                    ErrorKind::Io,
+                    format!("Can't read replay from {}: {}", path.display(), err),
+                )
+                .exit()
+        });
+        settings.world_seed = replay_data.world_seed;
+        settings.env_seed = replay_data.env_seed;
+        settings.number_of_players = replay_data.number_of_players;
+        settings.first_player = FirstPlayer::Player(replay_data.first_player);
+        settings.dice_kind = replay_data.dice_kind;
+        settings.board_config = replay_data.board_config;
+        app.insert_resource(replay_data.rules);
+        app.insert_resource(ReplayResource::new(replay_data.game_log));
+    }
+
+    build_app(app, settings, Vec::new());
+    // The saved game and its rules take the place of the ones set up from the arguments
+    if let Some(saved) = saved_game {
+        app.insert_resource(saved.game_state);
+        app.insert_resource(saved.rules);
+        app.insert_resource(saved.reinforcement_reserve);
+    }
     app.run();
 }
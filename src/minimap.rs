@@ -0,0 +1,168 @@
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    prelude::*,
+    render::{
+        camera::{RenderTarget, ScalingMode},
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        view::RenderLayers,
+    },
+};
+
+use crate::ai::AiPlayers;
+use crate::board::{region_color, FogOfWar, Palette, RegionMeshCache};
+use crate::game::{Board, GameState, RegionId};
+use crate::geometry::center;
+use crate::hex::HexCoord;
+use crate::ui::StackRankDiceUI;
+
+/// Side of the minimap texture in pixels
+const MINIMAP_SIZE: u32 = 200;
+/// Render layer of minimap tiles, so only the minimap camera sees them
+const MINIMAP_LAYER: u8 = 1;
+
+/// Resource that shows an overview of the whole board in a corner of the screen
+#[derive(Resource, Default)]
+pub struct Minimap(pub bool);
+
+/// Flat copy of a region drawn by the minimap camera
+#[derive(Component)]
+pub(crate) struct MinimapTile(pub RegionId);
+
+/// Distance from the center of the board to the outer edge of its farthest hex
+pub fn board_radius(board: &Board) -> f32 {
+    board
+        .hexes
+        .keys()
+        .map(|(q, r)| {
+            let [x, _, z] = center(1.0, &HexCoord::new(*q, *r), &[0.0, 0.0, 0.0]);
+            Vec2::new(x, z).length() + 1.0
+        })
+        .fold(1.0, f32::max)
+}
+
+pub(crate) fn setup_minimap(
+    mut commands: Commands,
+    minimap: Res<Minimap>,
+    game_state: Res<GameState>,
+    (fog_of_war, ai_players, palette): (Res<FogOfWar>, Res<AiPlayers>, Res<Palette>),
+    mut images: ResMut<Assets<Image>>,
+    (mut meshes, mut mesh_cache): (ResMut<Assets<Mesh>>, ResMut<RegionMeshCache>),
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !minimap.0 {
+        return;
+    }
+
+    let size = Extent3d {
+        width: MINIMAP_SIZE,
+        height: MINIMAP_SIZE,
+        ..default()
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+        },
+        ..default()
+    };
+    image.resize(size);
+    let image = images.add(image);
+
+    // Top-down camera, turned so the side facing the board camera is at the bottom
+    commands
+        .spawn(Camera3dBundle {
+            camera_3d: Camera3d {
+                clear_color: ClearColorConfig::Custom(Color::rgba(0.0, 0.0, 0.0, 0.6)),
+                ..default()
+            },
+            camera: Camera {
+                priority: -1,
+                target: RenderTarget::Image(image.clone()),
+                ..default()
+            },
+            projection: OrthographicProjection {
+                scaling_mode: ScalingMode::FixedVertical(2.0 * board_radius(&game_state.board)),
+                ..default()
+            }
+            .into(),
+            transform: Transform::from_xyz(0.0, 50.0, 0.0).looking_at(Vec3::ZERO, Vec3::NEG_X),
+            ..default()
+        })
+        .insert(UiCameraConfig { show_ui: false })
+        .insert(RenderLayers::layer(MINIMAP_LAYER))
+        .insert(Name::new("Minimap Camera"))
+        .insert(StackRankDiceUI);
+
+    let visible_regions = fog_of_war.visible_regions(&game_state, &ai_players);
+    for region in game_state.board.regions.iter() {
+        let [x, y, z] = center(1.0, &region.center_hex(), &[0.0, 0.0, 0.0]);
+        let color = region_color(&game_state, region, visible_regions.as_deref(), *palette);
+
+        commands
+            .spawn(PbrBundle {
+                mesh: mesh_cache.region_mesh(region, &mut meshes),
+                material: materials.add(StandardMaterial {
+                    base_color: color,
+                    unlit: true,
+                    ..default()
+                }),
+                transform: Transform::from_xyz(x, y, z),
+                ..default()
+            })
+            .insert(RenderLayers::layer(MINIMAP_LAYER))
+            .insert(MinimapTile(region.id))
+            .insert(StackRankDiceUI);
+    }
+
+    commands
+        .spawn(ImageBundle {
+            image: image.into(),
+            style: Style {
+                size: Size::new(Val::Px(MINIMAP_SIZE as f32), Val::Px(MINIMAP_SIZE as f32)),
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(80.0),
+                    right: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            },
+            ..default()
+        })
+        .insert(Name::new("Minimap"))
+        .insert(StackRankDiceUI);
+}
+
+/// Recolor minimap tiles after regions change hands or the turn passes under fog of war
+pub(crate) fn minimap_update(
+    game_state: Res<GameState>,
+    fog_of_war: Res<FogOfWar>,
+    ai_players: Res<AiPlayers>,
+    palette: Res<Palette>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    tile_query: Query<(&MinimapTile, &Handle<StandardMaterial>)>,
+) {
+    if !game_state.is_changed() {
+        return;
+    }
+
+    let visible_regions = fog_of_war.visible_regions(&game_state, &ai_players);
+    for (tile, material) in tile_query.iter() {
+        if let (Some(region), Some(material)) = (
+            game_state.board.region(tile.0).ok(),
+            materials.get_mut(material),
+        ) {
+            material.base_color =
+                region_color(&game_state, region, visible_regions.as_deref(), *palette);
+        }
+    }
+}
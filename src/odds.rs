@@ -0,0 +1,42 @@
+use crate::game::DiceKind;
+
+/// Probability that the sum of `attacker_dice` six-sided dice beats the sum of
+/// `defender_dice` ones. Ties go to the defender.
+pub fn clash_win_probability(attacker_dice: usize, defender_dice: usize) -> f64 {
+    clash_win_probability_with_bonus(attacker_dice, defender_dice, 0)
+}
+
+/// Same as [`clash_win_probability`], with `defence_bonus` added to the defender's sum
+pub fn clash_win_probability_with_bonus(
+    attacker_dice: usize,
+    defender_dice: usize,
+    defence_bonus: usize,
+) -> f64 {
+    clash_win_probability_with_dice(
+        DiceKind::default(),
+        attacker_dice,
+        defender_dice,
+        defence_bonus,
+    )
+}
+
+/// Same as [`clash_win_probability_with_bonus`], with both sides rolling `dice_kind`
+pub fn clash_win_probability_with_dice(
+    dice_kind: DiceKind,
+    attacker_dice: usize,
+    defender_dice: usize,
+    defence_bonus: usize,
+) -> f64 {
+    let attack = dice_kind.sum_distribution(attacker_dice);
+    let defence = dice_kind.sum_distribution(defender_dice);
+
+    let mut p = 0.0;
+    for (i, p_attack) in attack.iter().enumerate() {
+        for (j, p_defence) in defence.iter().enumerate() {
+            if attacker_dice + i > defender_dice + defence_bonus + j {
+                p += p_attack * p_defence;
+            }
+        }
+    }
+    p
+}
@@ -0,0 +1,308 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::board::{CaptureAnimation, Palette, RedrawBoard, StackRankDiceGameBoardElement};
+use crate::events::{spawn_game_over_screen, EventGameOver};
+use crate::game::{
+    Board, BoardConfig, GameAction, GameLogEntry, GameState, PlayerId, PlayerRegions, Region, Rules,
+};
+use crate::stats::SessionStats;
+use crate::tiered_prng::PrngResource;
+use crate::ui::{PlayerNames, StackRankDiceUI};
+
+/// Resource that enables a fast replay of the whole match before the game-over screen
+#[derive(Resource)]
+pub struct RecapAutoplay {
+    pub enabled: bool,
+    /// Time each turn stays on screen
+    pub step: Duration,
+}
+
+impl Default for RecapAutoplay {
+    fn default() -> Self {
+        RecapAutoplay {
+            enabled: false,
+            step: Duration::from_millis(400),
+        }
+    }
+}
+
+/// Board as it was at the end of a logged turn
+#[derive(Clone)]
+pub struct RecapFrame {
+    pub turn_counter: usize,
+    pub board: Board,
+}
+
+/// Replay a finished game and keep the board at the end of every logged turn
+pub fn recap_frames(game_state: &GameState) -> Vec<RecapFrame> {
+    let mut replayed = start_of_game(game_state);
+    let game_log = &game_state.game_log;
+
+    let mut frames = Vec::new();
+    for (i, gl) in game_log.iter().enumerate() {
+        replay_step(&mut replayed, gl);
+
+        let is_last_of_turn = game_log
+            .get(i + 1)
+            .is_none_or(|next| next.turn_counter != gl.turn_counter);
+        if is_last_of_turn {
+            frames.push(RecapFrame {
+                turn_counter: gl.turn_counter,
+                board: replayed.board.clone(),
+            });
+        }
+    }
+
+    frames
+}
+
+/// Game as it was before its first logged action, ready to have its log replayed with
+/// [`replay_step`]
+pub fn start_of_game(game_state: &GameState) -> GameState {
+    let board = initial_board(&game_state.game_log, &game_state.board);
+    GameState {
+        player_regions: PlayerRegions::from_board(&board),
+        board,
+        turn_of_player: game_state.first_player,
+        turn_counter: 0,
+        game_log: Vec::new(),
+        ..game_state.clone()
+    }
+}
+
+/// Board as it was before the first logged action. Every change to a region is logged, so
+/// regions never involved in an action are taken from the final board as they are.
+pub fn initial_board(game_log: &[GameLogEntry], final_board: &Board) -> Board {
+    let mut board = final_board.clone();
+    board.heat.clear();
+
+    for gl in game_log.iter().rev() {
+        for region in [&gl.region_1, &gl.region_2]
+            .into_iter()
+            .chain(gl.shelter.as_ref())
+        {
+            restore_region(&mut board.regions[region.id], region);
+        }
+    }
+
+    board
+}
+
+/// Apply one logged action to a game, leaving its regions and shelter as the action left them
+pub fn replay_step(game_state: &mut GameState, entry: &GameLogEntry) {
+    for _ in game_state.turn_counter..entry.turn_counter {
+        game_state.board.cool_down();
+    }
+
+    let mut region_1 = entry.region_1.clone();
+    let mut region_2 = entry.region_2.clone();
+    region_1.num_dice = entry.region_1_dice_after;
+    region_2.num_dice = entry.region_2_dice_after;
+    let mut shelter = entry.shelter.clone();
+
+    match entry.action {
+        GameAction::Clash => {
+            let (winner, loser) = match entry.attacker_won {
+                true => (&region_1, &mut region_2),
+                false => (&region_2, &mut region_1),
+            };
+            loser.owner = winner.owner;
+            loser.fortify = 0;
+            loser.capture_count += 1;
+            game_state.board.heat_up(loser.id);
+
+            if let Some(shelter) = shelter.as_mut() {
+                shelter.num_dice = entry.shelter_dice_after;
+            }
+        }
+        GameAction::Retreat => {}
+        GameAction::Fortify => {
+            region_1.fortify += 1;
+            region_2 = region_1.clone();
+        }
+        GameAction::Reinforce => {
+            region_2 = region_1.clone();
+        }
+    }
+
+    for region in [region_1, region_2].iter().chain(shelter.as_ref()) {
+        let owner = game_state.board.regions[region.id].owner;
+        if owner != region.owner {
+            game_state
+                .player_regions
+                .transfer(region.id, owner, region.owner);
+        }
+        restore_region(&mut game_state.board.regions[region.id], region);
+    }
+
+    game_state.turn_of_player = entry.turn_of_player;
+    game_state.turn_counter = entry.turn_counter;
+    game_state.game_log.push(entry.clone());
+}
+
+/// Give a region of the board the owner, dice, fortify level and captures of a logged one.
+/// Hexes stay, as logs decoded from a link don't carry them.
+fn restore_region(region: &mut Region, logged: &Region) {
+    region.owner = logged.owner;
+    region.num_dice = logged.num_dice;
+    region.fortify = logged.fortify;
+    region.capture_count = logged.capture_count;
+}
+
+/// Resource of a finished match replayed one logged action per key press
+#[derive(Resource)]
+pub struct ReplayResource {
+    /// Index of the next log entry to replay
+    pub index: usize,
+    game_log: Vec<GameLogEntry>,
+}
+
+impl ReplayResource {
+    /// Replay of a log from its first entry, on a game set up the way the logged one was
+    pub fn new(game_log: Vec<GameLogEntry>) -> Self {
+        ReplayResource { index: 0, game_log }
+    }
+}
+
+/// Resource with the `.srd` file the replay of a match is written to once it's over
+#[derive(Resource, Default)]
+pub struct ReplayExport(pub Option<PathBuf>);
+
+/// Write the replay of a won match to the file of [`ReplayExport`], if there's one
+pub(crate) fn export_replay_on_game_over(
+    mut event_game_over_reader: EventReader<EventGameOver>,
+    replay_export: Res<ReplayExport>,
+    game_state: Res<GameState>,
+    prng: Res<PrngResource>,
+    (rules, board_config): (Res<Rules>, Res<BoardConfig>),
+) {
+    let path = match &replay_export.0 {
+        Some(path) => path,
+        None => return,
+    };
+
+    for _ in event_game_over_reader.iter() {
+        let exported =
+            game_state.export_replay(prng.world_seed, prng.env_seed, &rules, &board_config, path);
+        match exported {
+            Ok(()) => info!("Replay written to {}", path.display()),
+            Err(err) => error!("Can't write replay to {}: {}", path.display(), err),
+        }
+    }
+}
+
+/// Start a replay with R once the match and its recap are over, then step through it
+/// with the right arrow key
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn replay_input(
+    keys: Res<Input<KeyCode>>,
+    mut commands: Commands,
+    replay: Option<ResMut<ReplayResource>>,
+    recap: Option<Res<Recap>>,
+    game_ui_elements_query: Query<Entity, With<StackRankDiceUI>>,
+    mut game_state: ResMut<GameState>,
+    mut capture_animation: ResMut<CaptureAnimation>,
+    mut redraw_board_writer: EventWriter<RedrawBoard>,
+) {
+    match replay {
+        None => {
+            let is_game_over = game_state.is_game_over().is_some();
+            if !is_game_over || recap.is_some() || !keys.just_pressed(KeyCode::R) {
+                return;
+            }
+
+            let game_log = game_state.game_log.clone();
+            *game_state = start_of_game(&game_state);
+
+            // Game-over screen
+            for e in game_ui_elements_query.iter() {
+                commands.entity(e).despawn_recursive();
+            }
+
+            commands.insert_resource(ReplayResource::new(game_log));
+        }
+        Some(mut replay) => {
+            if !keys.just_pressed(KeyCode::Right) {
+                return;
+            }
+
+            let entry = match replay.game_log.get(replay.index) {
+                Some(entry) => entry.clone(),
+                None => return,
+            };
+            replay_step(&mut game_state, &entry);
+            replay.index += 1;
+        }
+    }
+
+    capture_animation.transfer = None;
+    redraw_board_writer.send(RedrawBoard);
+}
+
+/// Recap being played on the game-over screen
+#[derive(Resource)]
+pub(crate) struct Recap {
+    frames: Vec<RecapFrame>,
+    next_frame: usize,
+    winner: PlayerId,
+    timer: Timer,
+}
+
+impl Recap {
+    pub fn new(frames: Vec<RecapFrame>, winner: PlayerId, step: Duration) -> Self {
+        Recap {
+            frames,
+            next_frame: 0,
+            winner,
+            timer: Timer::new(step, TimerMode::Repeating),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn recap_step(
+    mut commands: Commands,
+    recap: Option<ResMut<Recap>>,
+    time: Res<Time>,
+    game_elements_query: Query<Entity, With<StackRankDiceGameBoardElement>>,
+    asset_server: Res<AssetServer>,
+    mut game_state: ResMut<GameState>,
+    mut capture_animation: ResMut<CaptureAnimation>,
+    palette: Res<Palette>,
+    (session_stats, player_names): (Res<SessionStats>, Res<PlayerNames>),
+    mut redraw_board_writer: EventWriter<RedrawBoard>,
+) {
+    let mut recap = match recap {
+        Some(recap) => recap,
+        None => return,
+    };
+
+    if !recap.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    if recap.next_frame == recap.frames.len() {
+        for e in game_elements_query.iter() {
+            commands.entity(e).despawn_recursive();
+        }
+        commands.remove_resource::<Recap>();
+        spawn_game_over_screen(
+            &mut commands,
+            &asset_server,
+            recap.winner,
+            &session_stats,
+            &game_state.players,
+            *palette,
+            &player_names,
+        );
+        return;
+    }
+
+    game_state.board = recap.frames[recap.next_frame].board.clone();
+    recap.next_frame += 1;
+    capture_animation.transfer = None;
+    redraw_board_writer.send(RedrawBoard);
+}
@@ -0,0 +1,235 @@
+use crate::error::GameError;
+use crate::game::{GameAction, GameLogEntry, GameState, PlayerId, Region, RegionId};
+
+/// Version of the replay encoding, bumped whenever the layout changes
+const REPLAY_FORMAT_VERSION: u64 = 2;
+
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+const ACTIONS: [GameAction; 4] = [
+    GameAction::Clash,
+    GameAction::Retreat,
+    GameAction::Fortify,
+    GameAction::Reinforce,
+];
+
+/// Everything needed to replay a game: seeds to regenerate the board and its log, which is
+/// stepped through with [`crate::recap::replay_step`]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Replay {
+    pub world_seed: u64,
+    pub env_seed: u64,
+    pub number_of_players: usize,
+    pub game_log: Vec<GameLogEntry>,
+}
+
+impl Replay {
+    pub fn new(world_seed: u64, env_seed: u64, game_state: &GameState) -> Self {
+        Replay {
+            world_seed,
+            env_seed,
+            number_of_players: game_state.number_of_players,
+            game_log: game_state.game_log.clone(),
+        }
+    }
+}
+
+/// Encode a replay into a URL-safe (base64url) string to be used as a link fragment.
+/// Hexes of logged regions are left out, they come from the regenerated board.
+pub fn replay_to_url_fragment(replay: &Replay) -> String {
+    let mut bytes = Vec::new();
+    write_varint(&mut bytes, REPLAY_FORMAT_VERSION);
+    write_varint(&mut bytes, replay.world_seed);
+    write_varint(&mut bytes, replay.env_seed);
+    write_varint(&mut bytes, replay.number_of_players as u64);
+    write_varint(&mut bytes, replay.game_log.len() as u64);
+
+    for gl in replay.game_log.iter() {
+        let action = ACTIONS.iter().position(|a| *a == gl.action).unwrap();
+        write_varint(&mut bytes, action as u64);
+        write_varint(&mut bytes, gl.turn_counter as u64);
+        write_varint(&mut bytes, gl.turn_of_player.0 as u64);
+        write_region(&mut bytes, &gl.region_1);
+        write_region(&mut bytes, &gl.region_2);
+        for dice_result in [&gl.region_1_dice_result, &gl.region_2_dice_result] {
+            write_varint(&mut bytes, dice_result.len() as u64);
+            for value in dice_result.iter() {
+                write_varint(&mut bytes, *value as u64);
+            }
+        }
+        write_varint(&mut bytes, gl.region_1_dice_after as u64);
+        write_varint(&mut bytes, gl.region_2_dice_after as u64);
+        write_varint(&mut bytes, gl.attacker_won as u64);
+        write_varint(&mut bytes, gl.is_tie as u64);
+        match &gl.shelter {
+            Some(shelter) => {
+                write_varint(&mut bytes, 1);
+                write_region(&mut bytes, shelter);
+                write_varint(&mut bytes, gl.shelter_dice_after as u64);
+            }
+            None => write_varint(&mut bytes, 0),
+        }
+    }
+
+    base64url_encode(&bytes)
+}
+
+/// Decode a replay from a link fragment. A leading `#` is ignored.
+pub fn replay_from_url_fragment(fragment: &str) -> Result<Replay, GameError> {
+    let bytes = base64url_decode(fragment.trim_start_matches('#'))?;
+    let mut reader = bytes.iter();
+
+    if read_varint(&mut reader)? != REPLAY_FORMAT_VERSION {
+        return Err(GameError::InvalidReplay("unsupported replay version"));
+    }
+
+    let world_seed = read_varint(&mut reader)?;
+    let env_seed = read_varint(&mut reader)?;
+    let number_of_players = read_varint(&mut reader)? as usize;
+    let number_of_entries = read_varint(&mut reader)?;
+
+    let mut game_log = Vec::new();
+    for _ in 0..number_of_entries {
+        let action = *ACTIONS
+            .get(read_varint(&mut reader)? as usize)
+            .ok_or(GameError::InvalidReplay("unknown action"))?;
+        let turn_counter = read_varint(&mut reader)? as usize;
+        let turn_of_player = PlayerId(read_varint(&mut reader)? as usize);
+        let region_1 = read_region(&mut reader)?;
+        let region_2 = read_region(&mut reader)?;
+        let mut gl = GameLogEntry::new(action, turn_counter, turn_of_player, region_1, region_2);
+
+        gl.region_1_dice_result = read_dice_result(&mut reader)?;
+        gl.region_2_dice_result = read_dice_result(&mut reader)?;
+        gl.region_1_dice_after = read_varint(&mut reader)? as usize;
+        gl.region_2_dice_after = read_varint(&mut reader)? as usize;
+        gl.attacker_won = read_flag(&mut reader)?;
+        gl.is_tie = read_flag(&mut reader)?;
+        if read_flag(&mut reader)? {
+            gl.shelter = Some(read_region(&mut reader)?);
+            gl.shelter_dice_after = read_varint(&mut reader)? as usize;
+        }
+
+        game_log.push(gl);
+    }
+
+    if reader.next().is_some() {
+        return Err(GameError::InvalidReplay("trailing data"));
+    }
+
+    Ok(Replay {
+        world_seed,
+        env_seed,
+        number_of_players,
+        game_log,
+    })
+}
+
+/// Write a logged region without its hexes
+fn write_region(bytes: &mut Vec<u8>, region: &Region) {
+    write_varint(bytes, region.id.0 as u64);
+    write_varint(bytes, region.owner.0 as u64);
+    write_varint(bytes, region.num_dice as u64);
+    write_varint(bytes, region.fortify as u64);
+    write_varint(bytes, region.capture_count as u64);
+}
+
+fn read_region(reader: &mut std::slice::Iter<u8>) -> Result<Region, GameError> {
+    let id = RegionId(read_varint(reader)? as usize);
+    let owner = PlayerId(read_varint(reader)? as usize);
+    let num_dice = read_varint(reader)? as usize;
+    let fortify = u8::try_from(read_varint(reader)?)
+        .map_err(|_| GameError::InvalidReplay("fortify level is too high"))?;
+
+    Ok(Region {
+        fortify,
+        capture_count: read_varint(reader)? as usize,
+        ..Region::new(id, owner, num_dice, Vec::new())
+    })
+}
+
+fn read_flag(reader: &mut std::slice::Iter<u8>) -> Result<bool, GameError> {
+    match read_varint(reader)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(GameError::InvalidReplay("invalid flag")),
+    }
+}
+
+fn read_dice_result(reader: &mut std::slice::Iter<u8>) -> Result<Vec<usize>, GameError> {
+    let len = read_varint(reader)?;
+    (0..len)
+        .map(|_| read_varint(reader).map(|value| value as usize))
+        .collect()
+}
+
+/// Write an unsigned LEB128 integer
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes.push(byte);
+            return;
+        }
+        bytes.push(byte | 0x80);
+    }
+}
+
+/// Read an unsigned LEB128 integer
+fn read_varint(reader: &mut std::slice::Iter<u8>) -> Result<u64, GameError> {
+    let mut value: u64 = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = reader
+            .next()
+            .ok_or(GameError::InvalidReplay("unexpected end of data"))?;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(GameError::InvalidReplay("integer is too long"))
+}
+
+/// Base64url encoding without padding
+fn base64url_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+
+        for i in 0..=chunk.len() {
+            encoded.push(BASE64URL_ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    encoded
+}
+
+fn base64url_decode(encoded: &str) -> Result<Vec<u8>, GameError> {
+    let mut bytes = Vec::new();
+    for chunk in encoded.as_bytes().chunks(4) {
+        if chunk.len() == 1 {
+            return Err(GameError::InvalidReplay("truncated base64url"));
+        }
+
+        let mut n: u32 = 0;
+        for (i, c) in chunk.iter().enumerate() {
+            let sextet = BASE64URL_ALPHABET
+                .iter()
+                .position(|a| a == c)
+                .ok_or(GameError::InvalidReplay("invalid base64url character"))?;
+            n |= (sextet as u32) << (18 - 6 * i);
+        }
+
+        for i in 0..chunk.len() - 1 {
+            bytes.push((n >> (16 - 8 * i)) as u8);
+        }
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_dice::{DiceRollResult, DiceRollStartEvent};
+use rand::Rng;
+
+use crate::game::{DiceBag, DiceKind, GameState, RegionId};
+use crate::tiered_prng::PrngDiceResource;
+use crate::ui::{ClashOddsText, DiceRollUI};
+
+/// How long numbers tick in the overlay before settling on the drawn sums
+const TICK_DURATION: Duration = Duration::from_millis(1200);
+
+/// Resource that selects how dice of a clash are rolled
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DiceMode {
+    /// Dice are thrown by a physics simulation
+    #[default]
+    Physics,
+    /// Dice values are drawn from a PRNG and shown as ticking numbers in the overlay.
+    /// Physics isn't loaded in this mode.
+    RngOnly,
+    /// Like [`DiceMode::RngOnly`], but each region draws its dice from its own
+    /// [`DiceBag`], which makes streaks of high or low rolls less likely
+    ShuffleBag,
+}
+
+/// Resource with the dice bag of each region used by [`DiceMode::ShuffleBag`]
+#[derive(Resource, Default)]
+pub struct ShuffleBags {
+    bags: HashMap<RegionId, DiceBag>,
+}
+
+impl ShuffleBags {
+    /// Values of `num_dice` dice drawn from the bag of a region
+    pub fn roll(
+        &mut self,
+        region_id: RegionId,
+        num_dice: usize,
+        dice_kind: DiceKind,
+        rng: &mut impl Rng,
+    ) -> Vec<usize> {
+        let bag = self
+            .bags
+            .entry(region_id)
+            .or_insert_with(|| DiceBag::new(dice_kind));
+        (0..num_dice).map(|_| bag.draw(rng)).collect()
+    }
+}
+
+/// Dice roll in progress, resolved once the timer finishes
+#[derive(Component)]
+pub(crate) struct RngDiceRoll {
+    values: Vec<Vec<usize>>,
+    timer: Timer,
+}
+
+/// Draw dice values as soon as a roll starts
+pub(crate) fn rng_dice_roll_start(
+    mut commands: Commands,
+    mut dice_roll_start_reader: EventReader<DiceRollStartEvent>,
+    mut dice_prng: ResMut<PrngDiceResource>,
+    dice_mode: Res<DiceMode>,
+    game_state: Res<GameState>,
+    mut shuffle_bags: ResMut<ShuffleBags>,
+) {
+    for event in dice_roll_start_reader.iter() {
+        // The clash being rolled for is the last one logged
+        let region_ids = game_state
+            .game_log
+            .last()
+            .map(|gl| [gl.region_1.id, gl.region_2.id]);
+
+        let values = match (*dice_mode, region_ids) {
+            (DiceMode::ShuffleBag, Some(region_ids)) => region_ids
+                .iter()
+                .zip(event.num_dice.iter())
+                .map(|(region_id, num_dice)| {
+                    shuffle_bags.roll(
+                        *region_id,
+                        *num_dice,
+                        game_state.dice_kind,
+                        &mut dice_prng.rng,
+                    )
+                })
+                .collect(),
+            _ => event
+                .num_dice
+                .iter()
+                .map(|num_dice| game_state.dice_kind.roll(*num_dice, &mut dice_prng.rng))
+                .collect(),
+        };
+
+        commands.spawn(RngDiceRoll {
+            values,
+            timer: Timer::new(TICK_DURATION, TimerMode::Once),
+        });
+    }
+}
+
+/// Show random sums while a roll is in progress and fire its result once it settles
+pub(crate) fn rng_dice_roll_tick(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut roll_query: Query<(Entity, &mut RngDiceRoll)>,
+    mut text_query: Query<&mut Text, (With<DiceRollUI>, Without<ClashOddsText>)>,
+    mut dice_roll_result_writer: EventWriter<DiceRollResult>,
+) {
+    // Only used for the animation, the outcome is already drawn
+    let mut rng = rand::thread_rng();
+
+    for (entity, mut roll) in roll_query.iter_mut() {
+        roll.timer.tick(time.delta());
+        if roll.timer.finished() {
+            commands.entity(entity).despawn();
+            dice_roll_result_writer.send(DiceRollResult {
+                values: roll.values.clone(),
+            });
+            continue;
+        }
+
+        for (mut text, values) in text_query.iter_mut().zip(roll.values.iter()) {
+            let num_dice = values.len();
+            text.sections[0].value = format!("{}", rng.gen_range(num_dice..=6 * num_dice));
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use rand_chacha::ChaCha20Rng;
+
+use crate::ai::{BotContext, DiceBot};
+use crate::game::{
+    generate_playable_board, BoardConfig, DiceKind, GameState, PlayerId, Region, RegionId,
+    ReinforcementReserve, Rules,
+};
+use crate::tiered_prng::get_randomness;
+
+/// Number of turns after which a simulated game is called off without a winner
+const MAX_TURNS: usize = 10_000;
+
+/// Picks an attack `(region_1, region_2)` for the current player, or `None` to end the turn
+pub type SimulatedAi = dyn Fn(&GameState, &mut ChaCha20Rng) -> Option<(Region, Region)>;
+
+/// Result of a simulated game
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct GameOutcome {
+    /// Player owning every region at the end, `None` if the game hit the turn limit
+    pub winner: Option<PlayerId>,
+    pub turns: usize,
+    /// Number of clashes played
+    pub moves: usize,
+}
+
+/// Play a whole game without rendering. Randomness is split from the seeds the same way
+/// as in a rendered game, so the outcome only depends on the seeds, `rules`, `dice_kind`
+/// and `ai`.
+pub fn simulate_game(
+    world_seed: u64,
+    env_seed: u64,
+    players: usize,
+    rules: &Rules,
+    dice_kind: DiceKind,
+    ai: &SimulatedAi,
+) -> GameOutcome {
+    let board = generate_playable_board(players, &BoardConfig::default(), world_seed, 1)
+        .unwrap_or_else(|err| panic!("{}", err));
+    let mut game_state = GameState::from_board(board, players);
+    game_state.dice_kind = dice_kind;
+
+    play_out(&mut game_state, env_seed, rules, ai)
+}
+
+/// Same as [`simulate_game`], but continues from a given game, which is left in its final state
+pub fn play_out(
+    game_state: &mut GameState,
+    env_seed: u64,
+    rules: &Rules,
+    ai: &SimulatedAi,
+) -> GameOutcome {
+    play_out_with(game_state, env_seed, rules, |state, rng| {
+        ai(state, rng).map(|(region_1, region_2)| (region_1.id, region_2.id))
+    })
+}
+
+/// Same as [`play_out`], with player `i` played by `bots[i]`
+pub fn play_out_with_bots(
+    game_state: &mut GameState,
+    env_seed: u64,
+    rules: &Rules,
+    bots: &mut [Box<dyn DiceBot>],
+) -> GameOutcome {
+    play_out_with(game_state, env_seed, rules, |state, rng| {
+        let bot = bots.get_mut(state.turn_of_player.0)?;
+        bot.choose(state, &mut BotContext { rules, rng })
+    })
+}
+
+/// Play until a single player is left, with attacks `(region_1, region_2)` picked by `choose`.
+/// Clashes and turns go through the same [`GameState`] rules as in a rendered game.
+fn play_out_with(
+    game_state: &mut GameState,
+    env_seed: u64,
+    rules: &Rules,
+    mut choose: impl FnMut(&GameState, &mut ChaCha20Rng) -> Option<(RegionId, RegionId)>,
+) -> GameOutcome {
+    let mut ai_rng = get_randomness(env_seed);
+    let mut dice_rng = get_randomness(env_seed);
+    dice_rng.set_stream(1);
+    let mut env_rng = get_randomness(env_seed);
+    env_rng.set_stream(2);
+
+    let mut reserve = ReinforcementReserve::default();
+    let mut moves = 0;
+
+    while game_state.turn_counter < MAX_TURNS {
+        if let Some(winner) = game_state.is_game_over() {
+            return GameOutcome {
+                winner: Some(winner),
+                turns: game_state.turn_counter,
+                moves,
+            };
+        }
+
+        // Legality is checked against the board, in case `choose` worked on stale copies
+        // of regions. If the attack isn't legal, the player passes instead.
+        let attack = choose(game_state, &mut ai_rng).filter(|(region_1, region_2)| {
+            let regions = &game_state.board.regions;
+            match (regions.get(region_1.0), regions.get(region_2.0)) {
+                (Some(region_1), Some(region_2)) => {
+                    game_state.is_legal_move_with_rules(region_1, region_2, rules)
+                }
+                _ => false,
+            }
+        });
+        if let Some((region_1, region_2)) = attack {
+            if game_state
+                .play_clash(region_1, region_2, rules, &mut dice_rng, &mut env_rng)
+                .is_ok()
+            {
+                moves += 1;
+                continue;
+            }
+        }
+
+        game_state.end_turn(rules, &mut reserve, &mut env_rng);
+    }
+
+    GameOutcome {
+        winner: None,
+        turns: game_state.turn_counter,
+        moves,
+    }
+}
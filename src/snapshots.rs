@@ -0,0 +1,62 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::game::GameState;
+
+/// Resource that keeps the last few game states so recent history can be inspected
+/// when debugging. Only recorded in debug builds.
+#[derive(Resource)]
+pub struct SnapshotHistory {
+    capacity: usize,
+    snapshots: VecDeque<GameState>,
+}
+
+impl SnapshotHistory {
+    pub fn new(capacity: usize) -> Self {
+        SnapshotHistory {
+            capacity,
+            snapshots: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Store a snapshot, discarding the oldest one once capacity is reached
+    pub fn push(&mut self, game_state: GameState) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.snapshots.len() == self.capacity {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(game_state);
+    }
+
+    /// Snapshot taken `steps_back` changes ago, `0` being the most recent one
+    pub fn snapshot_at(&self, steps_back: usize) -> Option<&GameState> {
+        self.snapshots
+            .len()
+            .checked_sub(steps_back + 1)
+            .and_then(|index| self.snapshots.get(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.snapshots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+impl Default for SnapshotHistory {
+    fn default() -> Self {
+        SnapshotHistory::new(64)
+    }
+}
+
+pub(crate) fn record_snapshots(game_state: Res<GameState>, mut history: ResMut<SnapshotHistory>) {
+    if game_state.is_changed() {
+        history.push(game_state.clone());
+    }
+}
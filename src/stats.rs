@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::game::{GameLogEntry, PlayerId};
+
+/// Most regions captured by one player during a single turn
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CaptureChain {
+    pub player: PlayerId,
+    pub turn_counter: usize,
+    pub captures: usize,
+}
+
+/// Resource with statistics collected over all games of a session
+#[derive(Resource, Default)]
+pub struct SessionStats {
+    pub longest_capture_chain: Option<CaptureChain>,
+    pub games_played: usize,
+    /// Number of games won by each player index
+    pub wins: BTreeMap<PlayerId, usize>,
+    /// Turns played over all games
+    pub total_turns: usize,
+}
+
+impl SessionStats {
+    /// Merge statistics of a finished game into the session
+    pub fn record_game(&mut self, game_log: &[GameLogEntry], winner: PlayerId) {
+        self.games_played += 1;
+        *self.wins.entry(winner).or_insert(0) += 1;
+        self.total_turns += game_log.last().map_or(0, |gl| gl.turn_counter + 1);
+
+        if let Some(chain) = longest_capture_chain(game_log) {
+            match self.longest_capture_chain {
+                Some(longest) if longest.captures >= chain.captures => {}
+                _ => self.longest_capture_chain = Some(chain),
+            }
+        }
+    }
+
+    /// Average number of turns of a game, 0 before any game is finished
+    pub fn average_turns(&self) -> f64 {
+        match self.games_played {
+            0 => 0.0,
+            games => self.total_turns as f64 / games as f64,
+        }
+    }
+}
+
+/// Resource with real time spent in the current match, in total and by each player
+#[derive(Resource, Default)]
+pub struct MatchClock {
+    pub elapsed: Duration,
+    /// Time spent by each player index on their own turns
+    pub thinking_time: BTreeMap<PlayerId, Duration>,
+    /// Set once the match is over
+    pub stopped: bool,
+}
+
+impl MatchClock {
+    /// Advance the clock by `delta` of a turn of `player`
+    pub fn tick(&mut self, player: PlayerId, delta: Duration) {
+        if self.stopped {
+            return;
+        }
+
+        self.elapsed += delta;
+        *self.thinking_time.entry(player).or_default() += delta;
+    }
+
+    pub fn thinking_time(&self, player: PlayerId) -> Duration {
+        self.thinking_time.get(&player).copied().unwrap_or_default()
+    }
+}
+
+/// Duration as `MM:SS`
+pub fn format_clock(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Session statistics in the Prometheus text exposition format.
+/// Players are labelled 1-based, the way they are shown in the game.
+pub fn metrics_text(stats: &SessionStats) -> String {
+    let mut text = String::new();
+
+    writeln!(text, "# HELP games_total Games finished in this session.").unwrap();
+    writeln!(text, "# TYPE games_total counter").unwrap();
+    writeln!(text, "games_total {}", stats.games_played).unwrap();
+
+    writeln!(text, "# HELP wins_total Games won by a player.").unwrap();
+    writeln!(text, "# TYPE wins_total counter").unwrap();
+    for (player, wins) in stats.wins.iter() {
+        writeln!(text, "wins_total{{player=\"{}\"}} {}", player.0 + 1, wins).unwrap();
+    }
+
+    writeln!(text, "# HELP avg_turns Average number of turns per game.").unwrap();
+    writeln!(text, "# TYPE avg_turns gauge").unwrap();
+    writeln!(text, "avg_turns {}", stats.average_turns()).unwrap();
+
+    text
+}
+
+/// Find the turn in which a player captured the most regions. Earlier turns win ties.
+pub fn longest_capture_chain(game_log: &[GameLogEntry]) -> Option<CaptureChain> {
+    let mut captures_per_turn: BTreeMap<(usize, PlayerId), usize> = BTreeMap::new();
+    for gl in game_log.iter().filter(|gl| gl.attacker_won) {
+        *captures_per_turn
+            .entry((gl.turn_counter, gl.turn_of_player))
+            .or_insert(0) += 1;
+    }
+
+    let mut longest: Option<CaptureChain> = None;
+    for ((turn_counter, player), captures) in captures_per_turn.into_iter() {
+        if longest.is_none_or(|l| captures > l.captures) {
+            longest = Some(CaptureChain {
+                player,
+                turn_counter,
+                captures,
+            });
+        }
+    }
+    longest
+}
@@ -1,5 +1,7 @@
-use bevy::prelude::*;
-use rand::SeedableRng;
+#[cfg(feature = "render")]
+use bevy::prelude::{App, Plugin};
+use bevy_ecs::prelude::{FromWorld, Resource, World};
+use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 
 #[derive(Resource)]
@@ -8,6 +10,7 @@ pub struct PrngResource {
     pub env_seed: u64,
 }
 
+#[cfg(feature = "render")]
 pub struct PrngPlugin;
 
 #[derive(Resource)]
@@ -15,13 +18,42 @@ pub struct PrngMapResource {
     pub rng: ChaCha20Rng,
 }
 
+/// Source of randomness for AI players, derived from `env_seed`
+#[derive(Resource)]
+pub struct PrngAiResource {
+    pub rng: ChaCha20Rng,
+}
+
+/// Source of randomness for outcomes of clashes, like dice left on a captured region,
+/// derived from `env_seed`
+#[derive(Resource)]
+pub struct PrngEnvResource {
+    pub rng: ChaCha20Rng,
+}
+
+/// Source of randomness for dice rolled without physics, derived from `env_seed`
+#[derive(Resource)]
+pub struct PrngDiceResource {
+    pub rng: ChaCha20Rng,
+}
+
+#[cfg(feature = "render")]
 impl Plugin for PrngPlugin {
     fn build(&self, app: &mut App) {
         let seeds = app.world.get_resource::<PrngResource>().unwrap();
 
-        app.insert_resource(PrngMapResource {
-            rng: get_randomness(seeds.world_seed),
-        });
+        let map_rng = get_randomness(seeds.world_seed);
+        let ai_rng = get_randomness(seeds.env_seed);
+        let mut dice_rng = get_randomness(seeds.env_seed);
+        // Separate stream, so dice values don't mirror AI decisions
+        dice_rng.set_stream(1);
+        let mut env_rng = get_randomness(seeds.env_seed);
+        env_rng.set_stream(2);
+
+        app.insert_resource(PrngMapResource { rng: map_rng })
+            .insert_resource(PrngAiResource { rng: ai_rng })
+            .insert_resource(PrngDiceResource { rng: dice_rng })
+            .insert_resource(PrngEnvResource { rng: env_rng });
     }
 }
 
@@ -30,7 +62,6 @@ impl FromWorld for PrngResource {
         // Values zero will be considered uninitialized
 
         //world.insert_resource(); // MapPrng
-        //world.insert_resource(); // AiPrng
         PrngResource {
             world_seed: 0,
             env_seed: 0,
@@ -41,3 +72,21 @@ impl FromWorld for PrngResource {
 pub fn get_randomness(seed: u64) -> ChaCha20Rng {
     ChaCha20Rng::seed_from_u64(seed)
 }
+
+/// World seed of the board previewed after the one of `seed`. Never returns zero.
+pub fn preview_seed(seed: u64) -> u64 {
+    get_randomness(seed).next_u64().max(1)
+}
+
+/// Stable seed from a human-readable phrase, so memorable map names give reproducible boards.
+/// Case and extra whitespace are ignored. Never returns zero, which means an unset seed.
+pub fn seed_from_phrase(phrase: &str) -> u64 {
+    // 64-bit FNV-1a
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let normalized = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+    for byte in normalized.to_lowercase().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash.max(1)
+}
@@ -0,0 +1,148 @@
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, Write as _};
+use std::path::Path;
+
+use bevy::prelude::*;
+use rand_chacha::ChaCha20Rng;
+
+use crate::game::GameState;
+use crate::tiered_prng::{PrngAiResource, PrngDiceResource, PrngEnvResource, PrngMapResource};
+
+/// Digests of the positions of all PRNG streams
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RngDigests {
+    pub map: u64,
+    pub env: u64,
+    pub ai: u64,
+    pub dice: u64,
+}
+
+/// A single recorded step of a game
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TraceStep {
+    pub event: String,
+    pub rng: RngDigests,
+}
+
+/// Resource with a trace of the game: key events along with PRNG digests at each step.
+/// Traces of two runs with the same seeds are identical, so comparing them pinpoints
+/// where a nondeterminism bug kicks in. Recorded in debug and testing builds, and whenever
+/// a trace file is asked for.
+#[derive(Resource, Default)]
+pub struct TraceLog {
+    steps: Vec<TraceStep>,
+    /// File every step is appended to as it's recorded
+    file: Option<File>,
+}
+
+impl TraceLog {
+    /// Trace that is also written to a file at `path`, replacing what was there
+    pub fn to_file(path: &Path) -> io::Result<Self> {
+        Ok(TraceLog {
+            steps: Vec::new(),
+            file: Some(File::create(path)?),
+        })
+    }
+
+    pub fn record(&mut self, event: String, rng: RngDigests) {
+        let step = TraceStep { event, rng };
+        if let Some(file) = self.file.as_mut() {
+            if let Err(e) = writeln!(file, "{}", step_line(self.steps.len(), &step)) {
+                warn!("Can't write trace step, the trace file is abandoned: {}", e);
+                self.file = None;
+            }
+        }
+        self.steps.push(step);
+    }
+
+    pub fn steps(&self) -> &[TraceStep] {
+        &self.steps
+    }
+
+    /// Trace as text, one step per line
+    pub fn to_text(&self) -> String {
+        self.steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| step_line(i, step) + "\n")
+            .collect()
+    }
+
+    /// Index of the first step where two traces differ, `None` if they are identical
+    pub fn first_divergence(&self, other: &TraceLog) -> Option<usize> {
+        let common = self.steps.len().min(other.steps.len());
+        (0..common)
+            .find(|i| self.steps[*i] != other.steps[*i])
+            .or_else(|| (self.steps.len() != other.steps.len()).then_some(common))
+    }
+}
+
+/// Line of the `i`-th step in a trace file
+fn step_line(i: usize, step: &TraceStep) -> String {
+    format!(
+        "{} map={:016x} env={:016x} ai={:016x} dice={:016x} {}",
+        i, step.rng.map, step.rng.env, step.rng.ai, step.rng.dice, step.event
+    )
+}
+
+/// Digest of the seed, stream and position of a PRNG
+pub fn rng_digest(rng: &ChaCha20Rng) -> u64 {
+    // 64-bit FNV-1a
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let bytes = rng
+        .get_seed()
+        .into_iter()
+        .chain(rng.get_stream().to_le_bytes())
+        .chain(rng.get_word_pos().to_le_bytes());
+    for byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+/// Short description of the latest change of a game
+fn trace_event(game_state: &GameState) -> String {
+    let mut event = format!(
+        "turn={} player={}",
+        game_state.turn_counter, game_state.turn_of_player.0
+    );
+    if let Some(gl) = game_state.game_log.last() {
+        write!(
+            event,
+            " {}={}->{} dice={:?}/{:?}",
+            format!("{:?}", gl.action).to_lowercase(),
+            gl.region_1.id.0,
+            gl.region_2.id.0,
+            gl.region_1_dice_result,
+            gl.region_2_dice_result
+        )
+        .unwrap();
+    }
+    event
+}
+
+/// Record a step of the trace whenever the game changes
+pub fn record_trace(
+    game_state: Res<GameState>,
+    map_prng: Res<PrngMapResource>,
+    env_prng: Res<PrngEnvResource>,
+    ai_prng: Res<PrngAiResource>,
+    dice_prng: Res<PrngDiceResource>,
+    mut trace_log: ResMut<TraceLog>,
+) {
+    if !game_state.is_changed() {
+        return;
+    }
+
+    trace_log.record(
+        trace_event(&game_state),
+        RngDigests {
+            map: rng_digest(&map_prng.rng),
+            env: rng_digest(&env_prng.rng),
+            ai: rng_digest(&ai_prng.rng),
+            dice: rng_digest(&dice_prng.rng),
+        },
+    );
+}
@@ -1,10 +1,21 @@
-use bevy::{prelude::*, render::camera::ScalingMode};
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::{Diagnostics, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+    render::camera::ScalingMode,
+};
 use bevy_dice::DicePluginSettings;
 use bevy_kira_audio::prelude::*;
 use bevy_mod_picking::PickingCameraBundle;
 
-use crate::board::PLAYER_COLORS;
-use crate::game::GameState;
+use crate::ai::AiPlayers;
+use crate::board::{is_fogged, player_color, DiceMesh, FogOfWar, Palette};
+use crate::camera::CameraController;
+use crate::events::AttackConfirmation;
+use crate::game::{GameAction, GameState, PlayerId, Region, RegionId, ReinforcementReserve, Rules};
+use crate::stats::{format_clock, MatchClock};
+use crate::tiered_prng::PrngResource;
 
 /// Text area with a title for the game
 #[derive(Component)]
@@ -14,46 +25,720 @@ pub(crate) struct TitleText;
 #[derive(Component)]
 pub(crate) struct CurrentTurnText;
 
+/// Text area with the turn counter and the match clock
+#[derive(Component)]
+pub(crate) struct MatchClockText;
+
+/// Resource that toggles the turn counter and the match clock
+#[derive(Resource)]
+pub struct MatchClockDisplay {
+    pub enabled: bool,
+}
+
+impl Default for MatchClockDisplay {
+    fn default() -> Self {
+        MatchClockDisplay { enabled: true }
+    }
+}
+
 /// UI elements associated with dice rolling
 #[derive(Component)]
 pub(crate) struct DiceRollUI;
 
+/// Camera looking at the game board
+#[derive(Component)]
+pub(crate) struct BoardCamera;
+
+/// Text label with a number of dice on a region, following a point on the board
+#[derive(Component)]
+pub(crate) struct DiceCountLabel {
+    pub world_position: Vec3,
+    /// Shown even when [`ShowDiceCounts`] is off
+    pub always_shown: bool,
+}
+
+/// Text area with regions and dice of every player, a section per player
+#[derive(Component)]
+pub(crate) struct ScoreboardText;
+
+/// Text area with the attacker's odds to win a clash in progress
+#[derive(Component)]
+pub(crate) struct ClashOddsText;
+
+/// Bar chart with odds of each dice sum for one side of a clash
+#[derive(Component)]
+pub(crate) struct OddsBarChart {
+    side: usize,
+}
+
+/// Overlay hiding the board between turns of a hot seat game
+#[derive(Component)]
+pub(crate) struct HotSeatOverlay;
+
+/// Text area of the hot seat overlay naming the next player
+#[derive(Component)]
+pub(crate) struct HotSeatOverlayText;
+
+/// Resource that controls the between-turns overlay of a hot seat game
+#[derive(Resource)]
+pub struct HotSeat {
+    pub enabled: bool,
+    /// Whether the next player is yet to confirm they are ready
+    pub waiting_for_ready: bool,
+}
+
+impl Default for HotSeat {
+    fn default() -> Self {
+        HotSeat {
+            enabled: true,
+            waiting_for_ready: false,
+        }
+    }
+}
+
+/// Text area with odds of an attack waiting for confirmation
+#[derive(Component)]
+pub(crate) struct ArmedAttackText;
+
+/// Text area announcing a player who has lost their last region, hidden once `timer` is over
+#[derive(Component)]
+pub(crate) struct EliminationBanner {
+    pub timer: Timer,
+}
+
+/// Text area describing the region under the cursor
+#[derive(Component)]
+pub(crate) struct HoverInfoText;
+
+/// Resource with a description of the region under the cursor. Unlike highlighting,
+/// it's available for every region regardless of whose turn it is.
+#[derive(Resource, Default)]
+pub struct HoverInfo {
+    pub region: Option<RegionId>,
+    pub text: Option<String>,
+}
+
+/// Text area of the debug overlay
+#[derive(Component)]
+pub(crate) struct DebugOverlayText;
+
+/// Text area next to the cursor with details of the hovered region
+#[derive(Component)]
+pub(crate) struct RegionTooltipText;
+
+/// Text area with seeds of the current game, kept on the game over screen
+#[derive(Component)]
+pub(crate) struct SeedText;
+
+/// Resource with names of players for hot seat games, in order of player ids.
+/// Players without a name are called by their number.
+#[derive(Resource, Clone, Default, Debug)]
+pub struct PlayerNames(pub Vec<String>);
+
+impl PlayerNames {
+    pub fn name(&self, id: PlayerId) -> String {
+        match self.0.get(id.0) {
+            Some(name) if !name.trim().is_empty() => name.clone(),
+            _ => format!("PLAYER {}", id.0 + 1),
+        }
+    }
+}
+
+/// Resource that toggles labels with the number of dice above regions, bound to the N key
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ShowDiceCounts(pub bool);
+
+impl Default for ShowDiceCounts {
+    fn default() -> Self {
+        ShowDiceCounts(true)
+    }
+}
+
+/// Resource that toggles the tooltip of the hovered region, bound to the T key
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ShowRegionTooltip(pub bool);
+
+impl Default for ShowRegionTooltip {
+    fn default() -> Self {
+        ShowRegionTooltip(true)
+    }
+}
+
+/// Resource that toggles an overlay with performance numbers, useful for bug reports
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub enabled: bool,
+}
+
+/// Resource that controls how dice sums of both sides of a clash are revealed
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum RevealStyle {
+    /// Both sums are shown as soon as dice settle
+    #[default]
+    Simultaneous,
+    /// Attacker's sum is shown first, defender's one after a delay
+    Sequential { delay: Duration },
+}
+
+impl RevealStyle {
+    /// Whether the sum of a side (`0` for the attacker, `1` for the defender) is shown
+    /// `elapsed` time after dice results are known
+    pub fn is_revealed(&self, side: usize, elapsed: Duration) -> bool {
+        match self {
+            RevealStyle::Simultaneous => true,
+            RevealStyle::Sequential { delay } => side == 0 || elapsed >= *delay,
+        }
+    }
+}
+
 /// UI element for a game. Used for end-game screen to destroy all UI elements
 #[derive(Component)]
 pub(crate) struct StackRankDiceUI;
 
 pub(crate) fn player_turn_text_update(
     game_state: Res<GameState>,
+    palette: Res<Palette>,
+    player_names: Res<PlayerNames>,
     mut query: Query<&mut Text, With<CurrentTurnText>>,
 ) {
+    let id = game_state.players.id_of(game_state.turn_of_player);
+
     for mut text in &mut query {
-        text.sections[0].value = format!("PLAYER {} TURN", game_state.turn_of_player + 1,);
-        text.sections[0].style.color = PLAYER_COLORS[game_state.turn_of_player as usize];
+        text.sections[0].value = format!("{} TURN", player_names.name(id));
+        text.sections[0].style.color = player_color(id, *palette);
     }
 }
 
-pub(crate) fn dice_roll_result_text_update(
+pub(crate) fn scoreboard_text_update(
     game_state: Res<GameState>,
-    mut query: Query<&mut Text, With<DiceRollUI>>,
+    reserve: Res<ReinforcementReserve>,
+    rules: Res<Rules>,
+    palette: Res<Palette>,
+    player_names: Res<PlayerNames>,
+    mut query: Query<&mut Text, With<ScoreboardText>>,
 ) {
-    for (i, mut text) in &mut query.iter_mut().enumerate() {
-        let last_log_entry = game_state.game_log.last();
-        if last_log_entry.is_none() {
-            return;
+    let player_stats = game_state.player_stats();
+    // The player owning the most regions is marked with a star, nobody on a tie
+    let leader = game_state.board.leader();
+
+    for mut text in &mut query {
+        let style = text.sections[0].style.clone();
+        text.sections.resize(
+            player_stats.len(),
+            TextSection {
+                value: String::new(),
+                style,
+            },
+        );
+
+        for (section, stats) in text.sections.iter_mut().zip(player_stats.iter()) {
+            let id = game_state.players.id_of(stats.player);
+            section.value = format!(
+                "{}{}: {} REGIONS ({:.0}%), {} DICE, {} CONNECTED, +{} NEXT TURN\n",
+                if leader == Some(stats.player) {
+                    "* "
+                } else {
+                    ""
+                },
+                player_names.name(id),
+                stats.region_count,
+                game_state.board.player_share(stats.player) * 100.0,
+                stats.total_dice,
+                stats.largest_group,
+                game_state.pending_reinforcements(stats.player, &reserve, rules.largest_army_bonus)
+            );
+            section.style.color = player_color(id, *palette);
+        }
+    }
+}
+
+pub(crate) fn match_clock_update(
+    time: Res<Time>,
+    game_state: Res<GameState>,
+    mut match_clock: ResMut<MatchClock>,
+) {
+    match_clock.tick(game_state.turn_of_player, time.delta());
+}
+
+pub(crate) fn match_clock_text_update(
+    game_state: Res<GameState>,
+    match_clock: Res<MatchClock>,
+    match_clock_display: Res<MatchClockDisplay>,
+    player_names: Res<PlayerNames>,
+    mut query: Query<(&mut Text, &mut Visibility), With<MatchClockText>>,
+) {
+    for (mut text, mut visibility) in &mut query {
+        visibility.is_visible = match_clock_display.enabled;
+        if !match_clock_display.enabled {
+            continue;
         }
 
-        let log_entry = last_log_entry.unwrap();
-        let result_1: usize = log_entry.region_1_dice_result.iter().sum();
-        let result_2: usize = log_entry.region_2_dice_result.iter().sum();
+        text.sections[0].value = format!(
+            "TURN {}  {}  ({}: {})",
+            game_state.turn_counter + 1,
+            format_clock(match_clock.elapsed),
+            player_names.name(game_state.players.id_of(game_state.turn_of_player)),
+            format_clock(match_clock.thinking_time(game_state.turn_of_player)),
+        );
+    }
+}
+
+pub(crate) fn dice_roll_result_text_update(
+    game_state: Res<GameState>,
+    reveal_style: Res<RevealStyle>,
+    time: Res<Time>,
+    mut results_known_for: Local<Duration>,
+    mut query: Query<&mut Text, (With<DiceRollUI>, Without<ClashOddsText>)>,
+) {
+    let log_entry = match game_state.game_log.last() {
+        Some(log_entry) if log_entry.action == GameAction::Clash => log_entry,
+        _ => return,
+    };
+    let result_1 = log_entry.region_1_dice_sum();
+    let result_2 = log_entry.region_2_dice_sum();
+
+    // Dice results of a clash arrive together once the dice settle
+    if result_1 == 0 && result_2 == 0 {
+        *results_known_for = Duration::ZERO;
+    } else {
+        *results_known_for += time.delta();
+    }
 
+    for (i, mut text) in &mut query.iter_mut().enumerate() {
         if i == 0 && result_1 != 0 {
             text.sections[0].value = format!("{}", result_1);
         } else if result_2 != 0 {
-            text.sections[0].value = format!("{}", result_2);
+            text.sections[0].value = match reveal_style.is_revealed(1, *results_known_for) {
+                true => format!("{}", result_2),
+                false => String::new(),
+            };
+        }
+    }
+}
+
+pub(crate) fn clash_odds_text_update(
+    game_state: Res<GameState>,
+    mut query: Query<&mut Text, With<ClashOddsText>>,
+) {
+    if !game_state.is_changed() {
+        return;
+    }
+
+    let log_entry = match game_state.game_log.last() {
+        Some(log_entry) if log_entry.action == GameAction::Clash => log_entry,
+        _ => return,
+    };
+
+    let p = log_entry
+        .region_1
+        .attack_win_probability_with(&log_entry.region_2, game_state.dice_kind);
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("{:.0}% TO WIN", p * 100.0);
+    }
+}
+
+pub(crate) fn dice_counts_input(
+    keys: Res<Input<KeyCode>>,
+    mut show_dice_counts: ResMut<ShowDiceCounts>,
+) {
+    if keys.just_pressed(KeyCode::N) {
+        show_dice_counts.0 = !show_dice_counts.0;
+    }
+}
+
+pub(crate) fn dice_count_label_update(
+    camera_query: Query<(&Camera, &GlobalTransform), With<BoardCamera>>,
+    show_dice_counts: Res<ShowDiceCounts>,
+    mut label_query: Query<(&mut Style, &DiceCountLabel)>,
+) {
+    let (camera, camera_transform) = match camera_query.get_single() {
+        Ok(camera) => camera,
+        Err(_) => return,
+    };
+
+    for (mut style, label) in label_query.iter_mut() {
+        // Display is left alone by the capture animation, which reveals labels through visibility
+        style.display = match label.always_shown || show_dice_counts.0 {
+            true => Display::Flex,
+            false => Display::None,
+        };
+
+        if let Some(screen_position) =
+            camera.world_to_viewport(camera_transform, label.world_position)
+        {
+            style.position = UiRect {
+                left: Val::Px(screen_position.x),
+                bottom: Val::Px(screen_position.y),
+                ..default()
+            };
+        }
+    }
+}
+
+pub(crate) fn odds_bar_chart_update(
+    mut commands: Commands,
+    game_state: Res<GameState>,
+    palette: Res<Palette>,
+    mut seen_log_entries: Local<usize>,
+    query: Query<(Entity, &OddsBarChart)>,
+) {
+    // Rebuild charts only when a new clash is logged
+    if game_state.game_log.len() == *seen_log_entries {
+        return;
+    }
+    *seen_log_entries = game_state.game_log.len();
+
+    let log_entry = match game_state.game_log.last() {
+        Some(log_entry) if log_entry.action == GameAction::Clash => log_entry,
+        _ => return,
+    };
+
+    for (entity, chart) in query.iter() {
+        let region = match chart.side {
+            0 => &log_entry.region_1,
+            _ => &log_entry.region_2,
+        };
+
+        let distribution = game_state.dice_kind.sum_distribution(region.num_dice);
+        let max_p = distribution.iter().cloned().fold(0.0, f64::max);
+        let bar_width = 100.0 / distribution.len() as f32;
+
+        let mut chart_commands = commands.entity(entity);
+        chart_commands.despawn_descendants();
+        chart_commands.with_children(|parent| {
+            for p in distribution.iter() {
+                parent.spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(
+                            Val::Percent(bar_width),
+                            Val::Percent((p / max_p * 100.0) as f32),
+                        ),
+                        margin: UiRect::horizontal(Val::Px(1.0)),
+                        ..default()
+                    },
+                    background_color: player_color(
+                        game_state.players.id_of(region.owner),
+                        *palette,
+                    )
+                    .into(),
+                    ..default()
+                });
+            }
+        });
+    }
+}
+
+pub(crate) fn hot_seat_overlay_update(
+    hot_seat: Res<HotSeat>,
+    game_state: Res<GameState>,
+    palette: Res<Palette>,
+    player_names: Res<PlayerNames>,
+    mut overlay_query: Query<&mut Visibility, With<HotSeatOverlay>>,
+    mut text_query: Query<&mut Text, With<HotSeatOverlayText>>,
+) {
+    if !hot_seat.is_changed() {
+        return;
+    }
+
+    for mut visibility in overlay_query.iter_mut() {
+        visibility.is_visible = hot_seat.waiting_for_ready;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!(
+            "{}, PRESS SPACE WHEN READY",
+            player_names.name(game_state.players.id_of(game_state.turn_of_player))
+        );
+        text.sections[0].style.color = player_color(
+            game_state.players.id_of(game_state.turn_of_player),
+            *palette,
+        );
+    }
+}
+
+pub(crate) fn hot_seat_ready_input(keys: Res<Input<KeyCode>>, mut hot_seat: ResMut<HotSeat>) {
+    if hot_seat.waiting_for_ready
+        && (keys.just_pressed(KeyCode::Space) || keys.just_pressed(KeyCode::Return))
+    {
+        hot_seat.waiting_for_ready = false;
+    }
+}
+
+/// Short description of a hovered region owned by a player called `owner_name`
+pub fn region_description(region: &Region, owner_name: &str) -> String {
+    let mut description = format!("{}: {} DICE", owner_name, region.num_dice);
+    if region.fortify > 0 {
+        description.push_str(&format!(", FORTIFY {}", region.fortify));
+    }
+    description
+}
+
+pub(crate) fn hover_info_update(
+    regions: Query<(&Interaction, &Region)>,
+    game_state: Res<GameState>,
+    fog_of_war: Res<FogOfWar>,
+    ai_players: Res<AiPlayers>,
+    player_names: Res<PlayerNames>,
+    mut hover_info: ResMut<HoverInfo>,
+) {
+    let hovered = regions
+        .iter()
+        .find(|(interaction, _)| **interaction != Interaction::None)
+        .map(|(_, region)| region);
+    // Regions hidden by fog of war give nothing away, neither here nor in the tooltip
+    let hovered = hovered.filter(|region| {
+        let visible_regions = fog_of_war.visible_regions(&game_state, &ai_players);
+        !is_fogged(visible_regions.as_deref(), region.id)
+    });
+
+    let region = hovered.map(|r| r.id);
+    let text = hovered.map(|region| {
+        region_description(
+            region,
+            &player_names.name(game_state.players.id_of(region.owner)),
+        )
+    });
+
+    // Avoid triggering change detection every frame
+    if hover_info.region != region || hover_info.text != text {
+        hover_info.region = region;
+        hover_info.text = text;
+    }
+}
+
+pub(crate) fn hover_info_text_update(
+    hover_info: Res<HoverInfo>,
+    game_state: Res<GameState>,
+    palette: Res<Palette>,
+    mut query: Query<(&mut Text, &mut Visibility), With<HoverInfoText>>,
+) {
+    if !hover_info.is_changed() {
+        return;
+    }
+
+    for (mut text, mut visibility) in query.iter_mut() {
+        visibility.is_visible = hover_info.text.is_some();
+
+        if let (Some(region), Some(description)) = (hover_info.region, &hover_info.text) {
+            text.sections[0].value = description.clone();
+            text.sections[0].style.color = player_color(
+                game_state
+                    .players
+                    .id_of(game_state.board.regions[region].owner),
+                *palette,
+            );
+        }
+    }
+}
+
+/// Text of the tooltip of a region owned by a player called `owner_name`
+pub fn region_tooltip(region: &Region, owner_name: &str, enemy_neighbours: usize) -> String {
+    format!(
+        "{}\n{} DICE\n{} HEXES\n{} ENEMY NEIGHBOURS",
+        owner_name,
+        region.num_dice,
+        region.hexes.len(),
+        enemy_neighbours
+    )
+}
+
+pub(crate) fn region_tooltip_input(
+    keys: Res<Input<KeyCode>>,
+    mut show_region_tooltip: ResMut<ShowRegionTooltip>,
+) {
+    if keys.just_pressed(KeyCode::T) {
+        show_region_tooltip.0 = !show_region_tooltip.0;
+    }
+}
+
+/// Show details of the hovered region next to the cursor, hidden once it leaves all regions
+pub(crate) fn region_tooltip_update(
+    hover_info: Res<HoverInfo>,
+    show_region_tooltip: Res<ShowRegionTooltip>,
+    game_state: Res<GameState>,
+    player_names: Res<PlayerNames>,
+    windows: Option<Res<Windows>>,
+    mut query: Query<(&mut Text, &mut Style, &mut Visibility), With<RegionTooltipText>>,
+) {
+    let cursor = windows
+        .as_ref()
+        .and_then(|windows| windows.get_primary())
+        .and_then(|window| window.cursor_position());
+
+    for (mut text, mut style, mut visibility) in query.iter_mut() {
+        let region = match hover_info.region {
+            Some(region) if show_region_tooltip.0 => &game_state.board.regions[region],
+            _ => {
+                visibility.is_visible = false;
+                continue;
+            }
+        };
+
+        visibility.is_visible = true;
+        text.sections[0].value = region_tooltip(
+            region,
+            &player_names.name(game_state.players.id_of(region.owner)),
+            game_state.board.enemy_neighbour_count(region.id),
+        );
+
+        if let Some(cursor) = cursor {
+            style.position.left = Val::Px(cursor.x + 15.0);
+            style.position.bottom = Val::Px(cursor.y + 15.0);
+        }
+    }
+}
+
+pub(crate) fn armed_attack_text_update(
+    attack_confirmation: Res<AttackConfirmation>,
+    game_state: Res<GameState>,
+    mut query: Query<(&mut Text, &mut Visibility), With<ArmedAttackText>>,
+) {
+    if !attack_confirmation.is_changed() {
+        return;
+    }
+
+    for (mut text, mut visibility) in query.iter_mut() {
+        visibility.is_visible = attack_confirmation.armed.is_some();
+
+        if let Some((region_1, region_2)) = attack_confirmation.armed {
+            let regions = &game_state.board.regions;
+            let p = regions[region_1]
+                .attack_win_probability_with(&regions[region_2], game_state.dice_kind);
+            text.sections[0].value = format!(
+                "{:.0}% TO WIN, CLICK AGAIN OR PRESS ENTER TO ATTACK",
+                p * 100.0
+            );
+        }
+    }
+}
+
+pub(crate) fn elimination_banner_update(
+    time: Res<Time>,
+    mut query: Query<(&mut Visibility, &mut EliminationBanner)>,
+) {
+    for (mut visibility, mut banner) in query.iter_mut() {
+        if !visibility.is_visible {
+            continue;
+        }
+
+        banner.timer.tick(time.delta());
+        if banner.timer.finished() {
+            visibility.is_visible = false;
+        }
+    }
+}
+
+/// Text of the debug overlay. Diagnostics are missing until they are first measured.
+pub fn debug_overlay_text(
+    fps: Option<f64>,
+    entity_count: Option<f64>,
+    region_count: usize,
+    dice_mesh_count: usize,
+) -> String {
+    let fps = fps.map_or("-".to_string(), |fps| format!("{:.1}", fps));
+    let entity_count = entity_count.map_or("-".to_string(), |count| format!("{}", count));
+
+    format!(
+        "FPS: {}\nENTITIES: {}\nREGIONS: {}\nDICE MESHES: {}",
+        fps, entity_count, region_count, dice_mesh_count
+    )
+}
+
+/// Seeds of a game in a form players can share, e.g. `world:42 env:7`
+pub fn seed_text(world_seed: u64, env_seed: u64) -> String {
+    format!("world:{} env:{}", world_seed, env_seed)
+}
+
+pub(crate) fn setup_seed_text(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    prng_resource: Res<PrngResource>,
+) {
+    commands
+        .spawn(
+            TextBundle::from_section(
+                seed_text(prng_resource.world_seed, prng_resource.env_seed),
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 15.0,
+                    color: Color::GRAY,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(40.0),
+                    right: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(Name::new("Seed Text"))
+        .insert(SeedText);
+}
+
+/// Copy seeds of the game with C, so other players can reproduce it
+pub(crate) fn copy_seed_input(keys: Res<Input<KeyCode>>, prng_resource: Res<PrngResource>) {
+    if keys.just_pressed(KeyCode::C) {
+        copy_to_clipboard(seed_text(prng_resource.world_seed, prng_resource.env_seed));
+    }
+}
+
+#[cfg(all(feature = "clipboard", not(target_arch = "wasm32"), not(test)))]
+fn copy_to_clipboard(text: String) {
+    match arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text.clone())) {
+        Ok(()) => info!("Copied {} to the clipboard", text),
+        Err(err) => {
+            warn!("Failed to copy seeds to the clipboard: {}", err);
+            println!("{}", text);
         }
     }
 }
 
+#[cfg(not(all(feature = "clipboard", not(target_arch = "wasm32"), not(test))))]
+fn copy_to_clipboard(text: String) {
+    println!("{}", text);
+}
+
+pub(crate) fn debug_overlay_input(
+    keys: Res<Input<KeyCode>>,
+    mut debug_overlay: ResMut<DebugOverlay>,
+) {
+    if keys.just_pressed(KeyCode::F3) {
+        debug_overlay.enabled = !debug_overlay.enabled;
+    }
+}
+
+pub(crate) fn debug_overlay_update(
+    debug_overlay: Res<DebugOverlay>,
+    diagnostics: Option<Res<Diagnostics>>,
+    game_state: Res<GameState>,
+    dice_query: Query<(), With<DiceMesh>>,
+    mut text_query: Query<(&mut Text, &mut Visibility), With<DebugOverlayText>>,
+) {
+    for (mut text, mut visibility) in text_query.iter_mut() {
+        visibility.is_visible = debug_overlay.enabled;
+        if !debug_overlay.enabled {
+            continue;
+        }
+
+        let diagnostic_value = |id| {
+            diagnostics
+                .as_ref()
+                .and_then(|d| d.get(id))
+                .and_then(|d| d.smoothed())
+        };
+
+        text.sections[0].value = debug_overlay_text(
+            diagnostic_value(FrameTimeDiagnosticsPlugin::FPS),
+            diagnostic_value(EntityCountDiagnosticsPlugin::ENTITY_COUNT),
+            game_state.board.regions.len(),
+            dice_query.iter().count(),
+        );
+    }
+}
+
 pub(crate) fn setup_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
@@ -74,12 +759,13 @@ pub(crate) fn setup_ui(
                 priority: 1,
                 ..default()
             },
-            transform: Transform::from_translation(Vec3::new(50.0, 32., 0.0))
-                .looking_at(Vec3::default(), Vec3::Y),
+            transform: CameraController::default().transform(),
             ..Default::default()
         })
+        .insert(CameraController::default())
         .insert(PickingCameraBundle::default())
         // .insert(UiCameraConfig { show_ui: false })
+        .insert(BoardCamera)
         .insert(Name::new("Board Camera"));
 
     // Current Turn Text
@@ -107,6 +793,30 @@ pub(crate) fn setup_ui(
         .insert(CurrentTurnText)
         .insert(StackRankDiceUI);
 
+    // Turn Counter and Match Clock Text
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 25.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(100.0),
+                    left: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(MatchClockText)
+        .insert(StackRankDiceUI);
+
     // Dice Roll camera
     commands.spawn(Camera2dBundle {
         camera: Camera {
@@ -116,20 +826,23 @@ pub(crate) fn setup_ui(
         ..default()
     });
 
-    for (i, dice_camera) in dice_plugin_settings.render_handles.iter().enumerate() {
-        commands
-            .spawn(ImageBundle {
-                image: UiImage(dice_camera.clone()),
-                style: Style {
-                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+    for i in 0..dice_plugin_settings.number_of_fields {
+        // Dice are rendered only when rolled by physics
+        if let Some(dice_camera) = dice_plugin_settings.render_handles.get(i) {
+            commands
+                .spawn(ImageBundle {
+                    image: UiImage(dice_camera.clone()),
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                        ..default()
+                    },
                     ..default()
-                },
-                ..default()
-            })
-            .insert(Name::new("Dice Roll View"))
-            .insert(DiceRollUI)
-            .insert(Visibility { is_visible: false })
-            .insert(StackRankDiceUI);
+                })
+                .insert(Name::new("Dice Roll View"))
+                .insert(DiceRollUI)
+                .insert(Visibility { is_visible: false })
+                .insert(StackRankDiceUI);
+        }
 
         // Dice Throw Sum Text
         commands
@@ -157,8 +870,58 @@ pub(crate) fn setup_ui(
             .insert(DiceRollUI)
             .insert(StackRankDiceUI)
             .insert(Visibility { is_visible: false });
+
+        // Dice Sum Odds
+        commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        bottom: Val::Percent(15.0),
+                        left: Val::Percent(10.0 + 50.0 * (i as f32)),
+                        ..default()
+                    },
+                    size: Size::new(Val::Percent(30.0), Val::Percent(10.0)),
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                visibility: Visibility { is_visible: false },
+                ..default()
+            })
+            .insert(Name::new("Dice Sum Odds"))
+            .insert(OddsBarChart { side: i })
+            .insert(DiceRollUI)
+            .insert(StackRankDiceUI);
     }
 
+    // Clash Odds Text
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_text_alignment(TextAlignment::TOP_CENTER)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Percent(30.0),
+                    left: Val::Percent(45.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(Name::new("Clash Odds Text"))
+        .insert(ClashOddsText)
+        .insert(DiceRollUI)
+        .insert(StackRankDiceUI)
+        .insert(Visibility { is_visible: false });
+
     // Title Text
     commands
         .spawn(
@@ -183,9 +946,364 @@ pub(crate) fn setup_ui(
         )
         .insert(TitleText);
 
+    // Hot Seat Overlay
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::BLACK.into(),
+            visibility: Visibility { is_visible: false },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                        font_size: 50.0,
+                        color: Color::WHITE,
+                    },
+                ))
+                .insert(HotSeatOverlayText);
+        })
+        .insert(Name::new("Hot Seat Overlay"))
+        .insert(HotSeatOverlay)
+        .insert(StackRankDiceUI);
+
+    // Armed Attack Odds
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 30.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_text_alignment(TextAlignment::TOP_CENTER)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(60.0),
+                    left: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(Name::new("Armed Attack Odds"))
+        .insert(ArmedAttackText)
+        .insert(StackRankDiceUI)
+        .insert(Visibility { is_visible: false });
+
+    // Elimination Banner
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 40.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_text_alignment(TextAlignment::TOP_CENTER)
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(100.0),
+                    right: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(Name::new("Elimination Banner"))
+        .insert(EliminationBanner {
+            timer: Timer::new(Duration::from_secs(2), TimerMode::Once),
+        })
+        .insert(StackRankDiceUI)
+        .insert(Visibility { is_visible: false });
+
+    // Hover Info
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 30.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    bottom: Val::Px(5.0),
+                    right: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(Name::new("Hover Info"))
+        .insert(HoverInfoText)
+        .insert(StackRankDiceUI)
+        .insert(Visibility { is_visible: false });
+
+    // Region Tooltip
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                ..default()
+            }),
+        )
+        .insert(Name::new("Region Tooltip"))
+        .insert(RegionTooltipText)
+        .insert(StackRankDiceUI)
+        .insert(Visibility { is_visible: false });
+
+    // Scoreboard
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(120.0),
+                    left: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(Name::new("Scoreboard"))
+        .insert(ScoreboardText)
+        .insert(StackRankDiceUI);
+
+    // Debug Overlay
+    commands
+        .spawn(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(5.0),
+                    left: Val::Px(15.0),
+                    ..default()
+                },
+                ..default()
+            }),
+        )
+        .insert(Name::new("Debug Overlay"))
+        .insert(DebugOverlayText)
+        .insert(Visibility { is_visible: false });
+
     // Music
 
     audio
         .play(asset_server.load("sounds/laidback.ogg"))
         .looped();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Board;
+
+    #[test]
+    fn hover_info_for_opponent_region() {
+        let mut app = App::new();
+        app.init_resource::<HoverInfo>()
+            .init_resource::<FogOfWar>()
+            .init_resource::<AiPlayers>()
+            .insert_resource(PlayerNames(vec!["Alice".to_string(), "Bob".to_string()]))
+            .insert_resource(GameState::from_board(Board::from_regions(Vec::new()), 2))
+            .add_system(hover_info_update);
+
+        // Player 1 hovers a region of player 2 during their own turn
+        let opponent_region = Region {
+            owner: PlayerId(1),
+            num_dice: 4,
+            id: RegionId(3),
+            fortify: 2,
+            ..default()
+        };
+        app.world.spawn((Interaction::Hovered, opponent_region));
+        app.world.spawn((Interaction::None, Region::default()));
+        app.update();
+
+        let hover_info = app.world.resource::<HoverInfo>();
+        assert_eq!(hover_info.region, Some(RegionId(3)));
+        assert_eq!(hover_info.text.as_deref(), Some("Bob: 4 DICE, FORTIFY 2"));
+    }
+
+    #[test]
+    fn scoreboard_marks_the_leader() {
+        let board = Board::from_regions(
+            [0, 1, 1]
+                .into_iter()
+                .enumerate()
+                .map(|(id, owner)| {
+                    Region::new(RegionId(id), PlayerId(owner), 2, vec![(id as isize, 0)])
+                })
+                .collect(),
+        );
+        let mut app = App::new();
+        app.init_resource::<ReinforcementReserve>()
+            .init_resource::<Rules>()
+            .init_resource::<Palette>()
+            .init_resource::<PlayerNames>()
+            .insert_resource(GameState::from_board(board, 2))
+            .add_system(scoreboard_text_update);
+        let scoreboard = app
+            .world
+            .spawn((Text::from_section("", TextStyle::default()), ScoreboardText))
+            .id();
+        app.update();
+
+        let text = app.world.get::<Text>(scoreboard).unwrap();
+        assert!(text.sections[0]
+            .value
+            .starts_with("PLAYER 1: 1 REGIONS (33%)"));
+        assert!(text.sections[1]
+            .value
+            .starts_with("* PLAYER 2: 2 REGIONS (67%)"));
+    }
+
+    #[test]
+    fn no_hover_info_without_hovered_region() {
+        let mut app = App::new();
+        app.init_resource::<HoverInfo>()
+            .init_resource::<FogOfWar>()
+            .init_resource::<AiPlayers>()
+            .init_resource::<PlayerNames>()
+            .insert_resource(GameState::from_board(Board::from_regions(Vec::new()), 2))
+            .add_system(hover_info_update);
+
+        app.world.spawn((Interaction::None, Region::default()));
+        app.update();
+
+        assert!(app.world.resource::<HoverInfo>().text.is_none());
+    }
+
+    #[test]
+    fn no_hover_info_for_regions_in_fog() {
+        // Player 1 sees its neighbour, but not the region past it
+        let board = Board::from_regions(
+            [(0, 0), (1, 0), (2, 0)]
+                .into_iter()
+                .enumerate()
+                .map(|(id, coord)| Region::new(RegionId(id), PlayerId(id.min(1)), 3, vec![coord]))
+                .collect(),
+        );
+        let mut app = App::new();
+        app.init_resource::<HoverInfo>()
+            .insert_resource(FogOfWar(true))
+            .init_resource::<AiPlayers>()
+            .init_resource::<PlayerNames>()
+            .insert_resource(GameState::from_board(board.clone(), 2))
+            .add_system(hover_info_update);
+
+        let hovered = app
+            .world
+            .spawn((Interaction::Hovered, board.regions[2].clone()))
+            .id();
+        app.update();
+        let hover_info = app.world.resource::<HoverInfo>();
+        assert!(hover_info.region.is_none() && hover_info.text.is_none());
+
+        app.world.despawn(hovered);
+        app.world
+            .spawn((Interaction::Hovered, board.regions[1].clone()));
+        app.update();
+        assert_eq!(app.world.resource::<HoverInfo>().region, Some(RegionId(1)));
+    }
+
+    #[test]
+    fn region_tooltip_clears_when_cursor_leaves_regions() {
+        let board = Board::from_regions(
+            [(0, 0), (1, 0)]
+                .into_iter()
+                .enumerate()
+                .map(|(id, coord)| Region::new(RegionId(id), PlayerId(id), 3, vec![coord]))
+                .collect(),
+        );
+
+        let mut app = App::new();
+        app.init_resource::<ShowRegionTooltip>()
+            .init_resource::<PlayerNames>()
+            .insert_resource(HoverInfo {
+                region: Some(RegionId(0)),
+                text: None,
+            })
+            .insert_resource(GameState::from_board(board, 2))
+            .add_system(region_tooltip_update);
+        let tooltip = app
+            .world
+            .spawn((
+                Text::from_section("", TextStyle::default()),
+                Style::default(),
+                Visibility { is_visible: false },
+                RegionTooltipText,
+            ))
+            .id();
+
+        app.update();
+        let entity = app.world.entity(tooltip);
+        assert!(entity.get::<Visibility>().unwrap().is_visible);
+        assert_eq!(
+            entity.get::<Text>().unwrap().sections[0].value,
+            "PLAYER 1\n3 DICE\n1 HEXES\n1 ENEMY NEIGHBOURS"
+        );
+
+        app.world.resource_mut::<HoverInfo>().region = None;
+        app.update();
+        assert!(
+            !app.world
+                .entity(tooltip)
+                .get::<Visibility>()
+                .unwrap()
+                .is_visible
+        );
+    }
+
+    #[test]
+    fn players_without_a_name_are_numbered() {
+        let player_names = PlayerNames(vec!["Alice".to_string(), " ".to_string()]);
+
+        assert_eq!(player_names.name(PlayerId(0)), "Alice");
+        assert_eq!(player_names.name(PlayerId(1)), "PLAYER 2");
+        assert_eq!(player_names.name(PlayerId(2)), "PLAYER 3");
+    }
+}
@@ -0,0 +1,41 @@
+use stackrankdice::game::{Board, DiceCapRule, Region, RegionId, MAX_DICE};
+
+fn region(hexes: usize) -> Region {
+    Region {
+        hexes: (0..hexes as isize).map(|q| (q, 0)).collect(),
+        num_dice: 0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn stacking_dice_stops_at_the_cap() {
+    let mut region = region(1);
+
+    let overflow = region.add_dice(20);
+
+    assert_eq!(region.num_dice, MAX_DICE);
+    assert_eq!(region.num_dice, 8);
+    assert_eq!(overflow, 12);
+}
+
+#[test]
+fn dice_below_the_cap_all_fit() {
+    let mut region = region(1);
+    region.num_dice = 3;
+
+    assert_eq!(region.add_dice(4), 0);
+    assert_eq!(region.num_dice, 7);
+    assert_eq!(region.add_dice(0), 0);
+}
+
+#[test]
+fn board_applies_its_dice_cap_rule() {
+    let mut board = Board::from_regions(vec![region(2)]);
+    board.dice_cap_rule = DiceCapRule::RegionSize;
+
+    let overflow = board.add_dice(RegionId(0), 20);
+
+    assert_eq!(board.regions[0].num_dice, 4);
+    assert_eq!(overflow, 16);
+}
@@ -0,0 +1,64 @@
+use stackrankdice::game::{generate_board, BoardConfig, GameState, ReinforcementReserve, Rules};
+use stackrankdice::tiered_prng::get_randomness;
+
+#[test]
+fn adjacency_is_symmetric() {
+    for seed in 0..5 {
+        let board = generate_board(4, &BoardConfig::default(), get_randomness(seed));
+
+        for region in board.regions.iter() {
+            let neighbours = board.neighbours(region.id);
+            assert!(!neighbours.contains(&region.id));
+            assert!(neighbours.windows(2).all(|ids| ids[0] < ids[1]));
+            for other in neighbours.iter() {
+                assert!(board.neighbours(*other).contains(&region.id));
+            }
+        }
+    }
+}
+
+#[test]
+fn adjacency_matches_hexes() {
+    let board = generate_board(3, &BoardConfig::default(), get_randomness(7));
+
+    for region_1 in board.regions.iter() {
+        for region_2 in board.regions.iter() {
+            if region_1.id == region_2.id {
+                continue;
+            }
+            assert_eq!(
+                board.neighbours(region_1.id).contains(&region_2.id),
+                region_1.is_neighbour(region_2)
+            );
+            assert_eq!(
+                board.is_opponent(region_1.id, region_2.id),
+                region_1.owner != region_2.owner && region_1.is_neighbour(region_2)
+            );
+        }
+    }
+}
+
+#[test]
+fn loaded_game_has_adjacency() {
+    let board = generate_board(3, &BoardConfig::default(), get_randomness(7));
+    let game_state = GameState::from_board(board, 3);
+
+    let path = std::env::temp_dir().join(format!(
+        "stackrankdice-loaded_game_has_adjacency-{}-{:?}.json",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    game_state
+        .save_to_path(&Rules::default(), &ReinforcementReserve::default(), &path)
+        .unwrap();
+    let loaded = GameState::load_from_path(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let loaded = loaded.unwrap().game_state;
+    for region in game_state.board.regions.iter() {
+        assert_eq!(
+            loaded.board.neighbours(region.id),
+            game_state.board.neighbours(region.id)
+        );
+    }
+}
@@ -0,0 +1,23 @@
+use stackrankdice::game::RegionId;
+
+mod common;
+
+/// Region 0 of player 1 bordered by regions 1 (own), 2 and 3 (opponent),
+/// and a far opponent region 4
+fn board() -> stackrankdice::game::Board {
+    common::board(&[
+        ((0, 0), 0, 2),
+        ((1, 0), 0, 5),
+        ((-1, 0), 1, 3),
+        ((0, 1), 1, 4),
+        ((5, 5), 1, 8),
+    ])
+}
+
+#[test]
+fn sums_dice_of_bordering_opponents() {
+    let board = board();
+
+    assert_eq!(board.adjacent_enemy_dice(RegionId(0)), 3 + 4);
+    assert_eq!(board.adjacent_enemy_dice(RegionId(4)), 0);
+}
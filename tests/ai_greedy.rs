@@ -0,0 +1,52 @@
+use std::collections::HashSet;
+
+use stackrankdice::ai::{choose_move, AiStrategy};
+use stackrankdice::game::{GameState, RegionId};
+use stackrankdice::tiered_prng::get_randomness;
+
+mod common;
+
+/// Region 0 of player 1 bordered by regions 1, 2 and 3 of player 2
+fn game_state(dice: [usize; 4]) -> GameState {
+    let board = common::board(&[
+        ((0, 0), 0, dice[0]),
+        ((1, 0), 1, dice[1]),
+        ((-1, 0), 1, dice[2]),
+        ((0, 1), 1, dice[3]),
+    ]);
+    GameState::from_board(board, 2)
+}
+
+#[test]
+fn attacks_weakest_neighbour() {
+    let game_state = game_state([6, 5, 2, 4]);
+
+    for seed in 0..5 {
+        let chosen = choose_move(&game_state, &AiStrategy::Greedy, &mut get_randomness(seed));
+        assert_eq!(chosen, Some((RegionId(0), RegionId(2))));
+    }
+}
+
+#[test]
+fn ties_are_broken_randomly() {
+    let game_state = game_state([6, 2, 2, 2]);
+
+    let chosen: HashSet<(RegionId, RegionId)> = (0..20)
+        .map(|seed| {
+            choose_move(&game_state, &AiStrategy::Greedy, &mut get_randomness(seed)).unwrap()
+        })
+        .collect();
+
+    assert!(chosen.len() > 1);
+}
+
+#[test]
+fn passes_without_winnable_attack() {
+    // A single dice can't beat a sum of at least 7
+    let game_state = game_state([1, 7, 8, 8]);
+
+    assert_eq!(
+        choose_move(&game_state, &AiStrategy::Greedy, &mut get_randomness(0)),
+        None
+    );
+}
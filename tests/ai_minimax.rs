@@ -0,0 +1,63 @@
+use stackrankdice::ai::{best_move_minimax, board_value};
+use stackrankdice::game::{generate_board, BoardConfig, GameState, PlayerId, RegionId};
+use stackrankdice::tiered_prng::get_randomness;
+
+mod common;
+
+/// Region 0 of player 1 bordered by regions 1, 2 and 3 of player 2
+fn star(dice: [usize; 4]) -> GameState {
+    let board = common::board(&[
+        ((0, 0), 0, dice[0]),
+        ((1, 0), 1, dice[1]),
+        ((-1, 0), 1, dice[2]),
+        ((0, 1), 1, dice[3]),
+    ]);
+    GameState::from_board(board, 2)
+}
+
+#[test]
+fn board_value_counts_regions_and_largest_group() {
+    // A row of regions: player 1, player 2, player 2, player 1
+    let game_state = common::row_game(&[(0, 1), (1, 1), (1, 1), (0, 1)], 2);
+
+    assert_eq!(board_value(&game_state, PlayerId(0)), 2.0 + 1.0);
+    assert_eq!(board_value(&game_state, PlayerId(1)), 2.0 + 2.0);
+}
+
+#[test]
+fn depths_agree_on_single_winnable_attack() {
+    // A single dice only has a chance against another single dice
+    let game_state = star([1, 6, 6, 1]);
+
+    assert_eq!(
+        best_move_minimax(&game_state, 0),
+        Some((RegionId(0), RegionId(3)))
+    );
+    assert_eq!(
+        best_move_minimax(&game_state, 1),
+        Some((RegionId(0), RegionId(3)))
+    );
+}
+
+#[test]
+fn passes_without_winnable_attack() {
+    let game_state = star([1, 6, 6, 6]);
+
+    assert_eq!(best_move_minimax(&game_state, 2), None);
+}
+
+#[test]
+fn deep_search_picks_attack_of_current_player() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(7));
+    let game_state = GameState::from_board(board, 2);
+
+    let (region_1, region_2) = best_move_minimax(&game_state, 3).unwrap();
+    let regions = &game_state.board.regions;
+
+    assert_eq!(regions[region_1].owner, game_state.turn_of_player);
+    assert!(game_state.board.is_opponent(region_1, region_2));
+    assert_eq!(
+        best_move_minimax(&game_state, 3),
+        Some((region_1, region_2))
+    );
+}
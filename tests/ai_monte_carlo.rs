@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+
+use stackrankdice::ai::{choose_move, choose_move_with_rules, AiStrategy};
+use stackrankdice::game::{
+    generate_board, BoardConfig, DiceKind, GameState, MinAttackDice, PlayerId, PlayerRegions,
+    RegionId, Rules, TieRule,
+};
+use stackrankdice::tiered_prng::get_randomness;
+
+mod common;
+
+fn game_state(seed: u64) -> GameState {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(seed));
+
+    GameState::from_board(board, 2)
+}
+
+#[test]
+fn same_seed_and_budget_choose_same_move() {
+    let strategy = AiStrategy::MonteCarlo { rollouts: 100 };
+
+    for seed in 0..5 {
+        let game_state = game_state(seed);
+
+        let move_1 = choose_move(&game_state, &strategy, &mut get_randomness(seed));
+        let move_2 = choose_move(&game_state, &strategy, &mut get_randomness(seed));
+
+        assert!(move_1.is_some());
+        assert_eq!(move_1, move_2);
+    }
+}
+
+#[test]
+fn chosen_move_is_an_attack_of_current_player() {
+    let strategy = AiStrategy::MonteCarlo { rollouts: 50 };
+    let game_state = game_state(7);
+
+    let (region_1, region_2) = choose_move(&game_state, &strategy, &mut get_randomness(7)).unwrap();
+    let regions = &game_state.board.regions;
+
+    assert_eq!(regions[region_1].owner, game_state.turn_of_player);
+    assert!(game_state.board.is_opponent(region_1, region_2));
+}
+
+#[test]
+fn no_move_without_opponents() {
+    let mut game_state = game_state(3);
+    let strategy = AiStrategy::MonteCarlo { rollouts: 0 };
+    assert!(choose_move(&game_state, &strategy, &mut get_randomness(3)).is_some());
+
+    for region in game_state.board.regions.iter_mut() {
+        region.owner = PlayerId(0);
+    }
+    game_state.player_regions = PlayerRegions::from_board(&game_state.board);
+    assert!(choose_move(&game_state, &strategy, &mut get_randomness(3)).is_none());
+}
+
+#[test]
+fn ties_are_broken_by_ai_seed() {
+    // Without playouts every attack scores the same
+    let strategy = AiStrategy::MonteCarlo { rollouts: 0 };
+    let game_state = game_state(11);
+
+    let moves: HashSet<(RegionId, RegionId)> = (0..10)
+        .map(|seed| {
+            let move_1 = choose_move(&game_state, &strategy, &mut get_randomness(seed));
+            let move_2 = choose_move(&game_state, &strategy, &mut get_randomness(seed));
+            assert_eq!(move_1, move_2);
+            move_1.unwrap()
+        })
+        .collect();
+
+    assert!(moves.len() > 1);
+}
+
+#[test]
+fn playouts_follow_the_tie_rule() {
+    // Two-sided dice tie often. The region on the left is an easy catch, the one on
+    // the right is fought over and worth more, but only a fair bet if ties go to the attacker.
+    let mut game_state = common::row_game(&[(1, 1), (0, 2), (1, 2)], 2);
+    game_state.dice_kind = DiceKind::new(2).unwrap();
+    for _ in 0..3 {
+        game_state.board.heat_up(RegionId(2));
+    }
+    let strategy = AiStrategy::MonteCarlo { rollouts: 4000 };
+
+    let choose = |tie_rule| {
+        let rules = Rules {
+            min_attack_dice: MinAttackDice(2),
+            tie_rule,
+            ..Default::default()
+        };
+        choose_move_with_rules(&game_state, &strategy, &rules, &mut get_randomness(5))
+    };
+
+    assert_eq!(
+        choose(TieRule::DefenderWins),
+        Some((RegionId(1), RegionId(0)))
+    );
+    assert_eq!(
+        choose(TieRule::AttackerWins),
+        Some((RegionId(1), RegionId(2)))
+    );
+}
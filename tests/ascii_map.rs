@@ -0,0 +1,65 @@
+use stackrankdice::error::GameError;
+use stackrankdice::game::{Board, RegionId};
+
+const MAP: &str = "\
+0 0 1 1 .
+ 0 . 1 2 2
+0 0 . 2 2
+ . 1 1 . 2
+";
+
+#[test]
+fn map_round_trips() {
+    let board = Board::from_ascii(MAP).unwrap();
+
+    assert_eq!(board.to_ascii(), MAP);
+    assert_eq!(
+        Board::from_ascii(&board.to_ascii()).unwrap().regions,
+        board.regions
+    );
+}
+
+#[test]
+fn contiguous_hexes_of_an_owner_make_a_region() {
+    let board = Board::from_ascii(MAP).unwrap();
+
+    let owners: Vec<usize> = board.regions.iter().map(|r| r.owner.0).collect();
+    let sizes: Vec<usize> = board.regions.iter().map(|r| r.hexes.len()).collect();
+    assert_eq!(owners, vec![0, 1, 2, 1]);
+    assert_eq!(sizes, vec![5, 3, 5, 2]);
+    assert_eq!(board.hex_count(), 15);
+
+    for (id, region) in board.regions.iter().enumerate() {
+        assert_eq!(region.id, RegionId(id));
+        assert_eq!(region.num_dice, 1);
+        assert!(region.is_contiguous());
+    }
+    assert!(board.validate(3).is_ok());
+}
+
+#[test]
+fn regions_border_on_the_offset_grid() {
+    let board = Board::from_ascii(MAP).unwrap();
+
+    // Odd rows are shifted right, so the top right region touches the lower 2s
+    assert!(board.neighbours(RegionId(1)).contains(&RegionId(2)));
+    assert!(board.neighbours(RegionId(0)).contains(&RegionId(3)));
+    assert!(!board.neighbours(RegionId(1)).contains(&RegionId(3)));
+}
+
+#[test]
+fn compact_maps_are_accepted() {
+    let compact = MAP.replace(' ', "");
+
+    let board = Board::from_ascii(&compact).unwrap();
+
+    assert_eq!(board.to_ascii(), MAP);
+}
+
+#[test]
+fn unexpected_characters_are_rejected() {
+    assert_eq!(
+        Board::from_ascii("0 0\n 1 x").err(),
+        Some(GameError::InvalidMap { row: 1, column: 1 })
+    );
+}
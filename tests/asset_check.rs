@@ -0,0 +1,62 @@
+#![cfg(feature = "render")]
+
+use std::path::{Path, PathBuf};
+
+use bevy::asset::{AssetIo, AssetIoError, BoxedFuture, FileAssetIo, FileType, Metadata};
+use stackrankdice::assets::{missing_assets, DICE_ASSETS};
+
+/// Asset IO which only knows about a fixed list of files
+struct MockAssetIo {
+    files: Vec<PathBuf>,
+}
+
+impl AssetIo for MockAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move { Err(AssetIoError::NotFound(path.to_path_buf())) })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        Err(AssetIoError::NotFound(path.to_path_buf()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        if self.files.iter().any(|file| file == path) {
+            Ok(Metadata::new(FileType::File))
+        } else {
+            Err(AssetIoError::NotFound(path.to_path_buf()))
+        }
+    }
+
+    fn watch_path_for_changes(&self, _path: &Path) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn reports_missing_dice_texture() {
+    let asset_io = MockAssetIo {
+        files: vec![
+            PathBuf::from("models/dice/scene.gltf"),
+            PathBuf::from("models/dice/textures/Dice_baseColor.png"),
+            PathBuf::from("models/dice/textures/Dice_metallicRoughness.png"),
+        ],
+    };
+
+    let missing = missing_assets(&asset_io, &DICE_ASSETS);
+
+    assert_eq!(missing, vec!["models/dice/textures/Dice_normal.png"]);
+}
+
+#[test]
+fn shipped_dice_assets_are_present() {
+    let asset_io = FileAssetIo::new("assets", false);
+
+    assert!(missing_assets(&asset_io, &DICE_ASSETS).is_empty());
+}
@@ -0,0 +1,30 @@
+use stackrankdice::game::{generate_board, BoardConfig};
+use stackrankdice::tiered_prng::get_randomness;
+
+#[test]
+fn default_config_covers_about_half_of_the_board() {
+    let config = BoardConfig::default();
+
+    for number_of_players in [2, 4] {
+        for seed in 0..10 {
+            let board = generate_board(number_of_players, &config, get_randomness(seed));
+            let coverage = board.coverage(&config);
+
+            assert!(
+                (0.4..0.75).contains(&coverage),
+                "seed {} with {} players: coverage {}",
+                seed,
+                number_of_players,
+                coverage
+            );
+        }
+    }
+}
+
+#[test]
+fn hex_count_matches_region_hexes() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(3));
+    let region_hexes: usize = board.regions.iter().map(|r| r.hexes.len()).sum();
+
+    assert_eq!(board.hex_count(), region_hexes);
+}
@@ -0,0 +1,35 @@
+use stackrankdice::game::{generate_board, BoardConfig, RegionId};
+use stackrankdice::tiered_prng::get_randomness;
+
+#[test]
+fn generated_boards_never_exceed_max_regions() {
+    for max_regions in [1, 5, 17, 32] {
+        for seed in 0..10 {
+            let config = BoardConfig {
+                max_regions,
+                ..Default::default()
+            };
+            let board = generate_board(2, &config, get_randomness(seed));
+
+            assert!(board.regions.len() <= max_regions);
+            assert!(!board.regions.is_empty());
+            for (id, region) in board.regions.iter().enumerate() {
+                assert_eq!(region.id, RegionId(id));
+                assert!(region.num_dice >= 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn default_config_does_not_truncate_board() {
+    let unbounded = BoardConfig {
+        max_regions: usize::MAX,
+        ..Default::default()
+    };
+
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(4242));
+    let unbounded_board = generate_board(2, &unbounded, get_randomness(4242));
+
+    assert_eq!(board.regions.len(), unbounded_board.regions.len());
+}
@@ -0,0 +1,121 @@
+use stackrankdice::error::GameError;
+use stackrankdice::game::{
+    generate_board, generate_board_parallel, generate_playable_board, generation_threads,
+    try_generate_board, BoardConfig, BoardPreview, GameState, MAX_BOARD_ATTEMPTS,
+};
+use stackrankdice::tiered_prng::get_randomness;
+
+/// Smallest board crowded with as many patches as validation lets through
+fn crowded() -> BoardConfig {
+    BoardConfig {
+        board_size: 6,
+        number_of_patches: 4,
+        patch_size_divisor: 1,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn crowded_board_succeeds_or_fails_without_hanging() {
+    let config = crowded();
+    config.validate(8).unwrap();
+
+    for seed in 0..20 {
+        match try_generate_board(8, &config, get_randomness(seed)) {
+            Ok(board) => board.validate(8).unwrap(),
+            Err(err) => assert!(
+                matches!(err, GameError::BoardGenerationFailed { .. }),
+                "unexpected error {} on seed {}",
+                err,
+                seed
+            ),
+        }
+    }
+}
+
+#[test]
+fn invalid_config_is_an_error() {
+    let config = BoardConfig {
+        board_size: 2,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        try_generate_board(2, &config, get_randomness(1)),
+        Err(GameError::InvalidBoardConfig(_))
+    ));
+}
+
+#[test]
+fn parallel_generation_keeps_board_of_world_seed() {
+    let config = BoardConfig::default();
+    let board = generate_board(4, &config, get_randomness(42));
+    let parallel = generate_board_parallel(4, &config, 42, 4).unwrap();
+
+    assert_eq!(parallel.regions, board.regions);
+    assert_eq!(parallel.hexes, board.hexes);
+}
+
+#[test]
+fn parallel_generation_of_crowded_board_returns() {
+    let config = crowded();
+
+    if let Ok(board) = generate_board_parallel(8, &config, 3, 4) {
+        board.validate(8).unwrap();
+    }
+}
+
+#[test]
+fn single_thread_generation_tries_world_seed_only() {
+    let config = crowded();
+
+    for seed in 0..5 {
+        let board = try_generate_board(8, &config, get_randomness(seed));
+        let parallel = generate_board_parallel(8, &config, seed, 1);
+
+        assert_eq!(parallel.is_ok(), board.is_ok());
+        if let (Ok(parallel), Ok(board)) = (parallel, board) {
+            assert_eq!(parallel.regions, board.regions);
+        }
+    }
+}
+
+#[test]
+fn playable_board_lets_every_player_attack() {
+    for seed in 0..10 {
+        match generate_playable_board(4, &BoardConfig::default(), seed, 1) {
+            Ok(board) => {
+                let game_state = GameState::from_board(board, 4);
+                assert_eq!(game_state.players_with_moves().len(), 4);
+            }
+            Err(err) => assert_eq!(
+                err,
+                GameError::NoPlayableBoard {
+                    attempts: MAX_BOARD_ATTEMPTS
+                }
+            ),
+        }
+    }
+}
+
+#[test]
+fn preview_shows_the_playable_board_of_its_seed() {
+    let config = BoardConfig::default();
+    let preview = BoardPreview::new(42, 3, &config).unwrap();
+    let board = generate_playable_board(3, &config, 42, generation_threads()).unwrap();
+
+    assert_eq!(preview.board.regions, board.regions);
+}
+
+#[test]
+fn preview_of_invalid_config_is_an_error() {
+    let config = BoardConfig {
+        board_size: 2,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        BoardPreview::new(42, 2, &config),
+        Err(GameError::InvalidBoardConfig(_))
+    ));
+}
@@ -0,0 +1,44 @@
+use std::collections::HashSet;
+
+use stackrankdice::game::{generate_board, Board, BoardConfig, RegionId};
+use stackrankdice::tiered_prng::get_randomness;
+
+fn all_regions_reachable(board: &Board) -> bool {
+    let mut reached = HashSet::from([RegionId(0)]);
+    let mut frontier = vec![RegionId(0)];
+    while let Some(current) = frontier.pop() {
+        for &neighbour in board.neighbours(current) {
+            if reached.insert(neighbour) {
+                frontier.push(neighbour);
+            }
+        }
+    }
+
+    reached.len() == board.regions.len()
+}
+
+#[test]
+fn regions_grow_around_holes() {
+    let config = BoardConfig {
+        holes: 12,
+        ..Default::default()
+    };
+
+    for seed in 0..10 {
+        let board = generate_board(2, &config, get_randomness(seed));
+
+        assert_eq!(board.holes.len(), 12, "seed {}", seed);
+        for hole in board.holes.iter() {
+            assert!(!board.hexes.contains_key(hole), "seed {}: {:?}", seed, hole);
+            assert!(hole.0.abs() < 10 && hole.1.abs() < 10, "seed {}", seed);
+        }
+        assert!(board.validate(2).is_ok(), "seed {}", seed);
+        assert!(all_regions_reachable(&board), "seed {}", seed);
+    }
+}
+
+#[test]
+fn no_holes_by_default() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(4242));
+    assert!(board.holes.is_empty());
+}
@@ -0,0 +1,54 @@
+use stackrankdice::error::GameError;
+use stackrankdice::game::{generate_board, Board, BoardConfig};
+use stackrankdice::tiered_prng::get_randomness;
+
+const MAP: &str = "\
+0 0 1 1
+ 0 2 1 2
+";
+
+#[test]
+fn generated_boards_are_valid() {
+    for number_of_players in 2..=4 {
+        for seed in 0..10 {
+            let board = generate_board(
+                number_of_players,
+                &BoardConfig::default(),
+                get_randomness(seed),
+            );
+
+            assert!(board.ids_match_indices());
+            board.validate(number_of_players).unwrap();
+        }
+    }
+}
+
+#[test]
+fn corrupted_id_fails_validation() {
+    let mut board = Board::from_ascii(MAP).unwrap();
+    assert!(board.ids_match_indices());
+
+    board.regions.swap(0, 1);
+
+    assert!(!board.ids_match_indices());
+    assert_eq!(
+        board.validate(3),
+        Err(GameError::InvalidBoard {
+            region: 0,
+            reason: "region id doesn't match its index"
+        })
+    );
+}
+
+#[test]
+fn owner_and_dice_out_of_range_fail_validation() {
+    let mut board = Board::from_ascii(MAP).unwrap();
+    assert!(board.validate(2).is_err());
+    assert!(board.validate(3).is_ok());
+
+    board.regions[1].num_dice = 0;
+    assert!(matches!(
+        board.validate(3),
+        Err(GameError::InvalidBoard { region: 1, .. })
+    ));
+}
@@ -0,0 +1,46 @@
+use stackrankdice::error::GameError;
+use stackrankdice::game::{generate_board, try_generate_board, BoardConfig, PlayerId};
+use stackrankdice::tiered_prng::get_randomness;
+
+fn mirrored() -> BoardConfig {
+    BoardConfig {
+        mirror: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn mirrored_two_player_board_is_point_symmetric() {
+    for seed in 0..5 {
+        let board = generate_board(2, &mirrored(), get_randomness(seed));
+
+        assert!(board.is_point_symmetric(), "seed {}", seed);
+        board.validate(2).unwrap();
+
+        let dice_of = |player: usize| -> usize {
+            board
+                .regions
+                .iter()
+                .filter(|region| region.owner == PlayerId(player))
+                .map(|region| region.num_dice)
+                .sum()
+        };
+        assert!(dice_of(0) > 0);
+        assert_eq!(dice_of(0), dice_of(1));
+    }
+}
+
+#[test]
+fn random_board_is_not_point_symmetric() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(4242));
+
+    assert!(!board.is_point_symmetric());
+}
+
+#[test]
+fn mirrored_board_needs_even_number_of_players() {
+    assert!(matches!(
+        try_generate_board(3, &mirrored(), get_randomness(4242)),
+        Err(GameError::InvalidBoardConfig(_))
+    ));
+}
@@ -0,0 +1,48 @@
+use stackrankdice::game::{generate_board, BoardConfig, BoardPreview, PlayerId, Region};
+use stackrankdice::tiered_prng::get_randomness;
+
+/// Hexes, owner and dice of every region
+type Layout = Vec<(Vec<(isize, isize)>, PlayerId, usize)>;
+
+fn layout(regions: &[Region]) -> Layout {
+    regions
+        .iter()
+        .map(|r| (r.hexes.clone(), r.owner, r.num_dice))
+        .collect()
+}
+
+#[test]
+fn reroll_gives_another_board() {
+    let mut preview = BoardPreview::new(42, 2, &BoardConfig::default()).unwrap();
+    let first = layout(&preview.board.regions);
+
+    preview.reroll().unwrap();
+
+    assert_ne!(preview.world_seed, 42);
+    assert_ne!(layout(&preview.board.regions), first);
+}
+
+#[test]
+fn rerolls_are_deterministic() {
+    let mut preview_1 = BoardPreview::new(7, 3, &BoardConfig::default()).unwrap();
+    let mut preview_2 = BoardPreview::new(7, 3, &BoardConfig::default()).unwrap();
+    for _ in 0..3 {
+        preview_1.reroll().unwrap();
+        preview_2.reroll().unwrap();
+    }
+
+    assert_eq!(preview_1.world_seed, preview_2.world_seed);
+}
+
+#[test]
+fn commit_uses_previewed_seed() {
+    let config = BoardConfig::default();
+    let mut preview = BoardPreview::new(42, 2, &config).unwrap();
+    preview.reroll().unwrap();
+    let previewed = layout(&preview.board.regions);
+
+    let world_seed = preview.commit();
+    let board = generate_board(2, &config, get_randomness(world_seed));
+
+    assert_eq!(layout(&board.regions), previewed);
+}
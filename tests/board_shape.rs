@@ -0,0 +1,106 @@
+use stackrankdice::game::{generate_board, BoardConfig, BoardShape};
+use stackrankdice::hex::HexCoord;
+use stackrankdice::tiered_prng::get_randomness;
+
+fn shaped(shape: BoardShape) -> BoardConfig {
+    BoardConfig {
+        shape,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn hexagon_board_stays_within_radius() {
+    let config = shaped(BoardShape::Hexagon { radius: 6 });
+    config.validate(2).unwrap();
+
+    for seed in 0..5 {
+        let board = generate_board(2, &config, get_randomness(seed));
+        assert!(!board.regions.is_empty());
+
+        for region in board.regions.iter() {
+            for hex in region.hexes.iter() {
+                let distance = HexCoord::new(hex.0, hex.1).distance(&HexCoord::origin());
+                assert!(distance <= 6, "{:?} lies outside on seed {}", hex, seed);
+            }
+        }
+        board.validate(2).unwrap();
+    }
+}
+
+#[test]
+fn rectangle_board_stays_within_its_sides() {
+    let config = shaped(BoardShape::Rectangle { w: 24, h: 10 });
+    config.validate(3).unwrap();
+
+    for seed in 0..5 {
+        let board = generate_board(3, &config, get_randomness(seed));
+
+        for hex in board.hexes.keys() {
+            assert!((-12..12).contains(&hex.0), "{:?} on seed {}", hex, seed);
+            assert!((-5..5).contains(&hex.1), "{:?} on seed {}", hex, seed);
+        }
+        board.validate(3).unwrap();
+    }
+}
+
+#[test]
+fn tiny_shapes_are_rejected() {
+    assert!(shaped(BoardShape::Hexagon { radius: 1 })
+        .validate(2)
+        .is_err());
+    assert!(shaped(BoardShape::Rectangle { w: 40, h: 2 })
+        .validate(2)
+        .is_err());
+}
+
+#[test]
+fn area_follows_the_shape() {
+    assert_eq!(shaped(BoardShape::Square).board_area(), 400);
+    assert_eq!(
+        shaped(BoardShape::Rectangle { w: 24, h: 10 }).board_area(),
+        240
+    );
+    assert_eq!(shaped(BoardShape::Hexagon { radius: 6 }).board_area(), 127);
+}
+
+#[test]
+fn lakes_lie_inside_the_shape() {
+    for shape in [
+        BoardShape::Hexagon { radius: 6 },
+        BoardShape::Rectangle { w: 24, h: 10 },
+    ] {
+        let config = BoardConfig {
+            holes: 6,
+            ..shaped(shape)
+        };
+
+        for seed in 0..5 {
+            let board = generate_board(2, &config, get_randomness(seed));
+
+            assert_eq!(board.holes.len(), 6);
+            for (q, r) in board.holes.iter() {
+                assert!(HexCoord::new(*q, *r)
+                    .neighbors()
+                    .all(|hex| shape.contains((hex.q, hex.r))));
+            }
+        }
+    }
+}
+
+#[test]
+fn shaped_boards_ignore_board_size() {
+    let config = BoardConfig {
+        board_size: 0,
+        ..shaped(BoardShape::Hexagon { radius: 6 })
+    };
+
+    assert!(config.validate(2).is_ok());
+    assert!(shaped(BoardShape::Square).validate(2).is_ok());
+    assert!(BoardConfig {
+        board_size: 4,
+        ..shaped(BoardShape::Square)
+    }
+    .validate(2)
+    .is_err());
+}
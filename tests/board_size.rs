@@ -0,0 +1,70 @@
+use stackrankdice::error::GameError;
+use stackrankdice::game::{generate_board, BoardConfig, PlayerId};
+use stackrankdice::tiered_prng::get_randomness;
+
+fn sized(board_size: isize) -> BoardConfig {
+    BoardConfig {
+        board_size,
+        ..Default::default()
+    }
+}
+
+fn assert_every_player_has_a_region(config: &BoardConfig, number_of_players: usize) {
+    for seed in 0..5 {
+        let board = generate_board(number_of_players, config, get_randomness(seed));
+
+        for player in 0..number_of_players {
+            assert!(
+                board.regions.iter().any(|r| r.owner == PlayerId(player)),
+                "player {} has no regions on seed {}",
+                player,
+                seed
+            );
+        }
+    }
+}
+
+#[test]
+fn tiny_board_has_regions_for_every_player() {
+    let config = sized(8);
+    config.validate(2).unwrap();
+
+    assert_every_player_has_a_region(&config, 2);
+}
+
+#[test]
+fn large_board_has_regions_for_every_player() {
+    let config = sized(40);
+
+    for number_of_players in [2, 4, 8] {
+        config.validate(number_of_players).unwrap();
+        assert_every_player_has_a_region(&config, number_of_players);
+    }
+}
+
+#[test]
+fn large_board_covers_more_hexes() {
+    let default_board = generate_board(2, &BoardConfig::default(), get_randomness(1));
+    let large_board = generate_board(2, &sized(40), get_randomness(1));
+
+    assert!(large_board.hex_count() > default_board.hex_count() * 2);
+}
+
+#[test]
+fn too_many_patches_are_rejected() {
+    assert!(matches!(
+        sized(8).validate(4),
+        Err(GameError::InvalidBoardConfig(_))
+    ));
+    assert!(matches!(
+        sized(4).validate(2),
+        Err(GameError::InvalidBoardConfig(_))
+    ));
+    assert!(BoardConfig::default().validate(8).is_ok());
+}
+
+#[test]
+#[should_panic]
+fn generating_an_invalid_board_panics() {
+    generate_board(8, &sized(8), get_randomness(1));
+}
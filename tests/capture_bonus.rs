@@ -0,0 +1,48 @@
+use stackrankdice::game::{CaptureBonus, GameAction, GameLogEntry, GameState, PlayerId, RegionId};
+
+mod common;
+
+fn game_state_after_attack(attacker_won: bool) -> GameState {
+    let mut game_state = common::row_game(&[(0, 3), (1, 3)], 2);
+
+    game_state.game_log.push(GameLogEntry {
+        region_1_dice_result: vec![6, 6, 6],
+        region_2_dice_result: vec![if attacker_won { 1 } else { 6 }; 3],
+        attacker_won,
+        is_tie: !attacker_won,
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            0,
+            PlayerId(0),
+            game_state.board.regions[0].clone(),
+            game_state.board.regions[1].clone(),
+        )
+    });
+    game_state
+}
+
+#[test]
+fn capture_grants_another_attack() {
+    let game_state = game_state_after_attack(true);
+
+    assert!(game_state.can_attack(RegionId(0), CaptureBonus::ExtraAction));
+    assert!(!game_state.can_attack(RegionId(0), CaptureBonus::Disabled));
+}
+
+#[test]
+fn failed_attack_grants_nothing() {
+    let game_state = game_state_after_attack(false);
+
+    assert!(!game_state.can_attack(RegionId(0), CaptureBonus::ExtraAction));
+    assert!(!game_state.can_attack(RegionId(0), CaptureBonus::Disabled));
+}
+
+#[test]
+fn fortify_is_not_a_capture() {
+    let mut game_state = game_state_after_attack(true);
+    game_state.game_log.clear();
+    game_state.fortify(RegionId(0)).unwrap();
+
+    assert!(!game_state.can_attack(RegionId(0), CaptureBonus::ExtraAction));
+    assert!(game_state.can_attack(RegionId(1), CaptureBonus::ExtraAction));
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "render")]
+
+use stackrankdice::game::{GameAction, GameLogEntry, PlayerId, Region, RegionId};
+use stackrankdice::stats::{longest_capture_chain, CaptureChain, SessionStats};
+
+fn log_entry(turn_counter: usize, turn_of_player: usize, attacker_won: bool) -> GameLogEntry {
+    let region = Region::new(RegionId(0), PlayerId(turn_of_player), 1, Vec::new());
+
+    GameLogEntry {
+        region_1_dice_result: vec![1],
+        region_2_dice_result: vec![1],
+        attacker_won,
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            turn_counter,
+            PlayerId(turn_of_player),
+            region.clone(),
+            region,
+        )
+    }
+}
+
+#[test]
+fn longest_chain_from_crafted_log() {
+    let game_log = vec![
+        log_entry(0, 0, true),
+        log_entry(0, 0, false),
+        log_entry(0, 0, true),
+        log_entry(1, 1, true),
+        log_entry(1, 1, true),
+        log_entry(1, 1, true),
+        log_entry(1, 1, false),
+        log_entry(2, 0, true),
+        log_entry(2, 0, true),
+        log_entry(2, 0, true),
+    ];
+
+    // Turn 2 ties with turn 1, the earlier one is kept
+    assert_eq!(
+        longest_capture_chain(&game_log),
+        Some(CaptureChain {
+            player: PlayerId(1),
+            turn_counter: 1,
+            captures: 3,
+        })
+    );
+}
+
+#[test]
+fn no_chain_without_captures() {
+    assert_eq!(longest_capture_chain(&[]), None);
+    assert_eq!(longest_capture_chain(&[log_entry(0, 0, false)]), None);
+}
+
+#[test]
+fn session_keeps_longest_chain_across_games() {
+    let mut stats = SessionStats::default();
+
+    stats.record_game(&[log_entry(0, 0, true), log_entry(0, 0, true)], PlayerId(0));
+    stats.record_game(&[log_entry(0, 1, true)], PlayerId(1));
+
+    assert_eq!(stats.longest_capture_chain.unwrap().captures, 2);
+    assert_eq!(stats.longest_capture_chain.unwrap().player, PlayerId(0));
+}
@@ -0,0 +1,43 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{GameState, PlayerId, RegionId};
+
+mod common;
+
+/// A row of three regions: player 1, player 2, player 2
+fn game_state() -> GameState {
+    common::row_game(&[(0, 4), (1, 4), (1, 4)], 2)
+}
+
+#[test]
+fn region_captured_twice_counts_both_captures() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let mut game_state = game_state();
+    assert_eq!(game_state.board.most_contested_region(), None);
+
+    // Player 1 takes the middle region, then player 2 takes it back
+    game_state
+        .apply_clash(RegionId(0), RegionId(1), true, &mut rng)
+        .unwrap();
+    game_state
+        .apply_clash(RegionId(2), RegionId(1), true, &mut rng)
+        .unwrap();
+
+    assert_eq!(game_state.board.regions[1].owner, PlayerId(1));
+    assert_eq!(game_state.board.regions[1].capture_count, 2);
+    assert_eq!(game_state.board.regions[0].capture_count, 0);
+    assert_eq!(game_state.board.most_contested_region(), Some(RegionId(1)));
+}
+
+#[test]
+fn failed_attack_counts_as_capture_of_the_attacker() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let mut game_state = game_state();
+
+    game_state
+        .apply_clash(RegionId(0), RegionId(1), false, &mut rng)
+        .unwrap();
+
+    assert_eq!(game_state.board.regions[0].capture_count, 1);
+    assert_eq!(game_state.board.most_contested_region(), Some(RegionId(0)));
+}
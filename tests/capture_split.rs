@@ -0,0 +1,48 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{GameState, PlayerId, RegionId, Rules};
+
+mod common;
+
+/// A chain of three regions of player 0, with a region of player 1 bordering its middle
+fn game_state() -> GameState {
+    let board = common::board(&[
+        ((0, 0), 0, 3),
+        ((1, 0), 0, 3),
+        ((2, 0), 0, 3),
+        ((1, -1), 1, 3),
+    ]);
+    let mut game_state = GameState::from_board(board, 2);
+    game_state.turn_of_player = PlayerId(1);
+    game_state
+}
+
+#[test]
+fn capturing_middle_of_a_chain_splits_it() {
+    let mut game_state = game_state();
+    let mut rng = ChaCha20Rng::seed_from_u64(1);
+    assert!(game_state.board.is_connected(PlayerId(0)));
+
+    let capture_result = game_state
+        .resolve_clash(RegionId(3), RegionId(1), true, &Rules::default(), &mut rng)
+        .unwrap();
+
+    assert!(capture_result.split_occurred);
+    assert_eq!(capture_result.losing_player, PlayerId(0));
+    assert!(!game_state.board.is_connected(PlayerId(0)));
+    assert!(game_state.board.is_connected(PlayerId(1)));
+}
+
+#[test]
+fn losing_a_defence_doesnt_split_the_attacker() {
+    let mut game_state = game_state();
+    let mut rng = ChaCha20Rng::seed_from_u64(1);
+
+    let capture_result = game_state
+        .resolve_clash(RegionId(3), RegionId(1), false, &Rules::default(), &mut rng)
+        .unwrap();
+
+    assert!(!capture_result.split_occurred);
+    assert_eq!(capture_result.losing_player, PlayerId(1));
+    assert!(game_state.board.is_connected(PlayerId(0)));
+}
@@ -0,0 +1,55 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{GameState, PlayerId, RegionId};
+
+mod common;
+
+fn game_state(attacker_dice: usize, defender_dice: usize) -> GameState {
+    common::row_game(&[(0, attacker_dice), (1, defender_dice)], 2)
+}
+
+#[test]
+fn captured_region_receives_transferred_dice() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+
+    for _ in 0..100 {
+        let mut game_state = game_state(8, 3);
+        let transfer = game_state
+            .apply_clash(RegionId(0), RegionId(1), true, &mut rng)
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(transfer.from_region, RegionId(0));
+        assert_eq!(transfer.to_region, RegionId(1));
+        assert_eq!(transfer.num_dice, game_state.board.regions[1].num_dice);
+        assert!(transfer.num_dice >= 1 && transfer.num_dice < 8);
+        assert_eq!(game_state.board.regions[1].owner, PlayerId(0));
+    }
+}
+
+#[test]
+fn failed_attack_transfers_dice_to_attacking_region() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let mut game_state = game_state(2, 5);
+
+    let transfer = game_state
+        .apply_clash(RegionId(0), RegionId(1), false, &mut rng)
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(transfer.from_region, RegionId(1));
+    assert_eq!(transfer.to_region, RegionId(0));
+    assert_eq!(transfer.num_dice, game_state.board.regions[0].num_dice);
+    assert_eq!(game_state.board.regions[0].owner, PlayerId(1));
+}
+
+#[test]
+fn single_dice_winner_transfers_nothing() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let mut game_state = game_state(1, 1);
+
+    assert_eq!(
+        game_state.apply_clash(RegionId(0), RegionId(1), true, &mut rng),
+        Ok(None)
+    );
+}
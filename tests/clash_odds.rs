@@ -0,0 +1,46 @@
+use stackrankdice::game::Region;
+use stackrankdice::odds::clash_win_probability;
+
+#[test]
+fn single_dice_against_single_dice() {
+    // 15 of 36 outcomes have the first dice higher
+    assert!((clash_win_probability(1, 1) - 15.0 / 36.0).abs() < 1e-12);
+}
+
+#[test]
+fn single_dice_against_two_dice() {
+    // Face 3 beats a sum of 2, 4 beats sums up to 3, and so on
+    let p = (1.0 + 3.0 + 6.0 + 10.0) / 216.0;
+    assert!((clash_win_probability(1, 2) - p).abs() < 1e-12);
+}
+
+#[test]
+fn equal_dice_are_slightly_under_even() {
+    for n in 1..=8 {
+        let p = clash_win_probability(n, n);
+        assert!(p < 0.5 && p > 0.4, "{} dice: {}", n, p);
+    }
+}
+
+#[test]
+fn impossible_and_certain_clashes() {
+    assert_eq!(clash_win_probability(1, 6), 0.0);
+    assert!((clash_win_probability(8, 1) - 1.0).abs() < 1e-3);
+}
+
+#[test]
+fn matches_region_odds_without_fortify() {
+    let attacker = Region {
+        num_dice: 5,
+        ..Default::default()
+    };
+    let defender = Region {
+        num_dice: 3,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        attacker.attack_win_probability(&defender),
+        clash_win_probability(5, 3)
+    );
+}
@@ -0,0 +1,50 @@
+use stackrankdice::game::{GameAction, GameLogEntry, PlayerId, Region};
+
+fn log_entry(region_1_dice_result: Vec<usize>, region_2_dice_result: Vec<usize>) -> GameLogEntry {
+    GameLogEntry {
+        region_1_dice_result,
+        region_2_dice_result,
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            0,
+            PlayerId(0),
+            Region::default(),
+            Region::default(),
+        )
+    }
+}
+
+#[test]
+fn stored_outcome_matches_dice_sums() {
+    let cases = [
+        (vec![6, 6], vec![1, 2], true, false),
+        (vec![1], vec![3, 4], false, false),
+        (vec![3, 3], vec![2, 4], false, true),
+    ];
+
+    for (dice_1, dice_2, attacker_won, is_tie) in cases {
+        let mut entry = log_entry(dice_1.clone(), dice_2.clone());
+        entry.resolve();
+
+        let sum_1: usize = dice_1.iter().sum();
+        let sum_2: usize = dice_2.iter().sum();
+        assert_eq!(entry.attacker_won, sum_1 > sum_2);
+        assert_eq!(entry.attacker_won, attacker_won);
+        assert_eq!(entry.is_tie, is_tie);
+    }
+}
+
+#[test]
+fn dice_sums_follow_dice_results() {
+    let entry = log_entry(vec![6, 2, 5], vec![1, 4]);
+
+    assert_eq!(
+        entry.region_1_dice_sum(),
+        entry.region_1_dice_result.iter().sum::<usize>()
+    );
+    assert_eq!(
+        entry.region_2_dice_sum(),
+        entry.region_2_dice_result.iter().sum::<usize>()
+    );
+    assert_eq!(log_entry(Vec::new(), Vec::new()).region_1_dice_sum(), 0);
+}
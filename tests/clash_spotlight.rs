@@ -0,0 +1,42 @@
+use std::collections::{HashMap, HashSet};
+
+use stackrankdice::game::{Board, RegionId};
+
+mod common;
+
+/// Five single-hex regions: four in a row and one far away
+fn line_board() -> Board {
+    common::board(&[
+        ((0, 0), 0, 1),
+        ((1, 0), 1, 1),
+        ((2, 0), 0, 1),
+        ((3, 0), 1, 1),
+        ((10, 10), 0, 1),
+    ])
+}
+
+#[test]
+fn only_clash_participants_and_neighbours_are_spotlighted() {
+    let board = line_board();
+
+    let spotlight = board.clash_spotlight(RegionId(0), RegionId(1));
+
+    assert_eq!(
+        spotlight,
+        HashSet::from([RegionId(0), RegionId(1), RegionId(2)])
+    );
+}
+
+#[test]
+fn neighbours_ignore_owner() {
+    let board = line_board();
+
+    let neighbours: HashMap<RegionId, Vec<RegionId>> = board
+        .regions
+        .iter()
+        .map(|region| (region.id, board.neighbours(region.id).to_vec()))
+        .collect();
+
+    assert_eq!(neighbours[&RegionId(1)], vec![RegionId(0), RegionId(2)]);
+    assert!(neighbours[&RegionId(4)].is_empty());
+}
@@ -0,0 +1,36 @@
+//! Boards put together by hand for the integration tests
+
+// Each test crate uses only some of the helpers
+#![allow(dead_code)]
+
+use stackrankdice::game::{Board, GameState, PlayerId, Region, RegionId};
+
+/// Board of single-hex regions at given coordinates with their owners and dice,
+/// numbered in the given order
+pub fn board(regions: &[((isize, isize), usize, usize)]) -> Board {
+    Board::from_regions(
+        regions
+            .iter()
+            .enumerate()
+            .map(|(id, (coord, owner, num_dice))| {
+                Region::new(RegionId(id), PlayerId(*owner), *num_dice, vec![*coord])
+            })
+            .collect(),
+    )
+}
+
+/// Row of single-hex regions with given owners and dice, where only neighbours in the row border
+pub fn row(regions: &[(usize, usize)]) -> Board {
+    board(
+        &regions
+            .iter()
+            .enumerate()
+            .map(|(q, (owner, num_dice))| ((q as isize, 0), *owner, *num_dice))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Game between given number of players on a [`row`], player 0 to move
+pub fn row_game(regions: &[(usize, usize)], number_of_players: usize) -> GameState {
+    GameState::from_board(row(regions), number_of_players)
+}
@@ -0,0 +1,59 @@
+use stackrankdice::game::{generate_board, Board, BoardConfig, PlayerId, RegionId};
+use stackrankdice::tiered_prng::get_randomness;
+
+mod common;
+
+/// A row of regions with owners 0 0 1 0 1, and a far region of player 0
+fn board() -> Board {
+    common::board(&[
+        ((0, 0), 0, 1),
+        ((1, 0), 0, 1),
+        ((2, 0), 1, 1),
+        ((3, 0), 0, 1),
+        ((4, 0), 1, 1),
+        ((9, 9), 0, 1),
+    ])
+}
+
+#[test]
+fn groups_bordering_regions_of_a_player() {
+    let board = board();
+
+    assert_eq!(
+        board.connected_components(PlayerId(0)),
+        vec![
+            vec![RegionId(0), RegionId(1)],
+            vec![RegionId(3)],
+            vec![RegionId(5)]
+        ]
+    );
+    assert_eq!(
+        board.connected_components(PlayerId(1)),
+        vec![vec![RegionId(2)], vec![RegionId(4)]]
+    );
+    assert!(board.connected_components(PlayerId(2)).is_empty());
+}
+
+#[test]
+fn reassigning_leaves_no_isolated_single_hex_regions() {
+    let config = BoardConfig {
+        reassign_isolated_regions: true,
+        ..Default::default()
+    };
+
+    for seed in 0..10 {
+        let board = generate_board(3, &config, get_randomness(seed));
+
+        for player in 0..3 {
+            let components = board.connected_components(PlayerId(player));
+            assert!(!components.is_empty(), "seed {}", seed);
+
+            let isolated = components.iter().filter(|c| {
+                c.len() == 1
+                    && board.regions[c[0]].hexes.len() == 1
+                    && !board.neighbours(c[0]).is_empty()
+            });
+            assert!(isolated.count() <= 1, "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "render")]
+
+use stackrankdice::ui::debug_overlay_text;
+
+#[test]
+fn overlay_text_includes_region_count() {
+    let text = debug_overlay_text(Some(59.94), Some(412.0), 31, 96);
+
+    assert!(text.contains("REGIONS: 31"));
+    assert!(text.contains("FPS: 59.9"));
+    assert!(text.contains("ENTITIES: 412"));
+    assert!(text.contains("DICE MESHES: 96"));
+}
+
+#[test]
+fn overlay_text_without_diagnostics() {
+    let text = debug_overlay_text(None, None, 0, 0);
+
+    assert!(text.contains("FPS: -"));
+    assert!(text.contains("REGIONS: 0"));
+}
@@ -0,0 +1,61 @@
+use stackrankdice::game::{
+    generate_board, Board, BoardConfig, DiceAllocation, DiceCapRule, PlayerId,
+};
+use stackrankdice::tiered_prng::get_randomness;
+
+const ALLOCATIONS: [DiceAllocation; 3] = [
+    DiceAllocation::Uniform,
+    DiceAllocation::FrontloadBorders,
+    DiceAllocation::Clustered,
+];
+
+fn generate(seed: u64, dice_allocation: DiceAllocation, dice_cap_rule: DiceCapRule) -> Board {
+    let config = BoardConfig {
+        dice_allocation,
+        dice_cap_rule,
+        ..Default::default()
+    };
+    generate_board(3, &config, get_randomness(seed))
+}
+
+fn dice_per_player(board: &Board) -> Vec<usize> {
+    (0..3)
+        .map(|player| {
+            board
+                .regions
+                .iter()
+                .filter(|region| region.owner == PlayerId(player))
+                .map(|region| region.num_dice)
+                .sum()
+        })
+        .collect()
+}
+
+#[test]
+fn total_dice_per_player_is_conserved() {
+    for dice_cap_rule in [DiceCapRule::Global, DiceCapRule::RegionSize] {
+        for seed in 0..5 {
+            let uniform = generate(seed, DiceAllocation::Uniform, dice_cap_rule);
+
+            for dice_allocation in ALLOCATIONS {
+                let board = generate(seed, dice_allocation, dice_cap_rule);
+
+                assert_eq!(dice_per_player(&board), dice_per_player(&uniform));
+                for region in board.regions.iter() {
+                    assert!(region.num_dice >= 1);
+                    assert!(region.num_dice <= board.dice_cap(region));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn allocation_keeps_the_layout() {
+    let uniform = generate(4242, DiceAllocation::Uniform, DiceCapRule::Global);
+    let clustered = generate(4242, DiceAllocation::Clustered, DiceCapRule::Global);
+
+    let hexes =
+        |board: &Board| -> Vec<_> { board.regions.iter().map(|r| r.hexes.clone()).collect() };
+    assert_eq!(hexes(&uniform), hexes(&clustered));
+}
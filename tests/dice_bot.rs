@@ -0,0 +1,110 @@
+#![cfg(feature = "render")]
+
+use bevy::prelude::*;
+use stackrankdice::ai::{AiPlayers, AiStrategy, BotContext, DiceBot, RandomBot};
+use stackrankdice::app::{build_app, GameSettings};
+use stackrankdice::game::{generate_board, BoardConfig, GameState, PlayerId, RegionId, Rules};
+use stackrankdice::simulate::play_out_with_bots;
+use stackrankdice::tiered_prng::get_randomness;
+
+/// Bot of a researcher: attacks from the region with the most dice, if it has more dice
+/// than its target
+struct StrongestFirstBot;
+
+impl DiceBot for StrongestFirstBot {
+    fn choose(
+        &mut self,
+        state: &GameState,
+        context: &mut BotContext,
+    ) -> Option<(RegionId, RegionId)> {
+        state
+            .clone()
+            .possible_moves()
+            .into_iter()
+            .filter(|(region_1, region_2)| {
+                state.can_attack(region_1.id, context.rules.capture_bonus)
+                    && region_1.num_dice > region_2.num_dice
+            })
+            .max_by_key(|(region_1, _)| region_1.num_dice)
+            .map(|(region_1, region_2)| (region_1.id, region_2.id))
+    }
+}
+
+fn game_state(world_seed: u64) -> GameState {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(world_seed));
+    GameState::from_board(board, 2)
+}
+
+#[test]
+fn headless_game_between_bots_ends_with_single_owner() {
+    let mut game_state = game_state(42);
+    let mut bots: Vec<Box<dyn DiceBot>> =
+        vec![Box::new(StrongestFirstBot), Box::new(AiStrategy::Greedy)];
+
+    let outcome = play_out_with_bots(&mut game_state, 7, &Rules::default(), &mut bots);
+    let winner = outcome.winner.unwrap();
+
+    assert!(outcome.moves > 0);
+    assert!(game_state
+        .board
+        .regions
+        .iter()
+        .all(|region| region.owner == winner));
+}
+
+/// Bot attacking its own first region with itself, every time
+struct CheatingBot;
+
+impl DiceBot for CheatingBot {
+    fn choose(&mut self, state: &GameState, _: &mut BotContext) -> Option<(RegionId, RegionId)> {
+        let id = state.player_regions.regions_of(state.turn_of_player)[0];
+        Some((id, id))
+    }
+}
+
+#[test]
+fn illegal_moves_of_bots_end_their_turn() {
+    let mut game_state = game_state(42);
+    let mut bots: Vec<Box<dyn DiceBot>> = vec![Box::new(CheatingBot), Box::new(CheatingBot)];
+
+    let outcome = play_out_with_bots(&mut game_state, 7, &Rules::default(), &mut bots);
+
+    assert_eq!(outcome.winner, None);
+    assert_eq!(outcome.moves, 0);
+}
+
+#[test]
+fn random_bot_only_makes_legal_moves() {
+    let game_state = game_state(4242);
+    let rules = Rules::default();
+    let mut rng = get_randomness(17);
+    let mut context = BotContext {
+        rules: &rules,
+        rng: &mut rng,
+    };
+
+    for _ in 0..20 {
+        let (region_1, region_2) = RandomBot.choose(&game_state, &mut context).unwrap();
+        let regions = &game_state.board.regions;
+        assert!(game_state.is_legal_move(&regions[region_1], &regions[region_2]));
+    }
+}
+
+#[test]
+fn build_app_seats_bots_in_order() {
+    let mut app = App::new();
+    let settings = GameSettings {
+        testing: true,
+        ..GameSettings::new(4242, 17, 3)
+    };
+    build_app(
+        &mut app,
+        settings,
+        vec![Box::new(RandomBot), Box::new(AiStrategy::Greedy)],
+    );
+
+    let ai_players = app.world.resource::<AiPlayers>();
+    assert!(ai_players.is_ai(PlayerId(0)));
+    assert!(ai_players.is_ai(PlayerId(1)));
+    assert!(!ai_players.is_ai(PlayerId(2)));
+}
@@ -0,0 +1,76 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::error::GameError;
+use stackrankdice::game::{Board, DiceCapRule, GameState, PlayerId, Region, RegionId, MAX_DICE};
+
+/// A six-hex region of player 1 bordering a single-hex region of player 2,
+/// with another single-hex region of player 1 next to the big one
+fn game_state(dice_cap_rule: DiceCapRule) -> GameState {
+    let mut board = Board::from_regions(vec![
+        Region::new(
+            RegionId(0),
+            PlayerId(0),
+            8,
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (1, 1), (1, 2)],
+        ),
+        Region::new(RegionId(1), PlayerId(1), 2, vec![(2, 0)]),
+        Region::new(RegionId(2), PlayerId(0), 1, vec![(-1, 0)]),
+    ]);
+    board.dice_cap_rule = dice_cap_rule;
+
+    GameState::from_board(board, 2)
+}
+
+#[test]
+fn dice_cap_grows_with_region_size() {
+    let board = game_state(DiceCapRule::RegionSize).board;
+
+    assert_eq!(board.regions[0].dice_cap(DiceCapRule::Global), MAX_DICE);
+    assert_eq!(board.regions[1].dice_cap(DiceCapRule::Global), MAX_DICE);
+    assert!(
+        board.regions[1].dice_cap(DiceCapRule::RegionSize)
+            < board.regions[0].dice_cap(DiceCapRule::RegionSize)
+    );
+}
+
+#[test]
+fn captured_region_gets_no_more_than_its_cap() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+
+    for _ in 0..100 {
+        let mut game_state = game_state(DiceCapRule::RegionSize);
+        let cap = game_state.board.dice_cap(&game_state.board.regions[1]);
+
+        game_state
+            .apply_clash(RegionId(0), RegionId(1), true, &mut rng)
+            .unwrap();
+        assert!(game_state.board.regions[1].num_dice <= cap);
+        assert!(game_state.board.validate(2).is_ok());
+    }
+}
+
+#[test]
+fn moving_dice_respects_cap_of_target() {
+    let mut game_state = game_state(DiceCapRule::RegionSize);
+    let cap = game_state.board.dice_cap(&game_state.board.regions[2]);
+
+    assert!(matches!(
+        game_state.board.move_dice(RegionId(0), RegionId(2), cap),
+        Err(GameError::DiceCapExceeded { region: 2, .. })
+    ));
+    assert!(game_state
+        .board
+        .move_dice(RegionId(0), RegionId(2), cap - 1)
+        .is_ok());
+}
+
+#[test]
+fn global_cap_by_default() {
+    let mut game_state = game_state(DiceCapRule::default());
+
+    assert!(game_state
+        .board
+        .move_dice(RegionId(0), RegionId(2), 5)
+        .is_ok());
+    assert_eq!(game_state.board.regions[2].num_dice, 6);
+}
@@ -0,0 +1,47 @@
+use stackrankdice::game::Region;
+
+#[test]
+fn single_dice_is_uniform() {
+    let distribution = Region::dice_sum_distribution(1);
+
+    assert_eq!(distribution.len(), 6);
+    for p in distribution {
+        assert!((p - 1.0 / 6.0).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn distribution_sums_to_one() {
+    for n in [1, 2, 3, 5, 8] {
+        let distribution = Region::dice_sum_distribution(n);
+
+        assert_eq!(distribution.len(), 5 * n + 1);
+        assert!((distribution.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn two_dice_peak_at_seven() {
+    let distribution = Region::dice_sum_distribution(2);
+
+    // sum of 7 is at index 7 - 2
+    assert!((distribution[5] - 6.0 / 36.0).abs() < 1e-12);
+    assert!((distribution[0] - 1.0 / 36.0).abs() < 1e-12);
+}
+
+#[test]
+fn attack_odds_account_for_ties_and_fortify() {
+    let attacker = Region {
+        num_dice: 1,
+        ..Default::default()
+    };
+    let mut defender = Region {
+        num_dice: 1,
+        ..Default::default()
+    };
+
+    assert!((attacker.attack_win_probability(&defender) - 15.0 / 36.0).abs() < 1e-12);
+
+    defender.fortify = 3;
+    assert!((attacker.attack_win_probability(&defender) - 3.0 / 36.0).abs() < 1e-12);
+}
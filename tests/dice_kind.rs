@@ -0,0 +1,127 @@
+use std::path::PathBuf;
+use std::{env, fs};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::error::GameError;
+use stackrankdice::game::{
+    generate_board, BoardConfig, DiceBag, DiceKind, GameState, Region, ReinforcementReserve, Rules,
+    MAX_DICE_SIDES,
+};
+use stackrankdice::odds::{clash_win_probability, clash_win_probability_with_dice};
+use stackrankdice::tiered_prng::get_randomness;
+
+fn d8() -> DiceKind {
+    DiceKind::new(8).unwrap()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!(
+        "stackrankdice-{}-{}-{:?}.json",
+        name,
+        std::process::id(),
+        std::thread::current().id()
+    ))
+}
+
+#[test]
+fn six_sides_by_default() {
+    assert_eq!(DiceKind::default().sides(), 6);
+    for n in 1..=4 {
+        assert_eq!(
+            clash_win_probability_with_dice(DiceKind::default(), n, n, 0),
+            clash_win_probability(n, n)
+        );
+    }
+}
+
+#[test]
+fn d8_odds_differ_from_d6() {
+    // Ties are rarer with more faces, so the attacker does better
+    for n in 1..=4 {
+        let d6 = clash_win_probability_with_dice(DiceKind::default(), n, n, 0);
+        let d8 = clash_win_probability_with_dice(d8(), n, n, 0);
+        assert!(d8 > d6 + 1e-3, "{} dice: {} and {}", n, d6, d8);
+    }
+
+    // 28 of 64 outcomes have the first dice higher
+    assert!((clash_win_probability_with_dice(d8(), 1, 1, 0) - 28.0 / 64.0).abs() < 1e-12);
+    // One dice can't beat a sum of eight dice, whatever their faces
+    assert_eq!(clash_win_probability_with_dice(d8(), 1, 8, 0), 0.0);
+}
+
+#[test]
+fn sum_distribution_covers_all_faces() {
+    let distribution = d8().sum_distribution(3);
+    assert_eq!(distribution.len(), 3 * 8 - 3 + 1);
+    assert!((distribution.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!((distribution[0] - 1.0 / 512.0).abs() < 1e-12);
+}
+
+#[test]
+fn rolls_stay_within_faces() {
+    let mut rng = ChaCha20Rng::seed_from_u64(7);
+    let values = d8().roll(1000, &mut rng);
+    assert!(values.iter().all(|value| (1..=8).contains(value)));
+    assert!(values.contains(&8));
+
+    let mut bag = DiceBag::new(d8());
+    let mut faces: Vec<usize> = (0..8).map(|_| bag.draw(&mut rng)).collect();
+    faces.sort_unstable();
+    assert_eq!(faces, (1..=8).collect::<Vec<_>>());
+}
+
+#[test]
+fn region_odds_follow_dice_kind() {
+    let attacker = Region {
+        num_dice: 3,
+        ..Default::default()
+    };
+    let defender = Region {
+        num_dice: 2,
+        ..Default::default()
+    };
+
+    assert_eq!(
+        attacker.attack_win_probability_with(&defender, DiceKind::default()),
+        attacker.attack_win_probability(&defender)
+    );
+    assert_eq!(
+        attacker.attack_win_probability_with(&defender, d8()),
+        clash_win_probability_with_dice(d8(), 3, 2, 0)
+    );
+}
+
+#[test]
+fn saved_game_keeps_dice_kind() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(42));
+    let mut game_state = GameState::from_board(board, 2);
+    game_state.dice_kind = d8();
+
+    let path = temp_path("saved_game_keeps_dice_kind");
+    game_state
+        .save_to_path(&Rules::default(), &ReinforcementReserve::default(), &path)
+        .unwrap();
+    let loaded = GameState::load_from_path(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.unwrap().game_state.dice_kind, d8());
+}
+
+#[test]
+fn dice_need_two_sides_or_more() {
+    for sides in [0, 1, MAX_DICE_SIDES + 1] {
+        assert_eq!(
+            DiceKind::new(sides),
+            Err(GameError::InvalidDiceSides(sides))
+        );
+        let json = format!("{{\"sides\":{}}}", sides);
+        assert!(serde_json::from_str::<DiceKind>(&json).is_err());
+    }
+
+    assert_eq!(DiceKind::new(2).unwrap().sides(), 2);
+    assert_eq!(
+        serde_json::from_str::<DiceKind>("{\"sides\":8}").unwrap(),
+        d8()
+    );
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "render")]
+
+use stackrankdice::board::{dice_positions, DiceLayout};
+use stackrankdice::game::MAX_DICE;
+
+#[test]
+fn single_layout_spawns_one_dice_per_region() {
+    for num_dice in 1..=MAX_DICE {
+        let positions = dice_positions(DiceLayout::Single, &[1.0, 0.0, 2.0], num_dice);
+
+        assert_eq!(positions.len(), 1);
+    }
+}
+
+#[test]
+fn stacked_layout_spawns_every_dice() {
+    for num_dice in 1..=MAX_DICE {
+        let positions = dice_positions(DiceLayout::Stacked, &[1.0, 0.0, 2.0], num_dice);
+
+        assert_eq!(positions.len(), num_dice);
+    }
+}
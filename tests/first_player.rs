@@ -0,0 +1,43 @@
+#![cfg(feature = "render")]
+
+use bevy::prelude::*;
+use stackrankdice::app::{GameSettings, StackRankDicePlugin};
+use stackrankdice::game::{FirstPlayer, GameState, PlayerId};
+
+fn first_turn(env_seed: u64, first_player: FirstPlayer) -> (usize, usize, usize) {
+    let mut settings = GameSettings::new(4242, env_seed, 4);
+    settings.testing = true;
+    settings.first_player = first_player;
+
+    let mut app = App::new();
+    app.add_plugin(StackRankDicePlugin { settings });
+
+    let game_state = app.world.resource::<GameState>();
+    (
+        game_state.turn_of_player.0,
+        game_state.first_player.0,
+        game_state.turn_counter,
+    )
+}
+
+#[test]
+fn chosen_first_player_takes_turn_0() {
+    assert_eq!(first_turn(17, FirstPlayer::Player(PlayerId(2))), (2, 2, 0));
+    assert_eq!(first_turn(17, FirstPlayer::default()), (0, 0, 0));
+}
+
+#[test]
+fn random_first_player_follows_env_seed() {
+    let (player, first_player, turn_counter) = first_turn(17, FirstPlayer::Random);
+
+    assert!(player < 4);
+    assert_eq!(first_player, player);
+    assert_eq!(turn_counter, 0);
+    assert_eq!(first_turn(17, FirstPlayer::Random).0, player);
+}
+
+#[test]
+#[should_panic]
+fn first_player_must_exist() {
+    first_turn(17, FirstPlayer::Player(PlayerId(4)));
+}
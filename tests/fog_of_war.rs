@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+
+use stackrankdice::game::{generate_board, BoardConfig, GameState, PlayerId, RegionId};
+use stackrankdice::tiered_prng::get_randomness;
+
+fn game_state(seed: u64) -> GameState {
+    let board = generate_board(3, &BoardConfig::default(), get_randomness(seed));
+
+    GameState::from_board(board, 3)
+}
+
+#[test]
+fn visible_regions_are_owned_and_neighbours() {
+    for seed in 0..5 {
+        let game_state = game_state(seed);
+
+        for player in 0..3 {
+            let mut expected: HashSet<RegionId> = HashSet::new();
+            for region in game_state.board.regions.iter() {
+                if region.owner == PlayerId(player) {
+                    expected.insert(region.id);
+                    expected.extend(game_state.board.neighbours(region.id));
+                }
+            }
+
+            let visible = game_state.visible_regions(PlayerId(player));
+            assert_eq!(visible.len(), expected.len());
+            assert_eq!(visible.into_iter().collect::<HashSet<RegionId>>(), expected);
+        }
+    }
+}
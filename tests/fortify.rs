@@ -0,0 +1,71 @@
+use stackrankdice::error::GameError;
+use stackrankdice::game::{
+    Board, GameAction, GameLogEntry, GameState, PlayerId, Region, RegionId, MAX_FORTIFY,
+};
+
+fn clash(
+    defender_fortify: u8,
+    region_1_dice_result: Vec<usize>,
+    region_2_dice_result: Vec<usize>,
+) -> GameLogEntry {
+    let mut entry = GameLogEntry {
+        region_1_dice_result,
+        region_2_dice_result,
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            0,
+            PlayerId(0),
+            Region::default(),
+            Region {
+                fortify: defender_fortify,
+                ..Default::default()
+            },
+        )
+    };
+    entry.resolve();
+    entry
+}
+
+#[test]
+fn fortify_raises_defender_effective_sum() {
+    assert!(clash(0, vec![5, 4], vec![4, 3]).attacker_won);
+    assert!(clash(1, vec![5, 4], vec![4, 3]).attacker_won);
+    assert!(clash(2, vec![5, 4], vec![4, 3]).is_tie);
+    assert!(!clash(3, vec![5, 4], vec![4, 3]).attacker_won);
+}
+
+#[test]
+fn fortify_bonus_is_capped() {
+    let region = Region {
+        fortify: MAX_FORTIFY + 5,
+        ..Default::default()
+    };
+
+    assert_eq!(region.fortify_bonus(), MAX_FORTIFY as usize);
+    assert!(clash(MAX_FORTIFY + 5, vec![6, 6], vec![4, 4]).attacker_won);
+}
+
+#[test]
+fn fortify_action_is_capped_and_costs_an_action() {
+    let board = Board::from_regions(vec![Region::new(RegionId(0), PlayerId(0), 2, vec![(0, 0)])]);
+    let mut game_state = GameState::from_board(board, 2);
+
+    for level in 1..=MAX_FORTIFY {
+        assert_eq!(game_state.fortify(RegionId(0)), Ok(()));
+        assert_eq!(game_state.board.regions[0].fortify, level);
+        assert_eq!(
+            game_state.fortify(RegionId(0)),
+            Err(GameError::RegionAlreadyActed(0))
+        );
+        game_state.turn_counter += 1;
+    }
+
+    assert_eq!(
+        game_state.fortify(RegionId(0)),
+        Err(GameError::FortifyCapReached(0))
+    );
+    assert!(game_state
+        .game_log
+        .iter()
+        .all(|gl| gl.action == GameAction::Fortify));
+}
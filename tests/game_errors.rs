@@ -0,0 +1,104 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::error::GameError;
+use stackrankdice::game::{GameState, PlayerId, RegionId};
+
+mod common;
+
+/// Regions in a row: 0 and 1 belong to player 0, 2 to player 1, 3 to player 0
+fn game_state() -> GameState {
+    common::row_game(&[(0, 3), (0, 2), (1, 4), (0, 1)], 2)
+}
+
+#[test]
+fn invalid_region() {
+    let mut game_state = game_state();
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+    assert_eq!(
+        game_state.board.region(RegionId(10)).map(|r| r.id),
+        Err(GameError::InvalidRegion(10))
+    );
+    assert_eq!(
+        game_state.apply_clash(RegionId(1), RegionId(10), true, &mut rng),
+        Err(GameError::InvalidRegion(10))
+    );
+    assert_eq!(
+        game_state.board.move_dice(RegionId(10), RegionId(0), 1),
+        Err(GameError::InvalidRegion(10))
+    );
+}
+
+#[test]
+fn invalid_player() {
+    let mut game_state = game_state();
+
+    assert_eq!(
+        game_state.set_turn(PlayerId(2)),
+        Err(GameError::InvalidPlayer(2))
+    );
+    assert_eq!(game_state.set_turn(PlayerId(1)), Ok(()));
+    assert_eq!(game_state.turn_of_player, PlayerId(1));
+}
+
+#[test]
+fn not_opponents() {
+    let mut game_state = game_state();
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+
+    assert_eq!(
+        game_state.apply_clash(RegionId(0), RegionId(1), true, &mut rng),
+        Err(GameError::NotOpponents {
+            region_1: 0,
+            region_2: 1
+        })
+    );
+    assert_eq!(
+        game_state.apply_clash(RegionId(0), RegionId(2), true, &mut rng),
+        Err(GameError::NotOpponents {
+            region_1: 0,
+            region_2: 2
+        })
+    );
+}
+
+#[test]
+fn not_your_region_and_already_acted() {
+    let mut game_state = game_state();
+
+    assert_eq!(
+        game_state.retreat(RegionId(2), RegionId(1), 1),
+        Err(GameError::NotYourRegion(2))
+    );
+    assert_eq!(game_state.retreat(RegionId(0), RegionId(1), 1), Ok(()));
+    assert_eq!(
+        game_state.retreat(RegionId(0), RegionId(1), 1),
+        Err(GameError::RegionAlreadyActed(0))
+    );
+}
+
+#[test]
+fn dice_errors() {
+    let mut game_state = game_state();
+
+    assert_eq!(
+        game_state.board.move_dice(RegionId(0), RegionId(3), 1),
+        Err(GameError::NotConnected { from: 0, to: 3 })
+    );
+    assert_eq!(
+        game_state.board.move_dice(RegionId(0), RegionId(1), 3),
+        Err(GameError::NotEnoughDice {
+            region: 0,
+            num_dice: 3
+        })
+    );
+
+    game_state.board.regions[1].num_dice = 7;
+    assert_eq!(
+        game_state.board.move_dice(RegionId(0), RegionId(1), 2),
+        Err(GameError::DiceCapExceeded {
+            region: 1,
+            num_dice: 9
+        })
+    );
+}
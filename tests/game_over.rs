@@ -0,0 +1,29 @@
+use stackrankdice::game::{GameState, PlayerId};
+
+mod common;
+
+fn game_state(owners: &[usize]) -> GameState {
+    let regions: Vec<(usize, usize)> = owners.iter().map(|owner| (*owner, 2)).collect();
+    common::row_game(&regions, 3)
+}
+
+#[test]
+fn board_owned_by_one_player_is_won() {
+    assert_eq!(game_state(&[2, 2, 2, 2]).is_game_over(), Some(PlayerId(2)));
+}
+
+#[test]
+fn contested_board_has_no_winner() {
+    assert_eq!(game_state(&[0, 0, 1, 0]).is_game_over(), None);
+    assert_eq!(game_state(&[0, 1, 2]).is_game_over(), None);
+}
+
+#[test]
+fn single_region_board_is_won_by_its_owner() {
+    assert_eq!(game_state(&[1]).is_game_over(), Some(PlayerId(1)));
+}
+
+#[test]
+fn empty_board_has_no_winner() {
+    assert_eq!(game_state(&[]).is_game_over(), None);
+}
@@ -0,0 +1,18 @@
+#![cfg(feature = "render")]
+
+use bevy::prelude::*;
+use stackrankdice::app::{GameSettings, StackRankDicePlugin};
+use stackrankdice::game::GameState;
+
+#[test]
+fn plugin_sets_up_game_state() {
+    let mut settings = GameSettings::new(4242, 17, 3);
+    settings.testing = true;
+
+    let mut app = App::new();
+    app.add_plugin(StackRankDicePlugin { settings });
+
+    let game_state = app.world.get_resource::<GameState>().unwrap();
+    assert_eq!(game_state.number_of_players, 3);
+    assert!(!game_state.board.regions.is_empty());
+}
@@ -1,11 +1,20 @@
+#![cfg(feature = "render")]
+
 use bevy::prelude::*;
-use stackrankdice::{app::build_app, game::GameState};
+use stackrankdice::{
+    app::{build_app, GameSettings},
+    game::GameState,
+};
 
 #[test]
 fn fixed_world_undef_env_seed() {
     // Setup app
     let mut app = App::new();
-    build_app(&mut app, 4242, 0, 2, true);
+    let settings = GameSettings {
+        testing: true,
+        ..GameSettings::new(4242, 0, 2)
+    };
+    build_app(&mut app, settings, Vec::new());
 
     let game_state = app.world.get_resource::<GameState>().unwrap().clone();
 
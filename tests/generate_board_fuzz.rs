@@ -0,0 +1,68 @@
+//! Sweep of `generate_board` over seeds and configs, checking board invariants.
+//!
+//! The sweep is bounded to keep `cargo test` fast. To run a larger one locally:
+//!
+//! ```sh
+//! FUZZ_SEEDS=10000 cargo test --release --test generate_board_fuzz
+//! ```
+
+use std::panic;
+
+use stackrankdice::game::{generate_board, BoardConfig};
+use stackrankdice::tiered_prng::get_randomness;
+
+const DEFAULT_SEEDS: u64 = 50;
+
+fn number_of_seeds() -> u64 {
+    std::env::var("FUZZ_SEEDS")
+        .ok()
+        .and_then(|seeds| seeds.parse().ok())
+        .unwrap_or(DEFAULT_SEEDS)
+}
+
+#[test]
+fn generated_boards_hold_invariants() {
+    let configs = [
+        BoardConfig::default(),
+        BoardConfig {
+            max_regions: 7,
+            ..Default::default()
+        },
+    ];
+    let mut failures: Vec<String> = Vec::new();
+
+    for number_of_players in [2, 3, 4, 8] {
+        for config in configs.iter() {
+            for seed in 0..number_of_seeds() {
+                let result = panic::catch_unwind(|| {
+                    generate_board(number_of_players, config, get_randomness(seed))
+                });
+
+                let failure = match result {
+                    Err(_) => Some("generation panicked".to_string()),
+                    Ok(board) => match board.validate(number_of_players) {
+                        Err(err) => Some(err.to_string()),
+                        Ok(()) if board.regions.len() > config.max_regions => {
+                            Some("too many regions".to_string())
+                        }
+                        Ok(()) => None,
+                    },
+                };
+
+                if let Some(failure) = failure {
+                    failures.push(format!(
+                        "players: {}, max_regions: {}, seed: {} - {}",
+                        number_of_players, config.max_regions, seed, failure
+                    ));
+                }
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} boards broke invariants:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
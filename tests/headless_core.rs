@@ -0,0 +1,31 @@
+//! Game logic without rendering, as built with `--no-default-features`, e.g. for the web
+
+use stackrankdice::ai::{choose_move, AiStrategy};
+use stackrankdice::game::{generate_board, BoardConfig, DiceKind, GameState, Region, Rules};
+use stackrankdice::odds::clash_win_probability;
+use stackrankdice::simulate::simulate_game;
+use stackrankdice::tiered_prng::get_randomness;
+
+fn greedy(game_state: &GameState, rng: &mut rand_chacha::ChaCha20Rng) -> Option<(Region, Region)> {
+    choose_move(game_state, &AiStrategy::Greedy, rng).map(|(region_1, region_2)| {
+        (
+            game_state.board.regions[region_1].clone(),
+            game_state.board.regions[region_2].clone(),
+        )
+    })
+}
+
+#[test]
+fn board_generation_and_simulation_run_headless() {
+    let board = generate_board(3, &BoardConfig::default(), get_randomness(42));
+    let game_state = GameState::from_board(board, 3);
+    assert!(game_state.players_with_moves().len() > 1);
+    assert!(clash_win_probability(2, 1) > 0.5);
+
+    let outcome = simulate_game(42, 7, 2, &Rules::default(), DiceKind::default(), &greedy);
+    assert!(outcome.moves > 0);
+    assert_eq!(
+        outcome,
+        simulate_game(42, 7, 2, &Rules::default(), DiceKind::default(), &greedy)
+    );
+}
@@ -0,0 +1,40 @@
+use stackrankdice::game::{Board, PlayerId, Region, RegionId};
+
+/// A row of regions with the given owners and sizes in hexes
+fn board(regions: &[(usize, usize)]) -> Board {
+    let mut q = 0;
+    Board::from_regions(
+        regions
+            .iter()
+            .enumerate()
+            .map(|(id, (owner, size))| {
+                let hexes = (0..*size)
+                    .map(|_| {
+                        q += 1;
+                        (q, 0)
+                    })
+                    .collect();
+                Region::new(RegionId(id), PlayerId(*owner), 1, hexes)
+            })
+            .collect(),
+    )
+}
+
+#[test]
+fn owner_of_the_clear_largest_region() {
+    let board = board(&[(0, 2), (1, 5), (0, 3), (2, 1)]);
+
+    assert_eq!(board.largest_region_owner(), Some(PlayerId(1)));
+}
+
+#[test]
+fn no_owner_when_largest_regions_tie() {
+    let board = board(&[(0, 4), (1, 2), (2, 4)]);
+
+    assert_eq!(board.largest_region_owner(), None);
+}
+
+#[test]
+fn no_owner_on_an_empty_board() {
+    assert_eq!(Board::default().largest_region_owner(), None);
+}
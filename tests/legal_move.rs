@@ -0,0 +1,84 @@
+use stackrankdice::game::{
+    CaptureBonus, GameAction, GameLogEntry, GameState, MinAttackDice, PlayerId, Region, Rules,
+};
+
+mod common;
+
+/// A row of regions owned by players 0, 0, 1, 1 where only neighbours in the row border
+fn game_state() -> GameState {
+    common::row_game(&[(0, 3), (0, 3), (1, 3), (1, 3)], 2)
+}
+
+fn region(game_state: &GameState, id: usize) -> Region {
+    game_state.board.regions[id].clone()
+}
+
+#[test]
+fn attack_on_bordering_opponent_is_legal() {
+    let game_state = game_state();
+
+    assert!(game_state.is_legal_move(&region(&game_state, 1), &region(&game_state, 2)));
+}
+
+#[test]
+fn attack_on_own_region_is_rejected() {
+    let game_state = game_state();
+
+    assert!(!game_state.is_legal_move(&region(&game_state, 0), &region(&game_state, 1)));
+}
+
+#[test]
+fn attack_on_distant_region_is_rejected() {
+    let game_state = game_state();
+
+    assert!(!game_state.is_legal_move(&region(&game_state, 0), &region(&game_state, 2)));
+}
+
+#[test]
+fn attack_from_opponent_region_is_rejected() {
+    let game_state = game_state();
+
+    assert!(!game_state.is_legal_move(&region(&game_state, 2), &region(&game_state, 1)));
+}
+
+/// Default rules let a single dice attack, as the CLI does, `--min-attack-dice` raises the bar
+#[test]
+fn single_dice_attack_is_rejected_only_above_min_attack_dice() {
+    let mut game_state = game_state();
+    game_state.board.regions[1].num_dice = 1;
+    let (attacker, defender) = (region(&game_state, 1), region(&game_state, 2));
+
+    assert!(game_state.is_legal_move(&attacker, &defender));
+    let rules = Rules {
+        min_attack_dice: MinAttackDice(2),
+        ..Rules::default()
+    };
+    assert!(!game_state.is_legal_move_with_rules(&attacker, &defender, &rules));
+}
+
+#[test]
+fn second_attack_in_a_turn_is_rejected() {
+    let mut game_state = game_state();
+    let (attacker, defender) = (region(&game_state, 1), region(&game_state, 2));
+    game_state.game_log.push(GameLogEntry {
+        region_1_dice_result: vec![6, 6, 6],
+        region_2_dice_result: vec![1, 1, 1],
+        region_1_dice_after: 1,
+        region_2_dice_after: 2,
+        attacker_won: true,
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            0,
+            PlayerId(0),
+            attacker.clone(),
+            defender.clone(),
+        )
+    });
+
+    assert!(!game_state.is_legal_move(&attacker, &defender));
+    let rules = Rules {
+        capture_bonus: CaptureBonus::ExtraAction,
+        ..Rules::default()
+    };
+    assert!(game_state.is_legal_move_with_rules(&attacker, &defender, &rules));
+}
@@ -0,0 +1,87 @@
+use stackrankdice::game::{generate_board, roll_dice, BoardConfig, GameState, Rules, TieRule};
+use stackrankdice::tiered_prng::get_randomness;
+
+#[test]
+fn fixed_seed_gives_fixed_rolls() {
+    let rolls: Vec<Vec<usize>> = (0..2)
+        .map(|_| {
+            let mut rng = get_randomness(17);
+            (0..10)
+                .map(|_| roll_dice(8, &mut rng))
+                .collect::<Vec<_>>()
+                .concat()
+        })
+        .collect();
+
+    assert_eq!(rolls[0], rolls[1]);
+    assert_eq!(rolls[0].len(), 80);
+    assert!(rolls[0].iter().all(|value| (1..=6).contains(value)));
+    assert_ne!(rolls[0], roll_dice(80, &mut get_randomness(18)));
+}
+
+#[test]
+fn clash_is_played_without_physics() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(4242));
+    let game_state = GameState::from_board(board, 2);
+    let (region_1, region_2) = game_state.clone().possible_moves()[0].clone();
+
+    let outcomes: Vec<GameState> = (0..2)
+        .map(|_| {
+            let mut game_state = game_state.clone();
+            game_state
+                .play_clash(
+                    region_1.id,
+                    region_2.id,
+                    &Rules::default(),
+                    &mut get_randomness(17),
+                    &mut get_randomness(18),
+                )
+                .unwrap();
+            game_state
+        })
+        .collect();
+
+    for game_state in outcomes.iter() {
+        let log_entry = game_state.game_log.last().unwrap();
+        assert_eq!(log_entry.region_1_dice_result.len(), region_1.num_dice);
+        assert_eq!(log_entry.region_2_dice_result.len(), region_2.num_dice);
+
+        let winner = match log_entry.attacker_won {
+            true => region_1.owner,
+            false => region_2.owner,
+        };
+        assert_eq!(game_state.board.regions[region_1.id].owner, winner);
+        assert_eq!(game_state.board.regions[region_2.id].owner, winner);
+    }
+    assert_eq!(outcomes[0].board.regions, outcomes[1].board.regions);
+}
+
+#[test]
+fn ties_are_rerolled_by_the_rules() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(4242));
+    let mut game_state = GameState::from_board(board, 2);
+    let rules = Rules {
+        tie_rule: TieRule::Reroll,
+        ..Rules::default()
+    };
+    let (mut dice_rng, mut env_rng) = (get_randomness(17), get_randomness(18));
+
+    for _ in 0..50 {
+        let (region_1, region_2) = match game_state.clone().possible_moves().first() {
+            Some(attack) => attack.clone(),
+            None => break,
+        };
+        game_state
+            .play_clash(
+                region_1.id,
+                region_2.id,
+                &rules,
+                &mut dice_rng,
+                &mut env_rng,
+            )
+            .unwrap();
+    }
+
+    assert!(!game_state.game_log.is_empty());
+    assert!(game_state.game_log.iter().all(|gl| !gl.is_tie));
+}
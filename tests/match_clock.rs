@@ -0,0 +1,47 @@
+#![cfg(feature = "render")]
+
+use std::time::Duration;
+
+use stackrankdice::game::PlayerId;
+use stackrankdice::stats::{format_clock, MatchClock};
+
+#[test]
+fn only_active_player_accumulates_time() {
+    let mut match_clock = MatchClock::default();
+
+    for _ in 0..10 {
+        match_clock.tick(PlayerId(0), Duration::from_millis(100));
+    }
+    assert_eq!(
+        match_clock.thinking_time(PlayerId(0)),
+        Duration::from_secs(1)
+    );
+    assert_eq!(match_clock.thinking_time(PlayerId(1)), Duration::ZERO);
+
+    match_clock.tick(PlayerId(1), Duration::from_millis(500));
+    assert_eq!(
+        match_clock.thinking_time(PlayerId(0)),
+        Duration::from_secs(1)
+    );
+    assert_eq!(
+        match_clock.thinking_time(PlayerId(1)),
+        Duration::from_millis(500)
+    );
+    assert_eq!(match_clock.elapsed, Duration::from_millis(1500));
+}
+
+#[test]
+fn stopped_clock_does_not_tick() {
+    let mut match_clock = MatchClock::default();
+    match_clock.tick(PlayerId(0), Duration::from_secs(3));
+    match_clock.stopped = true;
+    match_clock.tick(PlayerId(0), Duration::from_secs(3));
+
+    assert_eq!(match_clock.elapsed, Duration::from_secs(3));
+}
+
+#[test]
+fn clock_is_formatted_as_minutes_and_seconds() {
+    assert_eq!(format_clock(Duration::from_secs(0)), "00:00");
+    assert_eq!(format_clock(Duration::from_secs(754)), "12:34");
+}
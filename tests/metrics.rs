@@ -0,0 +1,46 @@
+#![cfg(feature = "render")]
+
+use std::collections::BTreeMap;
+
+use stackrankdice::game::PlayerId;
+use stackrankdice::stats::{metrics_text, SessionStats};
+
+#[test]
+fn metrics_are_well_formed() {
+    let stats = SessionStats {
+        games_played: 3,
+        wins: BTreeMap::from([(PlayerId(0), 2), (PlayerId(1), 1)]),
+        total_turns: 30,
+        ..Default::default()
+    };
+
+    let text = metrics_text(&stats);
+    let samples: Vec<&str> = text.lines().filter(|l| !l.starts_with('#')).collect();
+
+    assert_eq!(
+        samples,
+        vec![
+            "games_total 3",
+            "wins_total{player=\"1\"} 2",
+            "wins_total{player=\"2\"} 1",
+            "avg_turns 10",
+        ]
+    );
+
+    for metric in ["games_total", "wins_total", "avg_turns"] {
+        assert!(text.contains(&format!("# TYPE {} ", metric)), "{}", metric);
+    }
+    for sample in samples {
+        let (_, value) = sample.rsplit_once(' ').unwrap();
+        assert!(value.parse::<f64>().is_ok(), "{}", sample);
+    }
+}
+
+#[test]
+fn empty_session_has_no_wins() {
+    let text = metrics_text(&SessionStats::default());
+
+    assert!(text.contains("games_total 0\n"));
+    assert!(text.contains("avg_turns 0\n"));
+    assert!(!text.contains("wins_total{"));
+}
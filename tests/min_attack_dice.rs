@@ -0,0 +1,23 @@
+use stackrankdice::game::{GameState, MinAttackDice, Region};
+
+mod common;
+
+/// Regions of player 1 with 1 and 3 dice on both sides of a region of player 2
+fn game_state() -> GameState {
+    common::row_game(&[(0, 1), (1, 2), (0, 3)], 2)
+}
+
+fn attackers(moves: Vec<(Region, Region)>) -> Vec<usize> {
+    moves.iter().map(|(region_1, _)| region_1.id.0).collect()
+}
+
+#[test]
+fn single_dice_attacks_are_allowed_by_default() {
+    assert_eq!(attackers(game_state().possible_moves()), vec![0, 2]);
+}
+
+#[test]
+fn attackers_below_threshold_are_left_out() {
+    let moves = game_state().possible_moves_with_min_dice(MinAttackDice(2));
+    assert_eq!(attackers(moves), vec![2]);
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "render")]
+
+use bevy::prelude::Color;
+use stackrankdice::board::{player_colors, Palette};
+
+#[test]
+fn default_palette_keeps_original_colors() {
+    assert_eq!(
+        player_colors(Palette::Default),
+        [
+            Color::PURPLE,
+            Color::CYAN,
+            Color::GREEN,
+            Color::YELLOW,
+            Color::RED,
+            Color::ORANGE,
+            Color::PINK,
+            Color::OLIVE,
+        ]
+    );
+}
+
+#[test]
+fn players_have_distinct_colors_in_every_palette() {
+    for palette in [
+        Palette::Default,
+        Palette::Deuteranopia,
+        Palette::HighContrast,
+    ] {
+        let colors = player_colors(palette);
+        for i in 0..colors.len() {
+            for j in i + 1..colors.len() {
+                assert_ne!(colors[i], colors[j], "{:?}", palette);
+            }
+        }
+    }
+}
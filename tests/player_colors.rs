@@ -0,0 +1,21 @@
+use stackrankdice::game::{PlayerId, PlayerRoster};
+
+#[test]
+fn player_id_survives_elimination_of_another_player() {
+    let mut players = PlayerRoster::new(4);
+    let last_player = players.id_of(PlayerId(3));
+    assert_eq!(last_player, PlayerId(3));
+
+    // Player 2 is knocked out, the ones after it shift down one owner index
+    players.eliminate(PlayerId(1));
+
+    assert_eq!(players.id_of(PlayerId(0)), PlayerId(0));
+    assert_eq!(players.id_of(PlayerId(1)), PlayerId(2));
+    assert_eq!(players.id_of(PlayerId(2)), last_player);
+}
+
+#[test]
+fn unknown_owner_falls_back_to_its_index() {
+    let players = PlayerRoster::default();
+    assert_eq!(players.id_of(PlayerId(5)), PlayerId(5));
+}
@@ -0,0 +1,60 @@
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{
+    generate_board, BoardConfig, GameState, PlayerId, PlayerRegions, RegionId,
+};
+use stackrankdice::tiered_prng::get_randomness;
+
+/// Region ids of a player found by scanning the whole board
+fn brute_force(game_state: &GameState, player: usize) -> Vec<RegionId> {
+    game_state
+        .board
+        .regions
+        .iter()
+        .filter(|r| r.owner == PlayerId(player))
+        .map(|r| r.id)
+        .collect()
+}
+
+#[test]
+fn cache_matches_board_after_captures() {
+    let mut rng = ChaCha20Rng::seed_from_u64(11);
+
+    for seed in 0..5 {
+        let board = generate_board(3, &BoardConfig::default(), get_randomness(seed));
+        let mut game_state = GameState::from_board(board, 3);
+
+        for step in 0..40 {
+            let moves: Vec<(RegionId, RegionId)> = game_state
+                .clone()
+                .possible_moves()
+                .into_iter()
+                .map(|(r1, r2)| (r1.id, r2.id))
+                .collect();
+            let (region_1, region_2) = match moves.choose(&mut rng) {
+                Some(attack) => *attack,
+                None => break,
+            };
+
+            game_state
+                .apply_clash(region_1, region_2, step % 3 != 0, &mut rng)
+                .unwrap();
+            game_state.turn_of_player = PlayerId(step % 3);
+
+            for player in 0..3 {
+                assert_eq!(
+                    game_state.player_regions.regions_of(PlayerId(player)),
+                    brute_force(&game_state, player).as_slice()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn unknown_player_has_no_regions() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(1));
+    let player_regions = PlayerRegions::from_board(&board);
+
+    assert!(player_regions.regions_of(PlayerId(5)).is_empty());
+}
@@ -0,0 +1,33 @@
+use stackrankdice::game::{Board, PlayerId};
+
+mod common;
+
+/// Board with regions owned by given players
+fn board(owners: &[usize]) -> Board {
+    let regions: Vec<(usize, usize)> = owners.iter().map(|owner| (*owner, 1)).collect();
+    common::row(&regions)
+}
+
+#[test]
+fn shares_sum_to_one() {
+    let board = board(&[0, 1, 2, 1, 0, 1, 1]);
+
+    assert_eq!(board.total_regions(), 7);
+    let total: f32 = (0..3).map(|p| board.player_share(PlayerId(p))).sum();
+    assert!((total - 1.0).abs() < 1e-6);
+    assert!((board.player_share(PlayerId(1)) - 4.0 / 7.0).abs() < 1e-6);
+    assert_eq!(board.player_share(PlayerId(3)), 0.0);
+}
+
+#[test]
+fn leader_owns_most_regions() {
+    assert_eq!(board(&[0, 1, 2, 1, 0, 1, 1]).leader(), Some(PlayerId(1)));
+    assert_eq!(board(&[2, 2, 2]).leader(), Some(PlayerId(2)));
+}
+
+#[test]
+fn no_leader_on_tie_or_empty_board() {
+    assert_eq!(board(&[0, 1, 1, 0]).leader(), None);
+    assert_eq!(board(&[]).leader(), None);
+    assert_eq!(board(&[]).player_share(PlayerId(0)), 0.0);
+}
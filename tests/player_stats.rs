@@ -0,0 +1,65 @@
+use stackrankdice::game::{Board, GameState, PlayerId, PlayerStats};
+
+fn game_state() -> GameState {
+    // Player 0 holds two regions apart from each other, player 3 has no regions left
+    let mut board = Board::from_ascii(
+        "\
+0 0 1 1 0
+ 0 2 1 2 0
+2 2 . 0 0
+",
+    )
+    .unwrap();
+    for region in board.regions.iter_mut() {
+        region.num_dice = region.id.0 + 1;
+    }
+
+    GameState::from_board(board, 4)
+}
+
+#[test]
+fn stats_add_up_to_board_totals() {
+    let game_state = game_state();
+
+    let stats = game_state.player_stats();
+
+    assert_eq!(stats.len(), 4);
+    let region_count: usize = stats.iter().map(|s| s.region_count).sum();
+    let total_dice: usize = stats.iter().map(|s| s.total_dice).sum();
+    assert_eq!(region_count, game_state.board.regions.len());
+    assert_eq!(
+        total_dice,
+        game_state
+            .board
+            .regions
+            .iter()
+            .map(|r| r.num_dice)
+            .sum::<usize>()
+    );
+}
+
+#[test]
+fn stats_of_each_player() {
+    let game_state = game_state();
+
+    let stats = game_state.player_stats();
+
+    for (player, stats) in stats.iter().enumerate() {
+        assert_eq!(stats.player, PlayerId(player));
+        assert_eq!(
+            stats.largest_group,
+            game_state.largest_connected_group(PlayerId(player))
+        );
+    }
+    assert_eq!(stats[0].region_count, 2);
+    assert_eq!(stats[0].largest_group, 1);
+    assert_eq!(
+        stats[3],
+        PlayerStats {
+            player: PlayerId(3),
+            region_count: 0,
+            total_dice: 0,
+            largest_group: 0,
+        }
+    );
+}
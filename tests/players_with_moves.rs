@@ -0,0 +1,55 @@
+#![cfg(feature = "render")]
+
+use bevy::prelude::*;
+use stackrankdice::app::{GameSettings, StackRankDicePlugin};
+use stackrankdice::game::{Board, GameState, PlayerId};
+
+fn game_state(map: &str, number_of_players: usize) -> GameState {
+    GameState::from_board(Board::from_ascii(map).unwrap(), number_of_players)
+}
+
+#[test]
+fn player_walled_in_by_own_regions_has_no_moves() {
+    // Player 3 sits in a lake, bordering only their own regions
+    let game_state = game_state(
+        "\
+0 0 1 1 . . .
+ 0 1 1 . 2 2 .
+0 0 1 . 2 2 .
+ . . . . . . .
+",
+        3,
+    );
+
+    assert_eq!(
+        game_state.players_with_moves(),
+        vec![PlayerId(0), PlayerId(1)]
+    );
+}
+
+#[test]
+fn player_without_regions_has_no_moves() {
+    let game_state = game_state("0 1 1 0", 3);
+
+    assert_eq!(
+        game_state.players_with_moves(),
+        vec![PlayerId(0), PlayerId(1)]
+    );
+}
+
+#[test]
+fn every_player_can_move_on_a_new_game() {
+    for seed in 1..5 {
+        let mut settings = GameSettings::new(seed, 17, 4);
+        settings.testing = true;
+
+        let mut app = App::new();
+        app.add_plugin(StackRankDicePlugin { settings });
+
+        let game_state = app.world.resource::<GameState>();
+        assert_eq!(
+            game_state.players_with_moves(),
+            (0..4).map(PlayerId).collect::<Vec<_>>()
+        );
+    }
+}
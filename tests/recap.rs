@@ -0,0 +1,108 @@
+#![cfg(feature = "render")]
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{
+    generate_board, roll_dice, Board, BoardConfig, GameAction, GameLogEntry, GameState, PlayerId,
+};
+use stackrankdice::recap::recap_frames;
+use stackrankdice::tiered_prng::get_randomness;
+
+fn region_states(board: &Board) -> Vec<(PlayerId, usize, u8)> {
+    board
+        .regions
+        .iter()
+        .map(|r| (r.owner, r.num_dice, r.fortify))
+        .collect()
+}
+
+/// Attack a few times per turn, keeping the board at the end of every turn
+fn play(game_state: &mut GameState, turns: usize) -> Vec<Board> {
+    let mut rng = ChaCha20Rng::seed_from_u64(5);
+    let mut boards = Vec::new();
+
+    for turn in 0..turns {
+        game_state.turn_counter = turn;
+        game_state.turn_of_player = PlayerId(turn % game_state.number_of_players);
+
+        for (region_1, region_2) in game_state.clone().possible_moves().into_iter().take(3) {
+            if game_state.has_acted(region_1.id) {
+                continue;
+            }
+
+            // Regions might have changed by an earlier attack of this turn
+            let region_1 = game_state.board.regions[region_1.id].clone();
+            let region_2 = game_state.board.regions[region_2.id].clone();
+            if !game_state.board.is_opponent(region_1.id, region_2.id) {
+                continue;
+            }
+
+            let mut log_entry = GameLogEntry {
+                region_1_dice_result: roll_dice(region_1.num_dice, &mut rng),
+                region_2_dice_result: roll_dice(region_2.num_dice, &mut rng),
+                ..GameLogEntry::new(
+                    GameAction::Clash,
+                    turn,
+                    game_state.turn_of_player,
+                    region_1,
+                    region_2,
+                )
+            };
+            log_entry.resolve();
+            game_state.game_log.push(log_entry.clone());
+
+            game_state
+                .apply_clash(
+                    log_entry.region_1.id,
+                    log_entry.region_2.id,
+                    log_entry.attacker_won,
+                    &mut rng,
+                )
+                .unwrap();
+            game_state.log_dice_after();
+        }
+
+        // Fortify a region which hasn't acted, if any
+        let fortify = game_state
+            .player_regions
+            .regions_of(game_state.turn_of_player)
+            .iter()
+            .find(|id| !game_state.has_acted(**id))
+            .cloned();
+        if let Some(id) = fortify {
+            game_state.fortify(id).unwrap();
+        }
+
+        boards.push(game_state.board.clone());
+    }
+
+    boards
+}
+
+#[test]
+fn recap_steps_through_each_turn_and_ends_on_final_board() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(21));
+    let mut game_state = GameState::from_board(board, 2);
+
+    let boards = play(&mut game_state, 6);
+    let frames = recap_frames(&game_state);
+
+    let turns: Vec<usize> = frames.iter().map(|f| f.turn_counter).collect();
+    assert_eq!(turns, (0..6).collect::<Vec<usize>>());
+
+    for (frame, board) in frames.iter().zip(boards.iter()) {
+        assert_eq!(region_states(&frame.board), region_states(board));
+    }
+
+    assert_eq!(
+        region_states(&frames.last().unwrap().board),
+        region_states(&game_state.board)
+    );
+}
+
+#[test]
+fn empty_log_has_no_frames() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(21));
+
+    assert!(recap_frames(&GameState::from_board(board, 2)).is_empty());
+}
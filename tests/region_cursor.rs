@@ -0,0 +1,60 @@
+use stackrankdice::game::{Board, RegionId};
+
+mod common;
+
+/// Region 0 in the middle, with a neighbour on each side
+fn board() -> Board {
+    common::board(&[
+        ((0, 0), 0, 2),
+        ((1, 0), 1, 2),
+        ((-1, 0), 0, 2),
+        ((0, -1), 1, 2),
+        ((0, 1), 0, 2),
+    ])
+}
+
+#[test]
+fn cursor_moves_to_neighbour_in_direction() {
+    let board = board();
+
+    assert_eq!(
+        board.neighbour_in_direction(RegionId(0), (1.0, 0.0)),
+        Some(RegionId(1))
+    );
+    assert_eq!(
+        board.neighbour_in_direction(RegionId(0), (-1.0, 0.0)),
+        Some(RegionId(2))
+    );
+    assert_eq!(
+        board.neighbour_in_direction(RegionId(0), (0.0, -1.0)),
+        Some(RegionId(3))
+    );
+    assert_eq!(
+        board.neighbour_in_direction(RegionId(0), (0.0, 1.0)),
+        Some(RegionId(4))
+    );
+}
+
+#[test]
+fn cursor_stays_without_neighbour_in_direction() {
+    let board = board();
+
+    // Region 1 only borders regions to its west
+    assert_eq!(board.neighbour_in_direction(RegionId(1), (1.0, 0.0)), None);
+    assert_eq!(
+        board.neighbour_in_direction(RegionId(1), (-1.0, 0.0)),
+        Some(RegionId(0))
+    );
+}
+
+#[test]
+fn neighbour_in_direction_skips_other_regions() {
+    let board = board();
+
+    // Regions 1 and 2 don't border each other, even though 2 lies to the west of 1
+    assert!(!board.neighbours(RegionId(1)).contains(&RegionId(2)));
+    assert_ne!(
+        board.neighbour_in_direction(RegionId(1), (-1.0, 0.0)),
+        Some(RegionId(2))
+    );
+}
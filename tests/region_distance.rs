@@ -0,0 +1,28 @@
+use stackrankdice::game::Region;
+
+fn region(hexes: Vec<(isize, isize)>) -> Region {
+    Region {
+        hexes,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn distance_between_closest_hexes() {
+    let region_1 = region(vec![(0, 0), (1, 0)]);
+    let region_2 = region(vec![(4, 0), (5, 0)]);
+    let neighbour = region(vec![(2, 0)]);
+
+    assert_eq!(region_1.distance_to(&region_2), 3);
+    assert_eq!(region_2.distance_to(&region_1), 3);
+    assert_eq!(region_1.distance_to(&neighbour), 1);
+    assert_eq!(region_1.distance_to(&region_1), 0);
+}
+
+#[test]
+fn empty_region_is_infinitely_far() {
+    assert_eq!(
+        region(vec![(0, 0)]).distance_to(&region(vec![])),
+        usize::MAX
+    );
+}
@@ -0,0 +1,29 @@
+use stackrankdice::game::{generate_board, BoardConfig, RegionId};
+use stackrankdice::tiered_prng::get_randomness;
+
+#[test]
+fn region_lookup_by_id_returns_same_region() {
+    let configs = [
+        BoardConfig::default(),
+        BoardConfig {
+            mirror: true,
+            ..Default::default()
+        },
+    ];
+
+    for config in configs.iter() {
+        for seed in 0..10 {
+            let board = generate_board(4, config, get_randomness(seed));
+
+            for (index, region) in board.regions.iter().enumerate() {
+                assert_eq!(board.region(region.id).unwrap().id, region.id);
+                assert_eq!(usize::from(region.id), index);
+            }
+        }
+    }
+}
+
+#[test]
+fn region_ids_convert_to_indices() {
+    assert_eq!(usize::from(RegionId(3)), 3);
+}
@@ -0,0 +1,56 @@
+use stackrankdice::game::{Board, PlayerId, RegionId};
+
+mod common;
+
+/// A row of single-hex regions along q, alternating owners
+fn row_board(length: usize) -> Board {
+    let regions: Vec<(usize, usize)> = (0..length).map(|id| (id % 2, 1)).collect();
+    common::row(&regions)
+}
+
+fn ids(ids: &[usize]) -> Vec<RegionId> {
+    ids.iter().copied().map(RegionId).collect()
+}
+
+#[test]
+fn path_follows_bordering_regions() {
+    let board = row_board(5);
+
+    assert_eq!(
+        board.region_path(RegionId(0), RegionId(4)),
+        Some(ids(&[0, 1, 2, 3, 4]))
+    );
+    assert_eq!(
+        board.region_path(RegionId(3), RegionId(1)),
+        Some(ids(&[3, 2, 1]))
+    );
+    assert_eq!(board.region_path(RegionId(2), RegionId(2)), Some(ids(&[2])));
+}
+
+#[test]
+fn no_path_across_a_gap_or_to_unknown_region() {
+    let board = common::board(&[
+        ((0, 0), 0, 1),
+        ((1, 0), 1, 1),
+        ((2, 0), 0, 1),
+        ((5, 0), 0, 1),
+    ]);
+
+    assert_eq!(board.region_path(RegionId(0), RegionId(3)), None);
+    assert_eq!(board.region_path(RegionId(0), RegionId(7)), None);
+}
+
+#[test]
+fn path_through_passable_regions_only() {
+    let board = row_board(5);
+
+    assert_eq!(
+        board.region_path_through(RegionId(1), RegionId(3), |r| r.owner == PlayerId(1)),
+        None,
+        "region 2 belongs to the other player"
+    );
+    assert_eq!(
+        board.region_path_through(RegionId(0), RegionId(4), |r| r.id != RegionId(7)),
+        Some(ids(&[0, 1, 2, 3, 4]))
+    );
+}
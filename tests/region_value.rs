@@ -0,0 +1,56 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{GameState, RegionId};
+
+mod common;
+
+/// Regions 0, 1 and 2 in a row owned by players 1, 2, 2 and a lone region 3 of player 1
+fn game_state() -> GameState {
+    let board = common::board(&[
+        ((0, 0), 0, 8),
+        ((1, 0), 1, 8),
+        ((2, 0), 1, 8),
+        ((5, 0), 0, 8),
+    ]);
+    GameState::from_board(board, 2)
+}
+
+#[test]
+fn contested_region_is_worth_more() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let mut game_state = game_state();
+
+    // Region 1 changes hands back and forth
+    for _ in 0..3 {
+        game_state
+            .apply_clash(RegionId(0), RegionId(1), true, &mut rng)
+            .unwrap();
+        game_state
+            .apply_clash(RegionId(2), RegionId(1), true, &mut rng)
+            .unwrap();
+        for region in game_state.board.regions.iter_mut() {
+            region.num_dice = 8;
+        }
+    }
+
+    let board = &game_state.board;
+    assert_eq!(board.region_value(RegionId(3)), 1.0);
+    assert!(board.region_value(RegionId(1)) > board.region_value(RegionId(3)));
+    assert!(board.region_value(RegionId(1)) > board.region_value(RegionId(0)));
+}
+
+#[test]
+fn heat_cools_down_over_turns() {
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let mut game_state = game_state();
+    game_state
+        .apply_clash(RegionId(0), RegionId(1), true, &mut rng)
+        .unwrap();
+
+    let hot = game_state.board.region_value(RegionId(1));
+    game_state.board.cool_down();
+    let cooler = game_state.board.region_value(RegionId(1));
+
+    assert!(cooler < hot);
+    assert!(cooler > 1.0);
+}
@@ -0,0 +1,124 @@
+use stackrankdice::game::{
+    GameState, LargestArmyBonus, PlayerId, ReinforcementReserve, Rules, MAX_DICE,
+};
+use stackrankdice::tiered_prng::get_randomness;
+
+mod common;
+
+/// Regions at given coordinates with owners and dice
+fn game_state(regions: &[((isize, isize), usize, usize)]) -> GameState {
+    GameState::from_board(common::board(regions), 2)
+}
+
+fn total_dice(game_state: &GameState, player: usize) -> usize {
+    game_state
+        .board
+        .regions
+        .iter()
+        .filter(|r| r.owner == PlayerId(player))
+        .map(|r| r.num_dice)
+        .sum()
+}
+
+#[test]
+fn single_region_gets_one_dice() {
+    let mut game_state = game_state(&[((0, 0), 0, 2), ((1, 0), 1, 2)]);
+
+    assert_eq!(game_state.largest_connected_group(PlayerId(0)), 1);
+    assert_eq!(
+        game_state.reinforce(PlayerId(0), 0, &mut get_randomness(1)),
+        0
+    );
+    assert_eq!(game_state.board.regions[0].num_dice, 3);
+    assert_eq!(game_state.board.regions[1].num_dice, 2);
+}
+
+#[test]
+fn chain_of_regions_counts_as_one_group() {
+    // Chain of three regions, a region of player 2 and a lone region
+    let mut game_state = game_state(&[
+        ((0, 0), 0, 1),
+        ((1, 0), 0, 1),
+        ((2, 0), 0, 1),
+        ((3, 0), 1, 1),
+        ((6, 0), 0, 1),
+    ]);
+
+    assert_eq!(game_state.largest_connected_group(PlayerId(0)), 3);
+    assert_eq!(game_state.largest_connected_group(PlayerId(1)), 1);
+
+    assert_eq!(
+        game_state.reinforce(PlayerId(0), 0, &mut get_randomness(1)),
+        0
+    );
+    assert_eq!(total_dice(&game_state, 0), 4 + 3);
+}
+
+#[test]
+fn dice_without_room_go_to_reserve() {
+    let mut game_state = game_state(&[((0, 0), 0, MAX_DICE - 1), ((1, 0), 1, 2)]);
+
+    // One dice from the group and two from the reserve, only one fits
+    assert_eq!(
+        game_state.reinforce(PlayerId(0), 2, &mut get_randomness(1)),
+        2
+    );
+    assert_eq!(game_state.board.regions[0].num_dice, MAX_DICE);
+}
+
+#[test]
+fn pending_reinforcements_add_reserve_to_largest_group() {
+    let game_state = game_state(&[
+        ((0, 0), 0, 1),
+        ((1, 0), 0, 1),
+        ((2, 0), 1, 1),
+        ((5, 0), 0, 1),
+    ]);
+    let mut reserve = ReinforcementReserve::default();
+    reserve.by_player.insert(PlayerId(0), 3);
+
+    let no_bonus = LargestArmyBonus(false);
+    assert_eq!(
+        game_state.pending_reinforcements(PlayerId(0), &reserve, no_bonus),
+        2 + 3
+    );
+    assert_eq!(
+        game_state.pending_reinforcements(PlayerId(1), &reserve, no_bonus),
+        1
+    );
+}
+
+#[test]
+fn end_turn_hands_out_pending_reinforcements() {
+    let mut game_state = game_state(&[((0, 0), 0, 1), ((1, 0), 0, 1), ((2, 0), 1, 1)]);
+    let mut reserve = ReinforcementReserve::default();
+    reserve.by_player.insert(PlayerId(0), 3);
+    let rules = Rules {
+        largest_army_bonus: LargestArmyBonus(true),
+        ..Rules::default()
+    };
+    let pending =
+        game_state.pending_reinforcements(PlayerId(0), &reserve, rules.largest_army_bonus);
+
+    assert_eq!(
+        game_state.end_turn(&rules, &mut reserve, &mut get_randomness(1)),
+        PlayerId(0)
+    );
+    assert_eq!(total_dice(&game_state, 0), 2 + pending);
+    assert!(!reserve.by_player.contains_key(&PlayerId(0)));
+    assert_eq!(game_state.turn_of_player, PlayerId(1));
+    assert_eq!(game_state.turn_counter, 1);
+}
+
+#[test]
+fn end_turn_skips_players_without_regions() {
+    let board = common::board(&[((0, 0), 0, 1), ((1, 0), 2, 1)]);
+    let mut game_state = GameState::from_board(board, 3);
+    game_state.end_turn(
+        &Rules::default(),
+        &mut ReinforcementReserve::default(),
+        &mut get_randomness(1),
+    );
+
+    assert_eq!(game_state.turn_of_player, PlayerId(2));
+}
@@ -0,0 +1,89 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use stackrankdice::game::{
+    generate_board, load_replay, BoardConfig, BoardShape, CaptureBonus, DiceKind, GameAction,
+    GameLogEntry, GameState, PlayerId, Rules, TieRule,
+};
+use stackrankdice::tiered_prng::get_randomness;
+
+/// File in the temp dir no other test or test run writes to
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!(
+        "stackrankdice-{}-{}-{:?}.srd",
+        name,
+        std::process::id(),
+        std::thread::current().id()
+    ))
+}
+
+#[test]
+fn exported_replay_loads_back() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(7));
+    let mut entry = GameLogEntry {
+        region_1_dice_result: vec![6, 5, 4],
+        region_2_dice_result: vec![1, 2],
+        region_1_dice_after: 1,
+        region_2_dice_after: 2,
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            3,
+            PlayerId(1),
+            board.regions[0].clone(),
+            board.regions[1].clone(),
+        )
+    };
+    entry.resolve();
+    let mut game_state = GameState::from_board(board, 2);
+    game_state.turn_of_player = PlayerId(1);
+    game_state.turn_counter = 3;
+    game_state.game_log.push(entry);
+    game_state.first_player = PlayerId(1);
+    game_state.dice_kind = DiceKind::new(8).unwrap();
+    let rules = Rules {
+        capture_bonus: CaptureBonus::ExtraAction,
+        tie_rule: TieRule::Reroll,
+        ..Rules::default()
+    };
+    let board_config = BoardConfig {
+        shape: BoardShape::Hexagon { radius: 9 },
+        ..BoardConfig::default()
+    };
+
+    let path = temp_path("exported-replay");
+    game_state
+        .export_replay(7, 11, &rules, &board_config, &path)
+        .unwrap();
+    let replay_data = load_replay(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(replay_data.world_seed, 7);
+    assert_eq!(replay_data.env_seed, 11);
+    assert_eq!(replay_data.number_of_players, 2);
+    assert_eq!(replay_data.first_player, PlayerId(1));
+    assert_eq!(replay_data.dice_kind, DiceKind::new(8).unwrap());
+    assert_eq!(replay_data.rules, rules);
+    assert_eq!(replay_data.board_config.shape, board_config.shape);
+    assert_eq!(replay_data.game_log.len(), 1);
+
+    let loaded = &replay_data.game_log[0];
+    let original = &game_state.game_log[0];
+    assert_eq!(loaded.turn_counter, original.turn_counter);
+    assert_eq!(loaded.turn_of_player, original.turn_of_player);
+    assert_eq!(loaded.region_1, original.region_1);
+    assert_eq!(loaded.region_2, original.region_2);
+    assert_eq!(loaded.region_1_dice_result, original.region_1_dice_result);
+    assert_eq!(loaded.region_2_dice_result, original.region_2_dice_result);
+    assert!(loaded.attacker_won);
+}
+
+#[test]
+fn loading_garbage_replay_fails() {
+    let path = temp_path("garbage-replay");
+    fs::write(&path, "not a replay").unwrap();
+    let loaded = load_replay(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert!(loaded.is_err());
+}
@@ -0,0 +1,138 @@
+#![cfg(feature = "render")]
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{
+    roll_dice, Board, GameAction, GameLogEntry, GameState, PlayerId, RegionId,
+    ReinforcementReserve, RetreatRule, Rules,
+};
+use stackrankdice::recap::{initial_board, replay_step, start_of_game};
+
+mod common;
+
+/// A row of regions owned by players 0, 1, 0, 1
+fn game_state() -> GameState {
+    common::row_game(&[(0, 8), (1, 2), (0, 8), (1, 3)], 2)
+}
+
+/// Attack the way a game does: log the clash, apply it and log dice left on the regions
+fn attack(
+    game_state: &mut GameState,
+    region_1_id: usize,
+    region_2_id: usize,
+    rules: &Rules,
+    rng: &mut ChaCha20Rng,
+) {
+    let region_1 = game_state.board.regions[region_1_id].clone();
+    let region_2 = game_state.board.regions[region_2_id].clone();
+    let mut log_entry = GameLogEntry {
+        region_1_dice_result: roll_dice(region_1.num_dice, rng),
+        region_2_dice_result: roll_dice(region_2.num_dice, rng),
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            game_state.turn_counter,
+            game_state.turn_of_player,
+            region_1,
+            region_2,
+        )
+    };
+    log_entry.resolve();
+    let attacker_won = log_entry.attacker_won;
+    game_state.game_log.push(log_entry);
+
+    let capture_result = game_state
+        .resolve_clash(
+            RegionId(region_1_id),
+            RegionId(region_2_id),
+            attacker_won,
+            rules,
+            rng,
+        )
+        .unwrap();
+    game_state.log_capture(&capture_result);
+}
+
+fn region_states(board: &Board) -> Vec<(PlayerId, usize, u8)> {
+    board
+        .regions
+        .iter()
+        .map(|r| (r.owner, r.num_dice, r.fortify))
+        .collect()
+}
+
+#[test]
+fn replayed_log_matches_live_game() {
+    let mut rng = ChaCha20Rng::seed_from_u64(3);
+    let mut live = game_state();
+    attack(&mut live, 0, 1, &Rules::default(), &mut rng);
+    attack(&mut live, 2, 3, &Rules::default(), &mut rng);
+
+    let mut replayed = game_state();
+    for entry in live.game_log.iter() {
+        replay_step(&mut replayed, entry);
+    }
+
+    assert_eq!(replayed.board.regions, live.board.regions);
+    assert_eq!(replayed.player_regions, live.player_regions);
+    assert_eq!(replayed.board.heat, live.board.heat);
+    assert_eq!(replayed.game_log.len(), live.game_log.len());
+}
+
+#[test]
+fn initial_board_is_rebuilt_from_log() {
+    let mut rng = ChaCha20Rng::seed_from_u64(3);
+    let mut live = game_state();
+    attack(&mut live, 0, 1, &Rules::default(), &mut rng);
+    attack(&mut live, 2, 3, &Rules::default(), &mut rng);
+
+    let board = initial_board(&live.game_log, &live.board);
+
+    assert_eq!(region_states(&board), region_states(&game_state().board));
+    assert!(board.heat.is_empty());
+}
+
+#[test]
+fn replay_includes_shelters_and_reinforcements() {
+    let rules = Rules {
+        retreat_rule: RetreatRule::Fraction(0.5),
+        ..Rules::default()
+    };
+    let mut rng = ChaCha20Rng::seed_from_u64(7);
+    let mut live = common::row_game(&[(0, 8), (1, 4), (1, 2), (0, 1)], 2);
+    let mut reserve = ReinforcementReserve::default();
+
+    for _ in 0..4 {
+        let player = live.turn_of_player;
+        let region_ids = live.player_regions.regions_of(player).to_vec();
+        for region_1 in region_ids {
+            let region = &live.board.regions[region_1];
+            if region.owner != player || region.num_dice < 2 {
+                continue;
+            }
+            let target = live
+                .board
+                .neighbours(region_1)
+                .iter()
+                .copied()
+                .find(|id| live.board.regions[*id].owner != player);
+            if let Some(region_2) = target {
+                attack(&mut live, region_1.0, region_2.0, &rules, &mut rng);
+            }
+        }
+        live.end_turn(&rules, &mut reserve, &mut rng);
+    }
+
+    assert!(live.game_log.iter().any(|gl| gl.shelter.is_some()));
+    assert!(live
+        .game_log
+        .iter()
+        .any(|gl| gl.action == GameAction::Reinforce));
+
+    let mut replayed = start_of_game(&live);
+    for entry in live.game_log.iter() {
+        replay_step(&mut replayed, entry);
+    }
+
+    assert_eq!(replayed.board.regions, live.board.regions);
+    assert_eq!(replayed.player_regions, live.player_regions);
+}
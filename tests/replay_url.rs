@@ -0,0 +1,91 @@
+use stackrankdice::error::GameError;
+use stackrankdice::game::{GameAction, GameLogEntry, PlayerId, Region, RegionId};
+use stackrankdice::replay::{replay_from_url_fragment, replay_to_url_fragment, Replay};
+
+fn replay() -> Replay {
+    let region =
+        |id, owner, num_dice| Region::new(RegionId(id), PlayerId(owner), num_dice, Vec::new());
+
+    Replay {
+        world_seed: u64::MAX,
+        env_seed: 4242,
+        number_of_players: 3,
+        game_log: vec![
+            GameLogEntry {
+                region_1_dice_result: vec![6, 5, 1],
+                region_2_dice_result: vec![2, 2],
+                region_1_dice_after: 1,
+                region_2_dice_after: 2,
+                attacker_won: true,
+                shelter: Some(Region {
+                    fortify: 2,
+                    capture_count: 1,
+                    ..region(5, 1, 3)
+                }),
+                shelter_dice_after: 4,
+                ..GameLogEntry::new(
+                    GameAction::Clash,
+                    0,
+                    PlayerId(0),
+                    region(3, 0, 3),
+                    region(17, 1, 2),
+                )
+            },
+            GameLogEntry {
+                region_1_dice_after: 8,
+                region_2_dice_after: 8,
+                ..GameLogEntry::new(
+                    GameAction::Reinforce,
+                    300,
+                    PlayerId(2),
+                    region(200, 2, 5),
+                    region(200, 2, 5),
+                )
+            },
+        ],
+    }
+}
+
+#[test]
+fn replay_round_trips_through_url_fragment() {
+    let replay = replay();
+
+    let fragment = replay_to_url_fragment(&replay);
+
+    assert_eq!(replay_from_url_fragment(&fragment), Ok(replay.clone()));
+    assert_eq!(
+        replay_from_url_fragment(&format!("#{}", fragment)),
+        Ok(replay)
+    );
+}
+
+#[test]
+fn url_fragment_is_url_safe() {
+    // Seeds with all bits set produce bytes which map onto `+` and `/` in plain base64
+    let fragment = replay_to_url_fragment(&replay());
+
+    assert!(fragment
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+}
+
+#[test]
+fn invalid_fragments_are_rejected() {
+    let fragment = replay_to_url_fragment(&replay());
+
+    for invalid in [
+        format!("{}+", &fragment[..fragment.len() - 1]),
+        format!("{}%3D", fragment),
+        fragment[..fragment.len() - 3].to_string(),
+        String::new(),
+    ] {
+        assert!(
+            matches!(
+                replay_from_url_fragment(&invalid),
+                Err(GameError::InvalidReplay(_))
+            ),
+            "{:?}",
+            invalid
+        );
+    }
+}
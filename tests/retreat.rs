@@ -0,0 +1,76 @@
+use stackrankdice::error::GameError;
+use stackrankdice::game::{GameAction, GameState, RegionId, MAX_DICE};
+
+mod common;
+
+/// Regions in a row: 0, 1 and 2 belong to player 0, 3 to player 1, 4 to player 0
+fn game_state() -> GameState {
+    common::row_game(&[(0, 3), (0, 2), (0, 5), (1, 4), (0, 1)], 2)
+}
+
+#[test]
+fn dice_move_between_connected_regions() {
+    let mut game_state = game_state();
+
+    assert_eq!(
+        game_state.board.move_dice(RegionId(2), RegionId(0), 4),
+        Ok(())
+    );
+
+    assert_eq!(game_state.board.regions[2].num_dice, 1);
+    assert_eq!(game_state.board.regions[0].num_dice, 7);
+}
+
+#[test]
+fn dice_cannot_move_to_disconnected_region() {
+    let mut game_state = game_state();
+
+    assert_eq!(
+        game_state.board.move_dice(RegionId(2), RegionId(4), 1),
+        Err(GameError::NotConnected { from: 2, to: 4 })
+    );
+    assert_eq!(game_state.board.regions[2].num_dice, 5);
+    assert_eq!(game_state.board.regions[4].num_dice, 1);
+}
+
+#[test]
+fn dice_cannot_exceed_cap_or_empty_region() {
+    let mut game_state = game_state();
+    game_state.board.regions[0].num_dice = MAX_DICE - 1;
+
+    assert_eq!(
+        game_state.board.move_dice(RegionId(2), RegionId(0), 2),
+        Err(GameError::DiceCapExceeded {
+            region: 0,
+            num_dice: MAX_DICE + 1
+        })
+    );
+    assert_eq!(
+        game_state.board.move_dice(RegionId(1), RegionId(0), 2),
+        Err(GameError::NotEnoughDice {
+            region: 1,
+            num_dice: 2
+        })
+    );
+    assert_eq!(
+        game_state.board.move_dice(RegionId(2), RegionId(0), 1),
+        Ok(())
+    );
+}
+
+#[test]
+fn retreat_costs_an_action() {
+    let mut game_state = game_state();
+
+    assert_eq!(game_state.retreat(RegionId(2), RegionId(1), 2), Ok(()));
+    assert!(game_state.has_acted(RegionId(2)));
+    assert_eq!(game_state.game_log[0].action, GameAction::Retreat);
+    assert_eq!(
+        game_state.retreat(RegionId(2), RegionId(1), 1),
+        Err(GameError::RegionAlreadyActed(2))
+    );
+    assert_eq!(
+        game_state.retreat(RegionId(3), RegionId(2), 1),
+        Err(GameError::NotYourRegion(3))
+    );
+}
@@ -0,0 +1,139 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{GameState, PlayerId, RegionId, RetreatRule, MAX_DICE};
+
+mod common;
+
+/// Attacker 0 next to defender 1, which has a friendly neighbour 2 and a far away region 3
+fn game_state(shelter_dice: usize) -> GameState {
+    let board = common::board(&[
+        ((0, 0), 0, 8),
+        ((1, 0), 1, 7),
+        ((2, 0), 1, shelter_dice),
+        ((10, 10), 1, 1),
+    ]);
+    GameState::from_board(board, 2)
+}
+
+fn dice_of_player(game_state: &GameState, player: usize) -> usize {
+    game_state
+        .board
+        .regions
+        .iter()
+        .filter(|r| r.owner == PlayerId(player))
+        .map(|r| r.num_dice)
+        .sum()
+}
+
+#[test]
+fn retreating_dice_land_on_adjacent_friendly_region() {
+    let mut rng = ChaCha20Rng::seed_from_u64(7);
+
+    for (shelter_dice, fraction, retreated) in [(2, 0.5, 3), (2, 1.0, 6), (6, 1.0, 2), (8, 1.0, 0)]
+    {
+        let mut game_state = game_state(shelter_dice);
+        let defender_dice = dice_of_player(&game_state, 1);
+
+        game_state
+            .apply_clash_with_retreat_rule(
+                RegionId(0),
+                RegionId(1),
+                true,
+                RetreatRule::Fraction(fraction),
+                &mut rng,
+            )
+            .unwrap();
+
+        let regions = &game_state.board.regions;
+        assert_eq!(regions[1].owner, PlayerId(0));
+        assert_eq!(regions[2].num_dice, shelter_dice + retreated);
+        assert!(regions[2].num_dice <= MAX_DICE);
+        assert_eq!(regions[3].num_dice, 1);
+
+        // Defender keeps exactly the retreated dice of the captured region
+        assert_eq!(
+            dice_of_player(&game_state, 1),
+            defender_dice - 7 + retreated
+        );
+    }
+}
+
+#[test]
+fn no_retreat_when_disabled_or_attacker_loses() {
+    let mut rng = ChaCha20Rng::seed_from_u64(7);
+
+    let mut state = game_state(2);
+    state
+        .apply_clash_with_retreat_rule(
+            RegionId(0),
+            RegionId(1),
+            true,
+            RetreatRule::Disabled,
+            &mut rng,
+        )
+        .unwrap();
+    assert_eq!(state.board.regions[2].num_dice, 2);
+
+    let mut state = game_state(2);
+    state
+        .apply_clash_with_retreat_rule(
+            RegionId(0),
+            RegionId(1),
+            false,
+            RetreatRule::Fraction(1.0),
+            &mut rng,
+        )
+        .unwrap();
+    assert_eq!(state.board.regions[2].num_dice, 2);
+}
+
+#[test]
+fn region_captured_by_a_single_dice_keeps_one_dice() {
+    let mut rng = ChaCha20Rng::seed_from_u64(7);
+    let mut game_state = game_state(2);
+    game_state.board.regions[0].num_dice = 1;
+
+    game_state
+        .apply_clash_with_retreat_rule(
+            RegionId(0),
+            RegionId(1),
+            true,
+            RetreatRule::Fraction(1.0),
+            &mut rng,
+        )
+        .unwrap();
+
+    let regions = &game_state.board.regions;
+    assert_eq!(regions[1].owner, PlayerId(0));
+    assert_eq!(regions[1].num_dice, 1);
+    assert_eq!(regions[2].num_dice, 8);
+    assert_eq!(game_state.board.validate(2), Ok(()));
+}
+
+#[test]
+fn dice_retreat_to_the_neighbour_with_most_room() {
+    let mut rng = ChaCha20Rng::seed_from_u64(7);
+    // Defender 1 between friendly regions 2 with 6 dice and 4 with 3 dice
+    let board = common::board(&[
+        ((0, 0), 0, 8),
+        ((1, 0), 1, 4),
+        ((2, 0), 1, 6),
+        ((10, 10), 1, 1),
+        ((1, 1), 1, 3),
+    ]);
+    let mut game_state = GameState::from_board(board, 2);
+
+    game_state
+        .apply_clash_with_retreat_rule(
+            RegionId(0),
+            RegionId(1),
+            true,
+            RetreatRule::Fraction(0.5),
+            &mut rng,
+        )
+        .unwrap();
+
+    let regions = &game_state.board.regions;
+    assert_eq!(regions[2].num_dice, 6);
+    assert_eq!(regions[4].num_dice, 5);
+}
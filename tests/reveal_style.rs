@@ -0,0 +1,26 @@
+#![cfg(feature = "render")]
+
+use std::time::Duration;
+
+use stackrankdice::ui::RevealStyle;
+
+#[test]
+fn sequential_reveals_defender_after_delay() {
+    let style = RevealStyle::Sequential {
+        delay: Duration::from_millis(800),
+    };
+
+    assert!(style.is_revealed(0, Duration::ZERO));
+    assert!(!style.is_revealed(1, Duration::ZERO));
+    assert!(!style.is_revealed(1, Duration::from_millis(799)));
+    assert!(style.is_revealed(1, Duration::from_millis(800)));
+}
+
+#[test]
+fn simultaneous_reveals_both_at_once() {
+    let style = RevealStyle::default();
+
+    assert_eq!(style, RevealStyle::Simultaneous);
+    assert!(style.is_revealed(0, Duration::ZERO));
+    assert!(style.is_revealed(1, Duration::ZERO));
+}
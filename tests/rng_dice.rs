@@ -0,0 +1,86 @@
+#![cfg(feature = "render")]
+
+use bevy::prelude::*;
+use bevy_dice::{DiceRollResult, DiceRollStartEvent};
+use bevy_rapier3d::prelude::RapierConfiguration;
+use stackrankdice::app::{build_app, GameSettings};
+use stackrankdice::game::{roll_dice, GameAction, GameLogEntry, GameState, PlayerId};
+use stackrankdice::rng_dice::DiceMode;
+use stackrankdice::tiered_prng::PrngDiceResource;
+
+fn app(dice_mode: DiceMode) -> App {
+    let mut app = App::new();
+    let settings = GameSettings {
+        dice_mode,
+        testing: true,
+        ..GameSettings::new(4242, 17, 2)
+    };
+    build_app(&mut app, settings, Vec::new());
+    app
+}
+
+#[test]
+fn rng_only_mode_resolves_clashes_without_physics() {
+    let mut app = app(DiceMode::RngOnly);
+
+    assert!(app.world.get_resource::<RapierConfiguration>().is_none());
+    assert!(app
+        .world
+        .get_resource::<Events<DiceRollStartEvent>>()
+        .is_some());
+    assert!(app.world.get_resource::<Events<DiceRollResult>>().is_some());
+
+    let game_state = app.world.resource::<GameState>().clone();
+    let (region_1, region_2) = game_state.clone().possible_moves()[0].clone();
+
+    let mut dice_prng = app.world.resource_mut::<PrngDiceResource>();
+    let mut log_entry = GameLogEntry {
+        region_1_dice_result: roll_dice(region_1.num_dice, &mut dice_prng.rng),
+        region_2_dice_result: roll_dice(region_2.num_dice, &mut dice_prng.rng),
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            0,
+            PlayerId(0),
+            region_1.clone(),
+            region_2.clone(),
+        )
+    };
+    log_entry.resolve();
+
+    let mut game_state = app.world.resource_mut::<GameState>();
+    game_state
+        .apply_clash(
+            region_1.id,
+            region_2.id,
+            log_entry.attacker_won,
+            &mut rand::thread_rng(),
+        )
+        .unwrap();
+
+    let winner = match log_entry.attacker_won {
+        true => region_1.owner,
+        false => region_2.owner,
+    };
+    assert_eq!(game_state.board.regions[region_1.id].owner, winner);
+    assert_eq!(game_state.board.regions[region_2.id].owner, winner);
+}
+
+#[test]
+fn physics_mode_loads_rapier() {
+    let app = app(DiceMode::Physics);
+
+    assert!(app.world.get_resource::<RapierConfiguration>().is_some());
+}
+
+#[test]
+fn dice_rolls_are_deterministic_for_env_seed() {
+    let mut rolls = Vec::new();
+    for _ in 0..2 {
+        let mut app = app(DiceMode::RngOnly);
+        let mut dice_prng = app.world.resource_mut::<PrngDiceResource>();
+        rolls.push(roll_dice(8, &mut dice_prng.rng));
+    }
+
+    assert_eq!(rolls[0], rolls[1]);
+    assert!(rolls[0].iter().all(|value| (1..=6).contains(value)));
+}
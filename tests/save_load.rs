@@ -0,0 +1,69 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use stackrankdice::game::{
+    generate_board, BoardConfig, GameState, PlayerId, ReinforcementReserve, Rules, TieRule,
+};
+use stackrankdice::tiered_prng::get_randomness;
+
+/// File in the temp dir no other test or test run writes to
+fn temp_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!(
+        "stackrankdice-{}-{}-{:?}.json",
+        name,
+        std::process::id(),
+        std::thread::current().id()
+    ))
+}
+
+#[test]
+fn saved_game_loads_back() {
+    let board = generate_board(3, &BoardConfig::default(), get_randomness(42));
+    let mut game_state = GameState::from_board(board, 3);
+    game_state.turn_of_player = PlayerId(1);
+    game_state.turn_counter = 5;
+
+    let path = temp_path("saved-game");
+    game_state
+        .save_to_path(&Rules::default(), &ReinforcementReserve::default(), &path)
+        .unwrap();
+    let loaded = GameState::load_from_path(&path).unwrap().game_state;
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.board.regions, game_state.board.regions);
+    assert_eq!(loaded.board.hexes, game_state.board.hexes);
+    assert_eq!(loaded.player_regions, game_state.player_regions);
+    assert_eq!(loaded.turn_of_player, PlayerId(1));
+    assert_eq!(loaded.turn_counter, 5);
+}
+
+#[test]
+fn saved_game_keeps_rules_and_reserve() {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(42));
+    let game_state = GameState::from_board(board, 2);
+    let rules = Rules {
+        tie_rule: TieRule::AttackerWins,
+        ..Default::default()
+    };
+    let mut reserve = ReinforcementReserve::default();
+    reserve.by_player.insert(PlayerId(1), 4);
+
+    let path = temp_path("saved-rules");
+    game_state.save_to_path(&rules, &reserve, &path).unwrap();
+    let loaded = GameState::load_from_path(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.rules, rules);
+    assert_eq!(loaded.reinforcement_reserve, reserve);
+}
+
+#[test]
+fn loading_garbage_fails() {
+    let path = temp_path("garbage-game");
+    fs::write(&path, "not a saved game").unwrap();
+    let loaded = GameState::load_from_path(&path);
+    fs::remove_file(&path).unwrap();
+
+    assert!(loaded.is_err());
+}
@@ -0,0 +1,8 @@
+#![cfg(feature = "render")]
+
+use stackrankdice::ui::seed_text;
+
+#[test]
+fn seed_text_names_both_seeds() {
+    assert_eq!(seed_text(4242, 17), "world:4242 env:17");
+}
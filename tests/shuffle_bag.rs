@@ -0,0 +1,26 @@
+use stackrankdice::game::DiceBag;
+use stackrankdice::tiered_prng::get_randomness;
+
+#[test]
+fn each_face_once_per_bag() {
+    let mut rng = get_randomness(42);
+    let mut bag = DiceBag::default();
+
+    for _ in 0..5 {
+        let mut faces: Vec<usize> = (0..6).map(|_| bag.draw(&mut rng)).collect();
+        faces.sort();
+
+        assert_eq!(faces, vec![1, 2, 3, 4, 5, 6]);
+    }
+}
+
+#[test]
+fn same_seed_draws_same_faces() {
+    let draw = |seed| {
+        let mut rng = get_randomness(seed);
+        let mut bag = DiceBag::default();
+        (0..18).map(|_| bag.draw(&mut rng)).collect::<Vec<usize>>()
+    };
+
+    assert_eq!(draw(7), draw(7));
+}
@@ -0,0 +1,60 @@
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::ai::{choose_move, AiStrategy};
+use stackrankdice::game::{
+    generate_board, BoardConfig, DiceKind, GameState, LargestArmyBonus, Region, Rules, TieRule,
+};
+use stackrankdice::simulate::{play_out, simulate_game};
+use stackrankdice::tiered_prng::get_randomness;
+
+fn greedy(game_state: &GameState, rng: &mut ChaCha20Rng) -> Option<(Region, Region)> {
+    choose_move(game_state, &AiStrategy::Greedy, rng).map(|(region_1, region_2)| {
+        (
+            game_state.board.regions[region_1].clone(),
+            game_state.board.regions[region_2].clone(),
+        )
+    })
+}
+
+fn game_state() -> GameState {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(42));
+    GameState::from_board(board, 2)
+}
+
+#[test]
+fn two_player_game_ends_with_single_owner() {
+    let mut game_state = game_state();
+
+    let outcome = play_out(&mut game_state, 7, &Rules::default(), &greedy);
+    let winner = outcome.winner.unwrap();
+
+    assert!(outcome.moves > 0);
+    assert_eq!(outcome.turns, game_state.turn_counter);
+    assert!(game_state
+        .board
+        .regions
+        .iter()
+        .all(|region| region.owner == winner));
+}
+
+#[test]
+fn same_seeds_give_same_outcome() {
+    assert_eq!(
+        simulate_game(42, 7, 2, &Rules::default(), DiceKind::default(), &greedy),
+        simulate_game(42, 7, 2, &Rules::default(), DiceKind::default(), &greedy)
+    );
+}
+
+#[test]
+fn simulated_game_follows_the_rules() {
+    let mut game_state = game_state();
+    let rules = Rules {
+        tie_rule: TieRule::Reroll,
+        largest_army_bonus: LargestArmyBonus(true),
+        ..Rules::default()
+    };
+
+    let outcome = play_out(&mut game_state, 7, &rules, &greedy);
+
+    assert!(outcome.winner.is_some());
+    assert!(game_state.game_log.iter().all(|gl| !gl.is_tie));
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "render")]
+
+use stackrankdice::game::{Board, GameState};
+use stackrankdice::snapshots::SnapshotHistory;
+
+fn game_state(turn_counter: usize) -> GameState {
+    let mut game_state = GameState::from_board(Board::default(), 2);
+    game_state.turn_counter = turn_counter;
+    game_state
+}
+
+#[test]
+fn ring_buffer_keeps_last_snapshots() {
+    let mut history = SnapshotHistory::new(3);
+    for turn_counter in 0..5 {
+        history.push(game_state(turn_counter));
+    }
+
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.snapshot_at(0).unwrap().turn_counter, 4);
+    assert_eq!(history.snapshot_at(2).unwrap().turn_counter, 2);
+    assert!(history.snapshot_at(3).is_none());
+}
+
+#[test]
+fn zero_capacity_keeps_nothing() {
+    let mut history = SnapshotHistory::new(0);
+    history.push(game_state(0));
+
+    assert!(history.is_empty());
+    assert!(history.snapshot_at(0).is_none());
+}
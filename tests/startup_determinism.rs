@@ -1,13 +1,19 @@
+#![cfg(feature = "render")]
+
 use bevy::prelude::*;
 use rand::Rng;
-use stackrankdice::app::build_app;
+use stackrankdice::app::{build_app, GameSettings};
 use stackrankdice::tiered_prng::PrngMapResource;
 
 #[test]
 fn fixed_world_undef_env_seed() {
     // Setup app
     let mut app = App::new();
-    build_app(&mut app, 4242, 0, 2, true);
+    let settings = GameSettings {
+        testing: true,
+        ..GameSettings::new(4242, 0, 2)
+    };
+    build_app(&mut app, settings, Vec::new());
 
     let mut map_prng = app.world.get_resource_mut::<PrngMapResource>().unwrap();
 
@@ -0,0 +1,26 @@
+#![cfg(feature = "render")]
+
+use stackrankdice::audio::AudioSettings;
+use stackrankdice::game::MAX_DICE;
+
+#[test]
+fn more_dice_sound_deeper_and_louder() {
+    let settings = AudioSettings::default();
+
+    for num_dice in 2..2 * MAX_DICE {
+        let fewer = settings.throw_sound(num_dice);
+        let more = settings.throw_sound(num_dice + 1);
+        assert!(more.playback_rate < fewer.playback_rate);
+        assert!(more.volume > fewer.volume);
+    }
+}
+
+#[test]
+fn unscaled_throw_sound_is_constant() {
+    let settings = AudioSettings {
+        scale_throw_sound: false,
+        ..Default::default()
+    };
+
+    assert_eq!(settings.throw_sound(2), settings.throw_sound(2 * MAX_DICE));
+}
@@ -0,0 +1,56 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{GameAction, GameLogEntry, GameState, PlayerId, RegionId, TieRule};
+
+mod common;
+
+/// Two bordering regions of players 1 and 2 with four dice each
+fn game_state() -> GameState {
+    common::row_game(&[(0, 4), (1, 4)], 2)
+}
+
+/// Resolve a clash of equal sums and return the owner of the defending region
+fn owner_after_tie(tie_rule: TieRule) -> (GameLogEntry, PlayerId) {
+    let mut game_state = game_state();
+    let mut log_entry = GameLogEntry {
+        region_1_dice_result: vec![1, 2, 3, 4],
+        region_2_dice_result: vec![4, 3, 2, 1],
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            0,
+            PlayerId(0),
+            game_state.board.regions[0].clone(),
+            game_state.board.regions[1].clone(),
+        )
+    };
+    log_entry.resolve_with_tie_rule(tie_rule);
+
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    game_state
+        .apply_clash(RegionId(0), RegionId(1), log_entry.attacker_won, &mut rng)
+        .unwrap();
+
+    (log_entry, game_state.board.regions[1].owner)
+}
+
+#[test]
+fn defender_keeps_region_by_default() {
+    assert_eq!(TieRule::default(), TieRule::DefenderWins);
+
+    let (log_entry, owner) = owner_after_tie(TieRule::DefenderWins);
+    assert!(log_entry.is_tie);
+    assert_eq!(owner, PlayerId(1));
+}
+
+#[test]
+fn attacker_takes_region() {
+    let (log_entry, owner) = owner_after_tie(TieRule::AttackerWins);
+    assert!(log_entry.is_tie && log_entry.attacker_won);
+    assert_eq!(owner, PlayerId(0));
+}
+
+#[test]
+fn reroll_leaves_tie_undecided() {
+    let (log_entry, _) = owner_after_tie(TieRule::Reroll);
+    assert!(log_entry.is_tie && !log_entry.attacker_won);
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "render")]
+
+use std::fs;
+
+use bevy::prelude::*;
+use stackrankdice::game::{generate_board, roll_dice, BoardConfig, GameState, Rules};
+use stackrankdice::tiered_prng::{
+    get_randomness, PrngDiceResource, PrngEnvResource, PrngPlugin, PrngResource,
+};
+use stackrankdice::trace::{record_trace, TraceLog};
+
+/// Frame at which the dice PRNG is drawn from once more than usual
+#[derive(Resource)]
+struct PerturbAt(Option<usize>);
+
+/// Play the first possible attack every frame
+fn play_move(
+    mut game_state: ResMut<GameState>,
+    mut dice_prng: ResMut<PrngDiceResource>,
+    mut env_prng: ResMut<PrngEnvResource>,
+    perturb_at: Res<PerturbAt>,
+    mut frame: Local<usize>,
+) {
+    if perturb_at.0 == Some(*frame) {
+        roll_dice(1, &mut dice_prng.rng);
+    }
+    *frame += 1;
+
+    if let Some((region_1, region_2)) = game_state.clone().possible_moves().first() {
+        let _ = game_state.play_clash(
+            region_1.id,
+            region_2.id,
+            &Rules::default(),
+            &mut dice_prng.rng,
+            &mut env_prng.rng,
+        );
+    }
+}
+
+/// Trace recorded over 10 frames of a game
+fn run(seed: u64, perturb_at: Option<usize>, trace_log: TraceLog) -> TraceLog {
+    let board = generate_board(2, &BoardConfig::default(), get_randomness(seed));
+    let mut app = App::new();
+    app.insert_resource(PrngResource {
+        world_seed: seed,
+        env_seed: 17,
+    })
+    .add_plugin(PrngPlugin)
+    .insert_resource(GameState::from_board(board, 2))
+    .insert_resource(PerturbAt(perturb_at))
+    .insert_resource(trace_log)
+    .add_system(play_move)
+    .add_system(record_trace.after(play_move));
+
+    for _ in 0..10 {
+        app.update();
+    }
+    app.world.remove_resource::<TraceLog>().unwrap()
+}
+
+#[test]
+fn same_seed_gives_same_trace() {
+    let trace_1 = run(42, None, TraceLog::default());
+    let trace_2 = run(42, None, TraceLog::default());
+
+    assert_eq!(trace_1.steps().len(), 10);
+    assert_eq!(trace_1.to_text(), trace_2.to_text());
+    assert_eq!(trace_1.first_divergence(&trace_2), None);
+}
+
+#[test]
+fn perturbed_run_diverges_at_perturbed_step() {
+    let trace_1 = run(42, None, TraceLog::default());
+    let trace_2 = run(42, Some(3), TraceLog::default());
+
+    assert_eq!(trace_1.first_divergence(&trace_2), Some(3));
+}
+
+#[test]
+fn trace_file_holds_every_step() {
+    let path = std::env::temp_dir().join(format!(
+        "stackrankdice-trace-{}-{:?}.txt",
+        std::process::id(),
+        std::thread::current().id()
+    ));
+    let trace_log = run(42, None, TraceLog::to_file(&path).unwrap());
+    let text = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(text, trace_log.to_text());
+}
@@ -0,0 +1,284 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+use stackrankdice::game::{
+    GameAction, GameLogEntry, GameState, PlayerId, PlayerRegions, RegionId, ReinforcementReserve,
+    RetreatRule, Rules, MAX_DICE,
+};
+
+mod common;
+
+/// Regions 0 and 3 of player 0 at both ends of a row, regions 1 and 2 of player 1 between them
+fn game_state() -> GameState {
+    let mut game_state = common::row_game(&[(0, 6), (1, 3), (1, 2), (0, 1)], 2);
+    for region in game_state.board.regions.iter_mut() {
+        region.fortify = 1;
+    }
+    game_state.turn_counter = 4;
+    game_state
+}
+
+/// Log and apply a clash of region 0 against region 1, the way a game does
+fn clash(
+    game_state: &mut GameState,
+    region_1_dice_result: Vec<usize>,
+    region_2_dice_result: Vec<usize>,
+    rules: &Rules,
+) {
+    let mut entry = GameLogEntry {
+        region_1_dice_result,
+        region_2_dice_result,
+        ..GameLogEntry::new(
+            GameAction::Clash,
+            game_state.turn_counter,
+            game_state.turn_of_player,
+            game_state.board.regions[0].clone(),
+            game_state.board.regions[1].clone(),
+        )
+    };
+    entry.resolve();
+    let attacker_won = entry.attacker_won;
+    game_state.game_log.push(entry);
+
+    let mut rng = ChaCha20Rng::seed_from_u64(42);
+    let capture_result = game_state
+        .resolve_clash(RegionId(0), RegionId(1), attacker_won, rules, &mut rng)
+        .unwrap();
+    game_state.log_capture(&capture_result);
+}
+
+/// Take back the last move under default rules, without any reserve
+fn undo(game_state: &mut GameState) -> Option<GameLogEntry> {
+    game_state.undo_last_move(&Rules::default(), &mut ReinforcementReserve::default())
+}
+
+fn end_turn(game_state: &mut GameState, reserve: &mut ReinforcementReserve) {
+    game_state.end_turn(
+        &Rules::default(),
+        reserve,
+        &mut ChaCha20Rng::seed_from_u64(7),
+    );
+}
+
+#[test]
+fn undo_restores_captured_region() {
+    let mut game_state = game_state();
+    let before = game_state.clone();
+
+    clash(
+        &mut game_state,
+        vec![6, 6, 6, 6, 6, 6],
+        vec![1, 1, 1],
+        &Rules::default(),
+    );
+    assert_eq!(game_state.board.regions[1].owner, PlayerId(0));
+
+    let entry = undo(&mut game_state).unwrap();
+
+    assert!(entry.attacker_won);
+    assert_eq!(game_state.board.regions, before.board.regions);
+    assert_eq!(game_state.player_regions, before.player_regions);
+    assert_eq!(
+        game_state
+            .board
+            .heat
+            .get(&RegionId(1))
+            .copied()
+            .unwrap_or(0.0),
+        0.0
+    );
+    assert!(game_state.game_log.is_empty());
+}
+
+#[test]
+fn undo_restores_failed_attack() {
+    let mut game_state = game_state();
+    let before = game_state.clone();
+
+    clash(
+        &mut game_state,
+        vec![1, 1, 1, 1, 1, 1],
+        vec![6, 6, 6],
+        &Rules::default(),
+    );
+    assert_eq!(game_state.board.regions[0].owner, PlayerId(1));
+
+    undo(&mut game_state).unwrap();
+
+    assert_eq!(game_state.board.regions, before.board.regions);
+    assert_eq!(game_state.player_regions, before.player_regions);
+    assert_eq!(
+        game_state
+            .board
+            .heat
+            .get(&RegionId(0))
+            .copied()
+            .unwrap_or(0.0),
+        0.0
+    );
+}
+
+#[test]
+fn undo_restores_shelter_of_retreating_dice() {
+    let mut game_state = game_state();
+    let before = game_state.clone();
+    let rules = Rules {
+        retreat_rule: RetreatRule::Fraction(0.5),
+        ..Default::default()
+    };
+
+    clash(
+        &mut game_state,
+        vec![6, 6, 6, 6, 6, 6],
+        vec![1, 1, 1],
+        &rules,
+    );
+    assert!(game_state.board.regions[2].num_dice > 2);
+
+    let entry = undo(&mut game_state).unwrap();
+
+    assert_eq!(entry.shelter.map(|r| r.id), Some(RegionId(2)));
+    assert_eq!(game_state.board.regions, before.board.regions);
+}
+
+#[test]
+fn undo_gives_back_the_turn_a_move_ended() {
+    let mut game_state = game_state();
+    let before = game_state.clone();
+    let mut reserve = ReinforcementReserve::default();
+
+    clash(
+        &mut game_state,
+        vec![6, 6, 6, 6, 6, 6],
+        vec![1, 1, 1],
+        &Rules::default(),
+    );
+    end_turn(&mut game_state, &mut reserve);
+    assert_eq!(game_state.turn_of_player, PlayerId(1));
+    assert_eq!(
+        game_state.game_log.last().map(|gl| gl.action),
+        Some(GameAction::Reinforce)
+    );
+
+    let entry = game_state
+        .undo_last_move(&Rules::default(), &mut reserve)
+        .unwrap();
+
+    assert_eq!(entry.action, GameAction::Clash);
+    assert_eq!(game_state.turn_of_player, PlayerId(0));
+    assert_eq!(game_state.turn_counter, before.turn_counter);
+    assert_eq!(game_state.board.regions, before.board.regions);
+    assert_eq!(game_state.player_regions, before.player_regions);
+    assert!(game_state.game_log.is_empty());
+    assert!(reserve.by_player.is_empty());
+}
+
+#[test]
+fn turn_ended_without_moves_is_given_back_alone() {
+    let mut game_state = game_state();
+    let before = game_state.clone();
+    let mut reserve = ReinforcementReserve::default();
+
+    game_state.fortify(RegionId(0)).unwrap();
+    let fortified = game_state.board.regions.clone();
+    end_turn(&mut game_state, &mut reserve);
+    end_turn(&mut game_state, &mut reserve);
+
+    // The turn of player 2 holds nothing but reinforcements
+    let entry = game_state
+        .undo_last_move(&Rules::default(), &mut reserve)
+        .unwrap();
+    assert_eq!(entry.action, GameAction::Reinforce);
+    assert_eq!(entry.turn_of_player, PlayerId(1));
+    assert_eq!(game_state.turn_of_player, PlayerId(1));
+
+    // Then the fortify ending the turn of player 1
+    let entry = game_state
+        .undo_last_move(&Rules::default(), &mut reserve)
+        .unwrap();
+    assert_eq!(entry.action, GameAction::Fortify);
+    assert_eq!(game_state.turn_of_player, PlayerId(0));
+    assert_eq!(game_state.turn_counter, before.turn_counter);
+    assert_eq!(game_state.board.regions, before.board.regions);
+    assert_ne!(fortified, before.board.regions);
+}
+
+#[test]
+fn undoing_an_ended_turn_restores_the_reserve() {
+    let mut game_state = game_state();
+    for region in game_state.board.regions.iter_mut() {
+        region.num_dice = MAX_DICE;
+    }
+    let before = game_state.clone();
+    let mut reserve = ReinforcementReserve::default();
+    reserve.by_player.insert(PlayerId(0), 3);
+
+    // Nothing fits, so every dice earned goes to the reserve
+    game_state.fortify(RegionId(0)).unwrap();
+    end_turn(&mut game_state, &mut reserve);
+    assert_eq!(reserve.by_player[&PlayerId(0)], 4);
+
+    game_state
+        .undo_last_move(&Rules::default(), &mut reserve)
+        .unwrap();
+
+    assert_eq!(reserve.by_player[&PlayerId(0)], 3);
+    assert_eq!(game_state.board.regions, before.board.regions);
+}
+
+#[test]
+fn nothing_is_undone_past_a_turn_that_left_no_log() {
+    let mut game_state = game_state();
+
+    clash(
+        &mut game_state,
+        vec![6, 6, 6, 6, 6, 6],
+        vec![1, 1, 1],
+        &Rules::default(),
+    );
+    game_state.set_turn(PlayerId(1)).unwrap();
+    game_state.turn_counter += 2;
+
+    assert!(undo(&mut game_state).is_none());
+    assert_eq!(game_state.game_log.len(), 1);
+    assert_eq!(game_state.board.regions[1].owner, PlayerId(0));
+}
+
+#[test]
+fn winning_move_stays() {
+    let mut game_state = game_state();
+    for region in game_state.board.regions.iter_mut().skip(2) {
+        region.owner = PlayerId(0);
+    }
+    game_state.player_regions = PlayerRegions::from_board(&game_state.board);
+
+    clash(
+        &mut game_state,
+        vec![6, 6, 6, 6, 6, 6],
+        vec![1, 1, 1],
+        &Rules::default(),
+    );
+    assert_eq!(game_state.is_game_over(), Some(PlayerId(0)));
+
+    assert!(undo(&mut game_state).is_none());
+    assert_eq!(game_state.game_log.len(), 1);
+}
+
+#[test]
+fn undo_restores_fortify_level() {
+    let mut game_state = game_state();
+
+    game_state.fortify(RegionId(0)).unwrap();
+    assert_eq!(game_state.board.regions[0].fortify, 2);
+
+    undo(&mut game_state).unwrap();
+
+    assert_eq!(game_state.board.regions[0].fortify, 1);
+    assert!(game_state.game_log.is_empty());
+}
+
+#[test]
+fn nothing_to_undo_on_empty_log() {
+    let mut game_state = game_state();
+
+    assert!(undo(&mut game_state).is_none());
+}